@@ -0,0 +1,117 @@
+use crate::headers::HeaderValue;
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// HTTP authentication schemes.
+///
+/// # Specifications
+///
+/// - [IANA HTTP Authentication Scheme Registry](https://www.iana.org/assignments/http-authschemes/http-authschemes.xhtml)
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AuthenticationScheme {
+    /// [RFC7617](https://tools.ietf.org/html/rfc7617) Basic auth
+    Basic,
+    /// [RFC6750](https://tools.ietf.org/html/rfc6750) Bearer auth
+    Bearer,
+    /// [RFC7616](https://tools.ietf.org/html/rfc7616) Digest auth
+    Digest,
+    /// [RFC7486](https://tools.ietf.org/html/rfc7486) HTTP Origin-Bound Authentication (HOBA)
+    Hoba,
+    /// [RFC8120](https://tools.ietf.org/html/rfc8120) Mutual auth
+    Mutual,
+    /// [RFC4559](https://tools.ietf.org/html/rfc4559) Negotiate auth
+    Negotiate,
+    /// [RFC5849](https://tools.ietf.org/html/rfc5849) OAuth
+    OAuth,
+    /// [RFC7804](https://tools.ietf.org/html/rfc7804) SCRAM SHA1 auth
+    ScramSha1,
+    /// [RFC7804](https://tools.ietf.org/html/rfc7804) SCRAM SHA256 auth
+    ScramSha256,
+    /// [RFC8292](https://tools.ietf.org/html/rfc8292) Vapid auth
+    Vapid,
+}
+
+impl AuthenticationScheme {
+    /// Get the name of the scheme as it's used in headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Basic => "Basic",
+            Self::Bearer => "Bearer",
+            Self::Digest => "Digest",
+            Self::Hoba => "HOBA",
+            Self::Mutual => "Mutual",
+            Self::Negotiate => "Negotiate",
+            Self::OAuth => "OAuth",
+            Self::ScramSha1 => "SCRAM-SHA-1",
+            Self::ScramSha256 => "SCRAM-SHA-256",
+            Self::Vapid => "vapid",
+        }
+    }
+}
+
+impl FromStr for AuthenticationScheme {
+    type Err = Error;
+
+    /// Parse a scheme name. Names are matched case-insensitively.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let schemes = [
+            Self::Basic,
+            Self::Bearer,
+            Self::Digest,
+            Self::Hoba,
+            Self::Mutual,
+            Self::Negotiate,
+            Self::OAuth,
+            Self::ScramSha1,
+            Self::ScramSha256,
+            Self::Vapid,
+        ];
+        match schemes
+            .iter()
+            .find(|scheme| scheme.as_str().eq_ignore_ascii_case(s))
+        {
+            Some(scheme) => Ok(*scheme),
+            None => {
+                let msg = format!("Unsupported authentication scheme: {}", s);
+                Err(Error::from_str(StatusCode::BadRequest, msg))
+            }
+        }
+    }
+}
+
+impl Display for AuthenticationScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<AuthenticationScheme> for HeaderValue {
+    fn from(scheme: AuthenticationScheme) -> Self {
+        // SAFETY: all scheme names are valid ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(scheme.as_str().as_bytes().to_vec()) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        assert_eq!(
+            "basic".parse::<AuthenticationScheme>()?,
+            AuthenticationScheme::Basic
+        );
+        assert_eq!(
+            "scram-sha-256".parse::<AuthenticationScheme>()?,
+            AuthenticationScheme::ScramSha256
+        );
+        let err = "Nori".parse::<AuthenticationScheme>().unwrap_err();
+        assert_eq!(err.status(), 400);
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use crate::auth::{AuthenticationScheme, Authorization};
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, AUTHORIZATION};
+use crate::{Error, StatusCode};
+
+use std::option;
+
+/// HTTP Bearer authorization.
+///
+/// # Specifications
+///
+/// - [RFC 6750, section 2.1: Authorization Request Header Field](https://tools.ietf.org/html/rfc6750#section-2.1)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Request;
+/// use http_types::auth::BearerToken;
+///
+/// let token = BearerToken::new("mF_9.B5f-4.1JqM");
+///
+/// let mut req = Request::new(http_types::Method::Get, "https://example.com");
+/// token.apply(&mut req);
+/// assert_eq!(req["Authorization"], "Bearer mF_9.B5f-4.1JqM");
+///
+/// let token = BearerToken::from_headers(req)?.unwrap();
+/// assert_eq!(token.token(), "mF_9.B5f-4.1JqM");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BearerToken {
+    token: String,
+}
+
+impl BearerToken {
+    /// Create a new instance of `BearerToken`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the token isn't valid `token68` syntax.
+    pub fn new(token: impl Into<String>) -> Self {
+        let token = token.into();
+        assert!(is_token68(&token), "Bearer tokens should be token68");
+        Self { token }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header doesn't use the `Bearer` scheme, or if the token isn't valid.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let auth = match Authorization::from_headers(headers)? {
+            Some(auth) => auth,
+            None => return Ok(None),
+        };
+
+        let scheme = auth.scheme();
+        if !matches!(scheme, AuthenticationScheme::Bearer) {
+            let msg = format!("Expected bearer auth scheme found `{}`", scheme);
+            return Err(Error::from_str(StatusCode::BadRequest, msg));
+        }
+
+        let token = auth.credentials();
+        if !is_token68(token) {
+            let msg = "Bearer token contains invalid characters";
+            return Err(Error::from_str(StatusCode::BadRequest, msg));
+        }
+        Ok(Some(Self {
+            token: token.to_owned(),
+        }))
+    }
+
+    /// Sets the header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        AUTHORIZATION
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let auth = Authorization::new(AuthenticationScheme::Bearer, self.token.clone());
+        auth.value()
+    }
+
+    /// Get the token.
+    pub fn token(&self) -> &str {
+        self.token.as_str()
+    }
+}
+
+impl ToHeaderValues for BearerToken {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+/// Returns `true` if the string matches the `token68` syntax.
+pub(crate) fn is_token68(s: &str) -> bool {
+    let s = s.trim_end_matches('=');
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-._~+/".contains(&b))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let token = BearerToken::new("dGhpcyBpcyBub3QgYSB0b2tlbg==");
+
+        let mut headers = Headers::new();
+        token.apply(&mut headers);
+
+        let token = BearerToken::from_headers(headers)?.unwrap();
+        assert_eq!(token.token(), "dGhpcyBpcyBub3QgYSB0b2tlbg==");
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for value in &["Basic bm9yaTpzZWNyZXRfZmlzaCEh", "Bearer a,b", "Bearer =="] {
+            let mut headers = Headers::new();
+            headers.insert(AUTHORIZATION, *value);
+            let err = BearerToken::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
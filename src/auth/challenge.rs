@@ -0,0 +1,217 @@
+use crate::auth::bearer_token::is_token68;
+use crate::auth::AuthenticationScheme;
+use crate::utils::{is_token, quote, unquote};
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+
+/// Parameters which are sent as tokens rather than quoted strings.
+///
+/// RFC 7616 defines these Digest parameters without quotes, and some clients
+/// don't accept them quoted. Every other parameter is sent as a quoted string,
+/// which RFC 7235 requires for `realm`.
+const UNQUOTED_PARAMS: [&str; 3] = ["algorithm", "stale", "userhash"];
+
+/// An authentication challenge, as sent in the `WWW-Authenticate` header.
+///
+/// A challenge consists of an authentication scheme, followed by either a
+/// `token68` or a list of parameters. Parameter names are case-insensitive,
+/// and are stored lowercased.
+///
+/// # Specifications
+///
+/// - [RFC 7235, section 2.1: Challenge and Response](https://tools.ietf.org/html/rfc7235#section-2.1)
+///
+/// # Examples
+///
+/// ```
+/// use http_types::auth::{AuthenticationScheme, Challenge};
+///
+/// let mut challenge = Challenge::new(AuthenticationScheme::Basic);
+/// challenge.set_realm("Nori's \"secret\" stash");
+/// challenge.insert_param("charset", "UTF-8");
+///
+/// assert_eq!(
+///     challenge.to_string(),
+///     r#"Basic realm="Nori's \"secret\" stash", charset="UTF-8""#
+/// );
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Challenge {
+    scheme: AuthenticationScheme,
+    token68: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+impl Challenge {
+    /// Create a new challenge for a scheme.
+    pub fn new(scheme: AuthenticationScheme) -> Self {
+        Self {
+            scheme,
+            token68: None,
+            params: vec![],
+        }
+    }
+
+    /// Get the authentication scheme.
+    pub fn scheme(&self) -> AuthenticationScheme {
+        self.scheme
+    }
+
+    /// Get the protection space of the challenge.
+    pub fn realm(&self) -> Option<&str> {
+        self.param("realm")
+    }
+
+    /// Set the protection space of the challenge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the realm isn't ASCII.
+    pub fn set_realm(&mut self, realm: impl Into<String>) {
+        self.insert_param("realm", realm);
+    }
+
+    /// Get the value of a parameter.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Insert a parameter, replacing any existing parameter with the same name.
+    ///
+    /// Setting a parameter removes the `token68` of the challenge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name isn't a token, or if the value isn't ASCII.
+    pub fn insert_param(&mut self, name: impl AsRef<str>, value: impl Into<String>) {
+        let name = name.as_ref().to_ascii_lowercase();
+        let value = value.into();
+        assert!(is_token(&name), "Auth param names should be tokens");
+        assert!(value.is_ascii(), "Auth param values should be ASCII");
+
+        self.token68 = None;
+        match self.params.iter_mut().find(|(key, _)| *key == name) {
+            Some(param) => param.1 = value,
+            None => self.params.push((name, value)),
+        }
+    }
+
+    /// Remove a parameter, returning its value if it was present.
+    pub fn remove_param(&mut self, name: &str) -> Option<String> {
+        let index = self
+            .params
+            .iter()
+            .position(|(key, _)| key.eq_ignore_ascii_case(name))?;
+        Some(self.params.remove(index).1)
+    }
+
+    /// An iterator over the parameters, in the order they were inserted.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Get the `token68` of the challenge.
+    pub fn token68(&self) -> Option<&str> {
+        self.token68.as_deref()
+    }
+
+    /// Set the `token68` of the challenge, removing all parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the token isn't valid `token68` syntax.
+    pub fn set_token68(&mut self, token: impl Into<String>) {
+        let token = token.into();
+        assert!(is_token68(&token), "Challenge tokens should be token68");
+        self.params.clear();
+        self.token68 = Some(token);
+    }
+
+    /// Parse the value of a parameter, which is either a token or a quoted
+    /// string.
+    pub(crate) fn parse_param(name: &str, value: &str) -> crate::Result<(String, String)> {
+        let name = name.trim();
+        let value = value.trim();
+        let value = if value.starts_with('"') {
+            unquote(value)
+        } else if is_token(value) {
+            Some(value.to_owned())
+        } else {
+            None
+        };
+        match value {
+            Some(value) if is_token(name) => Ok((name.to_ascii_lowercase(), value)),
+            _ => {
+                let msg = format!("Invalid auth param: {}", name);
+                Err(Error::from_str(StatusCode::BadRequest, msg))
+            }
+        }
+    }
+}
+
+impl Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.scheme)?;
+        if let Some(token) = &self.token68 {
+            return write!(f, " {}", token);
+        }
+        for (n, (name, value)) in self.params.iter().enumerate() {
+            let separator = if n == 0 { " " } else { ", " };
+            if UNQUOTED_PARAMS.contains(&name.as_str()) && is_token(value) {
+                write!(f, "{}{}={}", separator, name, value)?;
+            } else {
+                write!(f, "{}{}={}", separator, name, quote(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn params() {
+        let mut challenge = Challenge::new(AuthenticationScheme::Digest);
+        challenge.set_realm("http-auth@example.org");
+        challenge.insert_param("Algorithm", "SHA-256");
+        challenge.insert_param("stale", "TRUE");
+        challenge.insert_param("algorithm", "MD5");
+        assert_eq!(challenge.param("ALGORITHM"), Some("MD5"));
+        assert_eq!(
+            challenge.to_string(),
+            r#"Digest realm="http-auth@example.org", algorithm=MD5, stale=TRUE"#
+        );
+
+        assert_eq!(challenge.remove_param("stale"), Some("TRUE".to_owned()));
+        assert_eq!(challenge.params().count(), 2);
+    }
+
+    #[test]
+    fn token68() {
+        let mut challenge = Challenge::new(AuthenticationScheme::Negotiate);
+        challenge.insert_param("realm", "a");
+        challenge.set_token68("YIIB5gYGKwYBBQUCoIIB2jCC==");
+        assert_eq!(challenge.realm(), None);
+        assert_eq!(
+            challenge.to_string(),
+            "Negotiate YIIB5gYGKwYBBQUCoIIB2jCC=="
+        );
+    }
+
+    #[test]
+    fn parse_param() -> crate::Result<()> {
+        let param = Challenge::parse_param("Realm", r#""a \"b\"""#)?;
+        assert_eq!(param, ("realm".to_owned(), r#"a "b""#.to_owned()));
+        let err = Challenge::parse_param("realm", "a b").unwrap_err();
+        assert_eq!(err.status(), 400);
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{Error, StatusCode};
+
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+use std::fmt::{self, Display, Write};
+use std::str::FromStr;
+
+/// Hash algorithms used by Digest authentication.
+///
+/// # Specifications
+///
+/// - [RFC 7616, section 3.3: The WWW-Authenticate Response Header Field](https://tools.ietf.org/html/rfc7616#section-3.3)
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DigestAlgorithm {
+    /// The MD5 algorithm, which is the default for backwards compatibility.
+    Md5,
+    /// The SHA-256 algorithm.
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    /// Get the name of the algorithm as it's used in headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Sha256 => "SHA-256",
+        }
+    }
+
+    /// Hash the input, returning the lowercase hex encoded digest.
+    pub fn hash(&self, input: impl AsRef<[u8]>) -> String {
+        match self {
+            Self::Md5 => hex(&Md5::digest(input.as_ref())),
+            Self::Sha256 => hex(&Sha256::digest(input.as_ref())),
+        }
+    }
+
+    /// Compute the `HA1` hash of a user's credentials.
+    ///
+    /// Servers can store this hash instead of the password to verify
+    /// `DigestCredentials`.
+    pub fn ha1(&self, username: &str, realm: &str, password: &str) -> String {
+        self.hash(format!("{}:{}:{}", username, realm, password))
+    }
+}
+
+impl FromStr for DigestAlgorithm {
+    type Err = Error;
+
+    /// Parse an algorithm name. Names are matched case-insensitively.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("MD5") {
+            Ok(Self::Md5)
+        } else if s.eq_ignore_ascii_case("SHA-256") {
+            Ok(Self::Sha256)
+        } else {
+            let msg = format!("Unsupported digest algorithm: {}", s);
+            Err(Error::from_str(StatusCode::BadRequest, msg))
+        }
+    }
+}
+
+impl Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The quality of protection applied to a Digest authentication response.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Qop {
+    /// Authentication.
+    Auth,
+    /// Authentication with integrity protection of the body.
+    AuthInt,
+}
+
+impl Qop {
+    /// Get the name of the quality of protection as it's used in headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auth => "auth",
+            Self::AuthInt => "auth-int",
+        }
+    }
+}
+
+impl FromStr for Qop {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("auth") {
+            Ok(Self::Auth)
+        } else if s.eq_ignore_ascii_case("auth-int") {
+            Ok(Self::AuthInt)
+        } else {
+            let msg = format!("Unsupported quality of protection: {}", s);
+            Err(Error::from_str(StatusCode::BadRequest, msg))
+        }
+    }
+}
+
+impl Display for Qop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        write!(output, "{:02x}", byte).unwrap();
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hashes() {
+        assert_eq!(
+            DigestAlgorithm::Md5.hash("Mufasa:http-auth@example.org:Circle of Life"),
+            "3d78807defe7de2157e2b0b6573a855f"
+        );
+        assert_eq!(
+            DigestAlgorithm::Sha256.hash(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        assert_eq!(
+            "sha-256".parse::<DigestAlgorithm>()?,
+            DigestAlgorithm::Sha256
+        );
+        assert_eq!("AUTH-INT".parse::<Qop>()?, Qop::AuthInt);
+        let err = "SHA-512-256".parse::<DigestAlgorithm>().unwrap_err();
+        assert_eq!(err.status(), 400);
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+use crate::auth::{AuthenticationScheme, Challenge, DigestAlgorithm, Qop, WwwAuthenticate};
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, WWW_AUTHENTICATE};
+use crate::{Error, StatusCode};
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+use std::option;
+
+/// A Digest authentication challenge.
+///
+/// # Specifications
+///
+/// - [RFC 7616, section 3.3: The WWW-Authenticate Response Header Field](https://tools.ietf.org/html/rfc7616#section-3.3)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::{Response, StatusCode};
+/// use http_types::auth::{DigestAlgorithm, DigestChallenge};
+///
+/// let mut challenge = DigestChallenge::new("http-auth@example.org");
+/// challenge.set_algorithm(DigestAlgorithm::Sha256);
+///
+/// let mut res = Response::new(StatusCode::Unauthorized);
+/// challenge.apply(&mut res);
+///
+/// let parsed = DigestChallenge::from_headers(res)?.unwrap();
+/// assert_eq!(parsed, challenge);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DigestChallenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: DigestAlgorithm,
+    qop: Vec<Qop>,
+    stale: bool,
+}
+
+impl DigestChallenge {
+    /// Create a new challenge for a realm, with a randomly generated nonce.
+    ///
+    /// The challenge offers the `auth` quality of protection, and uses the
+    /// MD5 algorithm.
+    pub fn new(realm: impl Into<String>) -> Self {
+        let nonce = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .collect();
+        Self {
+            realm: realm.into(),
+            nonce,
+            opaque: None,
+            algorithm: DigestAlgorithm::Md5,
+            qop: vec![Qop::Auth],
+            stale: false,
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// Returns `None` if the headers don't contain a Digest challenge.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let www_auth = match WwwAuthenticate::from_headers(headers)? {
+            Some(www_auth) => www_auth,
+            None => return Ok(None),
+        };
+        match www_auth.get(AuthenticationScheme::Digest) {
+            Some(challenge) => Self::from_challenge(challenge).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Create a new instance from a generic challenge.
+    ///
+    /// Unknown quality of protection values are skipped.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// challenge doesn't use the Digest scheme, is missing the `realm` or
+    /// `nonce` parameters, or uses an unsupported algorithm.
+    pub fn from_challenge(challenge: &Challenge) -> crate::Result<Self> {
+        if challenge.scheme() != AuthenticationScheme::Digest {
+            let msg = format!("Expected digest auth scheme found `{}`", challenge.scheme());
+            return Err(Error::from_str(StatusCode::BadRequest, msg));
+        }
+
+        let (realm, nonce) = match (challenge.realm(), challenge.param("nonce")) {
+            (Some(realm), Some(nonce)) => (realm.to_owned(), nonce.to_owned()),
+            _ => {
+                let msg = "Digest challenges should contain a realm and a nonce";
+                return Err(Error::from_str(StatusCode::BadRequest, msg));
+            }
+        };
+        let algorithm = match challenge.param("algorithm") {
+            Some(algorithm) => algorithm.parse()?,
+            None => DigestAlgorithm::Md5,
+        };
+        let qop = challenge
+            .param("qop")
+            .unwrap_or("")
+            .split(',')
+            .filter_map(|qop| qop.parse().ok())
+            .collect();
+        let stale =
+            matches!(challenge.param("stale"), Some(stale) if stale.eq_ignore_ascii_case("true"));
+
+        Ok(Self {
+            realm,
+            nonce,
+            opaque: challenge.param("opaque").map(ToOwned::to_owned),
+            algorithm,
+            qop,
+            stale,
+        })
+    }
+
+    /// Sets the `WWW-Authenticate` header, replacing any other challenges.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        WWW_AUTHENTICATE
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = Challenge::from(self.clone()).to_string();
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Get the protection space.
+    pub fn realm(&self) -> &str {
+        &self.realm
+    }
+
+    /// Get the nonce.
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    /// Set the nonce.
+    pub fn set_nonce(&mut self, nonce: impl Into<String>) {
+        self.nonce = nonce.into();
+    }
+
+    /// Get the opaque data, which clients return unchanged.
+    pub fn opaque(&self) -> Option<&str> {
+        self.opaque.as_deref()
+    }
+
+    /// Set the opaque data, which clients return unchanged.
+    pub fn set_opaque(&mut self, opaque: Option<String>) {
+        self.opaque = opaque;
+    }
+
+    /// Get the hash algorithm.
+    pub fn algorithm(&self) -> DigestAlgorithm {
+        self.algorithm
+    }
+
+    /// Set the hash algorithm.
+    pub fn set_algorithm(&mut self, algorithm: DigestAlgorithm) {
+        self.algorithm = algorithm;
+    }
+
+    /// Get the offered qualities of protection.
+    ///
+    /// An empty list means the challenge is compatible with RFC 2069.
+    pub fn qop(&self) -> &[Qop] {
+        &self.qop
+    }
+
+    /// Set the offered qualities of protection.
+    pub fn set_qop(&mut self, qop: Vec<Qop>) {
+        self.qop = qop;
+    }
+
+    /// Returns `true` if the previous request was rejected because its nonce
+    /// was stale.
+    pub fn stale(&self) -> bool {
+        self.stale
+    }
+
+    /// Set whether the previous request was rejected because its nonce was
+    /// stale, which tells the client to retry without prompting the user.
+    pub fn set_stale(&mut self, stale: bool) {
+        self.stale = stale;
+    }
+}
+
+impl From<DigestChallenge> for Challenge {
+    fn from(digest: DigestChallenge) -> Self {
+        let mut challenge = Challenge::new(AuthenticationScheme::Digest);
+        challenge.set_realm(digest.realm);
+        if !digest.qop.is_empty() {
+            let qop: Vec<_> = digest.qop.iter().map(Qop::as_str).collect();
+            challenge.insert_param("qop", qop.join(", "));
+        }
+        challenge.insert_param("algorithm", digest.algorithm.as_str());
+        challenge.insert_param("nonce", digest.nonce);
+        if let Some(opaque) = digest.opaque {
+            challenge.insert_param("opaque", opaque);
+        }
+        if digest.stale {
+            challenge.insert_param("stale", "true");
+        }
+        challenge
+    }
+}
+
+impl ToHeaderValues for DigestChallenge {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn parse_rfc_example() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(
+            WWW_AUTHENTICATE,
+            concat!(
+                r#"Digest realm="http-auth@example.org", qop="auth, auth-int", "#,
+                r#"algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", "#,
+                r#"opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS", "#,
+                r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=MD5"#,
+            ),
+        );
+
+        let challenge = DigestChallenge::from_headers(headers)?.unwrap();
+        assert_eq!(challenge.realm(), "http-auth@example.org");
+        assert_eq!(challenge.algorithm(), DigestAlgorithm::Sha256);
+        assert_eq!(challenge.qop(), &[Qop::Auth, Qop::AuthInt]);
+        assert_eq!(
+            challenge.opaque(),
+            Some("FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS")
+        );
+        assert!(!challenge.stale());
+        Ok(())
+    }
+
+    #[test]
+    fn encode() {
+        let mut challenge = DigestChallenge::new("nori");
+        challenge.set_nonce("abc");
+        challenge.set_stale(true);
+        assert_eq!(
+            challenge.value(),
+            r#"Digest realm="nori", qop="auth", algorithm=MD5, nonce="abc", stale=true"#
+        );
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for value in &[
+            r#"Digest realm="a""#,
+            r#"Digest realm="a", nonce="b", algorithm=x"#,
+        ] {
+            let mut headers = Headers::new();
+            headers.insert(WWW_AUTHENTICATE, *value);
+            let err = DigestChallenge::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
@@ -0,0 +1,507 @@
+use crate::auth::{
+    AuthenticationScheme, Authorization, Challenge, DigestAlgorithm, DigestChallenge, Qop,
+};
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, AUTHORIZATION};
+use crate::utils::{decode_ext_value, encode_ext_value, is_printable, quote, split_quoted};
+use crate::{Error, Method, StatusCode};
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+use std::fmt::Write;
+use std::option;
+
+/// Digest authorization credentials.
+///
+/// Clients create credentials from the server's `DigestChallenge`, and then
+/// compute the `response` hash using the user's password. Servers parse the
+/// credentials from the `Authorization` header, and verify them against the
+/// stored `HA1` hash of the user's credentials.
+///
+/// Usernames which aren't printable ASCII are sent in the `username*`
+/// parameter.
+///
+/// # Specifications
+///
+/// - [RFC 7616, section 3.4: The Authorization Header Field](https://tools.ietf.org/html/rfc7616#section-3.4)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::{Method, Request, Url};
+/// use http_types::auth::{DigestAlgorithm, DigestChallenge, DigestCredentials};
+///
+/// // The server challenges the client.
+/// let challenge = DigestChallenge::new("http-auth@example.org");
+///
+/// // The client responds to the challenge.
+/// let mut credentials = DigestCredentials::new(&challenge, "Mufasa", "/dir/index.html");
+/// credentials.compute("Circle of Life", Method::Get, b"");
+///
+/// let mut req = Request::new(Method::Get, "https://example.org/dir/index.html");
+/// credentials.apply(&mut req);
+///
+/// // The server verifies the response against the stored hash.
+/// let ha1 = DigestAlgorithm::Md5.ha1("Mufasa", "http-auth@example.org", "Circle of Life");
+/// let credentials = DigestCredentials::from_headers(&req)?.unwrap();
+/// assert!(credentials.verify(&challenge, &ha1, req.method(), b""));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DigestCredentials {
+    username: String,
+    realm: String,
+    nonce: String,
+    uri: String,
+    algorithm: DigestAlgorithm,
+    qop: Option<Qop>,
+    nc: u32,
+    cnonce: Option<String>,
+    opaque: Option<String>,
+    response: String,
+}
+
+impl DigestCredentials {
+    /// Create new credentials in response to a challenge.
+    ///
+    /// The `auth` quality of protection is used when the challenge offers
+    /// it, `auth-int` otherwise. The nonce count starts at `1` and a random
+    /// client nonce is generated. Call `compute` to compute the response.
+    pub fn new(
+        challenge: &DigestChallenge,
+        username: impl Into<String>,
+        uri: impl Into<String>,
+    ) -> Self {
+        let qop = match challenge.qop() {
+            qop if qop.contains(&Qop::Auth) => Some(Qop::Auth),
+            qop if qop.contains(&Qop::AuthInt) => Some(Qop::AuthInt),
+            _ => None,
+        };
+        let cnonce = qop.map(|_| {
+            rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(32)
+                .collect()
+        });
+        Self {
+            username: username.into(),
+            realm: challenge.realm().to_owned(),
+            nonce: challenge.nonce().to_owned(),
+            uri: uri.into(),
+            algorithm: challenge.algorithm(),
+            qop,
+            nc: 1,
+            cnonce,
+            opaque: challenge.opaque().map(ToOwned::to_owned),
+            response: String::new(),
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header doesn't use the `Digest` scheme, or if a required parameter is
+    /// missing or malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let auth = match Authorization::from_headers(headers)? {
+            Some(auth) => auth,
+            None => return Ok(None),
+        };
+
+        let scheme = auth.scheme();
+        if !matches!(scheme, AuthenticationScheme::Digest) {
+            let msg = format!("Expected digest auth scheme found `{}`", scheme);
+            return Err(Error::from_str(StatusCode::BadRequest, msg));
+        }
+
+        let mut params = Challenge::new(AuthenticationScheme::Digest);
+        for item in split_quoted(auth.credentials(), ',') {
+            let (name, value) = match item.find('=') {
+                Some(index) => Challenge::parse_param(&item[..index], &item[index + 1..])?,
+                None => {
+                    let msg = format!("Invalid digest auth param: {}", item);
+                    return Err(Error::from_str(StatusCode::BadRequest, msg));
+                }
+            };
+            params.insert_param(name, value);
+        }
+
+        let required = |name: &str| match params.param(name) {
+            Some(value) => Ok(value.to_owned()),
+            None => {
+                let msg = format!("Digest credentials are missing the `{}` param", name);
+                Err(Error::from_str(StatusCode::BadRequest, msg))
+            }
+        };
+        let username = match params.param("username*") {
+            Some(value) => match decode_ext_value(value) {
+                Some(username) => username,
+                None => {
+                    let msg = format!("Invalid digest username: {}", value);
+                    return Err(Error::from_str(StatusCode::BadRequest, msg));
+                }
+            },
+            None => required("username")?,
+        };
+        let realm = required("realm")?;
+        let nonce = required("nonce")?;
+        let uri = required("uri")?;
+        let response = required("response")?;
+
+        let algorithm = match params.param("algorithm") {
+            Some(algorithm) => algorithm.parse()?,
+            None => DigestAlgorithm::Md5,
+        };
+        let qop: Option<Qop> = params.param("qop").map(str::parse).transpose()?;
+        let (nc, cnonce) = match qop {
+            Some(_) => {
+                let nc = required("nc")?;
+                let nc = match u32::from_str_radix(&nc, 16) {
+                    Ok(value) if nc.len() == 8 => value,
+                    _ => {
+                        let msg = format!("Invalid digest nonce count: {}", nc);
+                        return Err(Error::from_str(StatusCode::BadRequest, msg));
+                    }
+                };
+                (nc, Some(required("cnonce")?))
+            }
+            None => (0, None),
+        };
+
+        Ok(Some(Self {
+            username,
+            realm,
+            nonce,
+            uri,
+            algorithm,
+            qop,
+            nc,
+            cnonce,
+            opaque: params.param("opaque").map(ToOwned::to_owned),
+            response,
+        }))
+    }
+
+    /// Sets the header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        AUTHORIZATION
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let username = match is_printable(&self.username) {
+            true => format!("username={}", quote(&self.username)),
+            false => format!("username*={}", encode_ext_value(&self.username)),
+        };
+        let mut output = format!(
+            "Digest {}, realm={}, uri={}, algorithm={}, nonce={}",
+            username,
+            quote(&self.realm),
+            quote(&self.uri),
+            self.algorithm,
+            quote(&self.nonce),
+        );
+        if let (Some(qop), Some(cnonce)) = (self.qop, &self.cnonce) {
+            write!(
+                output,
+                ", nc={:08x}, cnonce={}, qop={}",
+                self.nc,
+                quote(cnonce),
+                qop
+            )
+            .unwrap();
+        }
+        write!(output, ", response={}", quote(&self.response)).unwrap();
+        if let Some(opaque) = &self.opaque {
+            write!(output, ", opaque={}", quote(opaque)).unwrap();
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Compute the `response` hash from the user's password.
+    ///
+    /// The body is only used by the `auth-int` quality of protection.
+    pub fn compute(&mut self, password: &str, method: Method, body: &[u8]) {
+        let ha1 = self.algorithm.ha1(&self.username, &self.realm, password);
+        self.response = self.expected_response(&ha1, method, body);
+    }
+
+    /// Verify the credentials of a request against the challenge that was
+    /// sent, and the stored `HA1` hash of the user's credentials.
+    ///
+    /// The realm, nonce, opaque data and algorithm of the credentials should
+    /// match the challenge. Checking the nonce count for replays, and that
+    /// the `uri` matches the request target, is up to the caller.
+    pub fn verify(
+        &self,
+        challenge: &DigestChallenge,
+        ha1: &str,
+        method: Method,
+        body: &[u8],
+    ) -> bool {
+        let qop_offered = match self.qop {
+            Some(qop) => challenge.qop().contains(&qop),
+            None => challenge.qop().is_empty(),
+        };
+        let expected = self.expected_response(ha1, method, body);
+
+        self.realm == challenge.realm()
+            && self.nonce == challenge.nonce()
+            && self.opaque.as_deref() == challenge.opaque()
+            && self.algorithm == challenge.algorithm()
+            && qop_offered
+            && constant_time_eq(expected.as_bytes(), self.response.as_bytes())
+    }
+
+    fn expected_response(&self, ha1: &str, method: Method, body: &[u8]) -> String {
+        let hash = |input: String| self.algorithm.hash(input);
+        let ha2 = match self.qop {
+            Some(Qop::AuthInt) => hash(format!(
+                "{}:{}:{}",
+                method,
+                self.uri,
+                self.algorithm.hash(body)
+            )),
+            _ => hash(format!("{}:{}", method, self.uri)),
+        };
+        match (self.qop, &self.cnonce) {
+            (Some(qop), Some(cnonce)) => hash(format!(
+                "{}:{}:{:08x}:{}:{}:{}",
+                ha1, self.nonce, self.nc, cnonce, qop, ha2
+            )),
+            _ => hash(format!("{}:{}:{}", ha1, self.nonce, ha2)),
+        }
+    }
+
+    /// Get the username.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Get the protection space.
+    pub fn realm(&self) -> &str {
+        &self.realm
+    }
+
+    /// Get the nonce sent by the server.
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    /// Get the request target.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Get the hash algorithm.
+    pub fn algorithm(&self) -> DigestAlgorithm {
+        self.algorithm
+    }
+
+    /// Get the quality of protection.
+    pub fn qop(&self) -> Option<Qop> {
+        self.qop
+    }
+
+    /// Set the quality of protection.
+    ///
+    /// A client nonce is generated if the credentials don't have one yet.
+    pub fn set_qop(&mut self, qop: Option<Qop>) {
+        if qop.is_some() && self.cnonce.is_none() {
+            let cnonce = rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(32)
+                .collect();
+            self.cnonce = Some(cnonce);
+        }
+        self.qop = qop;
+    }
+
+    /// Get the number of requests sent with the current nonce, including
+    /// this one.
+    pub fn nc(&self) -> u32 {
+        self.nc
+    }
+
+    /// Set the number of requests sent with the current nonce, including
+    /// this one.
+    pub fn set_nc(&mut self, nc: u32) {
+        self.nc = nc;
+    }
+
+    /// Get the client nonce.
+    pub fn cnonce(&self) -> Option<&str> {
+        self.cnonce.as_deref()
+    }
+
+    /// Set the client nonce.
+    pub fn set_cnonce(&mut self, cnonce: impl Into<String>) {
+        self.cnonce = Some(cnonce.into());
+    }
+
+    /// Get the opaque data sent by the server.
+    pub fn opaque(&self) -> Option<&str> {
+        self.opaque.as_deref()
+    }
+
+    /// Get the `response` hash.
+    pub fn response(&self) -> &str {
+        &self.response
+    }
+}
+
+impl ToHeaderValues for DigestCredentials {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+/// Compare two byte strings in constant time.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    /// The example from RFC 7616, section 3.9.1.
+    fn rfc_example(algorithm: DigestAlgorithm) -> (DigestChallenge, DigestCredentials) {
+        let mut challenge = DigestChallenge::new("http-auth@example.org");
+        challenge.set_nonce("7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v");
+        challenge.set_opaque(Some("FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS".into()));
+        challenge.set_qop(vec![Qop::Auth, Qop::AuthInt]);
+        challenge.set_algorithm(algorithm);
+
+        let mut credentials = DigestCredentials::new(&challenge, "Mufasa", "/dir/index.html");
+        credentials.set_cnonce("f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ");
+        credentials.compute("Circle of Life", Method::Get, b"");
+        (challenge, credentials)
+    }
+
+    #[test]
+    fn rfc_example_responses() {
+        let (_, credentials) = rfc_example(DigestAlgorithm::Md5);
+        assert_eq!(credentials.response(), "8ca523f5e9506fed4657c9700eebdbec");
+
+        let (_, credentials) = rfc_example(DigestAlgorithm::Sha256);
+        assert_eq!(
+            credentials.response(),
+            "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1"
+        );
+        assert_eq!(
+            credentials.value(),
+            concat!(
+                r#"Digest username="Mufasa", realm="http-auth@example.org", "#,
+                r#"uri="/dir/index.html", algorithm=SHA-256, "#,
+                r#"nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", nc=00000001, "#,
+                r#"cnonce="f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ", qop=auth, "#,
+                r#"response="753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1", "#,
+                r#"opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+            )
+        );
+    }
+
+    #[test]
+    fn verify_roundtrip() -> crate::Result<()> {
+        let (challenge, credentials) = rfc_example(DigestAlgorithm::Sha256);
+        let mut headers = Headers::new();
+        credentials.apply(&mut headers);
+
+        let parsed = DigestCredentials::from_headers(headers)?.unwrap();
+        assert_eq!(parsed, credentials);
+
+        let ha1 = DigestAlgorithm::Sha256.ha1("Mufasa", "http-auth@example.org", "Circle of Life");
+        assert!(parsed.verify(&challenge, &ha1, Method::Get, b""));
+        assert!(!parsed.verify(&challenge, &ha1, Method::Post, b""));
+
+        let wrong = DigestAlgorithm::Sha256.ha1("Mufasa", "http-auth@example.org", "Hakuna Matata");
+        assert!(!parsed.verify(&challenge, &wrong, Method::Get, b""));
+
+        let mut other = challenge.clone();
+        other.set_nonce("stale");
+        assert!(!parsed.verify(&other, &ha1, Method::Get, b""));
+        Ok(())
+    }
+
+    #[test]
+    fn auth_int_covers_the_body() {
+        let (challenge, mut credentials) = rfc_example(DigestAlgorithm::Md5);
+        credentials.set_qop(Some(Qop::AuthInt));
+        credentials.set_nc(2);
+        credentials.compute("Circle of Life", Method::Post, b"meow");
+
+        let ha1 = DigestAlgorithm::Md5.ha1("Mufasa", "http-auth@example.org", "Circle of Life");
+        assert!(credentials.verify(&challenge, &ha1, Method::Post, b"meow"));
+        assert!(!credentials.verify(&challenge, &ha1, Method::Post, b"woof"));
+    }
+
+    #[test]
+    fn rfc_2069_compatibility() -> crate::Result<()> {
+        let mut challenge = DigestChallenge::new("nori");
+        challenge.set_qop(vec![]);
+        let mut credentials = DigestCredentials::new(&challenge, "chashu", "/");
+        credentials.compute("fish", Method::Get, b"");
+        assert_eq!(credentials.cnonce(), None);
+
+        let mut headers = Headers::new();
+        credentials.apply(&mut headers);
+        let parsed = DigestCredentials::from_headers(headers)?.unwrap();
+
+        let ha1 = DigestAlgorithm::Md5.ha1("chashu", "nori", "fish");
+        assert!(parsed.verify(&challenge, &ha1, Method::Get, b""));
+        Ok(())
+    }
+
+    #[test]
+    fn non_ascii_usernames() -> crate::Result<()> {
+        let challenge = DigestChallenge::new("api@example.org");
+        let mut credentials = DigestCredentials::new(&challenge, "Jäsøn Doe", "/doe.json");
+        credentials.compute("Secret, or not?", Method::Get, b"");
+
+        let value = credentials.value();
+        assert!(value
+            .as_str()
+            .starts_with("Digest username*=UTF-8''J%C3%A4s%C3%B8n%20Doe, "));
+
+        let mut headers = Headers::new();
+        credentials.apply(&mut headers);
+        let parsed = DigestCredentials::from_headers(headers)?.unwrap();
+        assert_eq!(parsed.username(), "Jäsøn Doe");
+
+        let ha1 = DigestAlgorithm::Md5.ha1("Jäsøn Doe", "api@example.org", "Secret, or not?");
+        assert!(parsed.verify(&challenge, &ha1, Method::Get, b""));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = [
+            r#"Basic bm9yaTpzZWNyZXRfZmlzaCEh"#,
+            r#"Digest username="a", realm="b", nonce="c", response="d""#,
+            r#"Digest username*=a, realm="b", nonce="c", uri="/", response="d""#,
+            r#"Digest username="a", realm="b", nonce="c", uri="/", response="d", qop=auth, nc=1, cnonce="e""#,
+        ];
+        for value in &values {
+            let mut headers = Headers::new();
+            headers.insert(AUTHORIZATION, *value);
+            let err = DigestCredentials::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! HTTP authentication and authorization.
+//!
+//! A server which requires authentication responds with `401 Unauthorized`
+//! and one or more challenges in the `WWW-Authenticate` header. The user
+//! agent then sends its credentials in the `Authorization` header.
+//!
+//! # Specifications
+//!
+//! - [RFC 7235: HTTP/1.1: Authentication](https://tools.ietf.org/html/rfc7235)
+//! - [RFC 7616: HTTP Digest Access Authentication](https://tools.ietf.org/html/rfc7616)
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> {
+//! #
+//! use http_types::{Method, Request, Response, StatusCode};
+//! use http_types::auth::{AuthenticationScheme, BasicAuth, Challenge, WwwAuthenticate};
+//!
+//! let mut req = Request::new(Method::Get, "https://example.com");
+//! BasicAuth::new("nori", "secret_fish!!").apply(&mut req);
+//!
+//! let mut res = Response::new(StatusCode::Ok);
+//! match BasicAuth::from_headers(&req)? {
+//!     Some(auth) if auth.username() == "nori" => {}
+//!     _ => {
+//!         let mut challenge = Challenge::new(AuthenticationScheme::Basic);
+//!         challenge.set_realm("Nori's stash");
+//!         let mut www_auth = WwwAuthenticate::new();
+//!         www_auth.push(challenge);
+//!
+//!         res.set_status(StatusCode::Unauthorized);
+//!         www_auth.apply(&mut res);
+//!     }
+//! }
+//! assert_eq!(res.status(), StatusCode::Ok);
+//! #
+//! # Ok(()) }
+//! ```
+
+mod authentication_scheme;
+mod authorization;
+mod basic_auth;
+mod bearer_token;
+mod challenge;
+mod digest_algorithm;
+mod digest_challenge;
+mod digest_credentials;
+mod www_authenticate;
+
+pub use authentication_scheme::AuthenticationScheme;
+pub use authorization::Authorization;
+pub use basic_auth::BasicAuth;
+pub use bearer_token::BearerToken;
+pub use challenge::Challenge;
+pub use digest_algorithm::{DigestAlgorithm, Qop};
+pub use digest_challenge::DigestChallenge;
+pub use digest_credentials::DigestCredentials;
+pub use www_authenticate::{Iter, WwwAuthenticate};
@@ -0,0 +1,297 @@
+use crate::auth::bearer_token::is_token68;
+use crate::auth::{AuthenticationScheme, Challenge};
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, WWW_AUTHENTICATE};
+use crate::utils::split_quoted;
+use crate::{Error, StatusCode};
+
+use std::fmt::Write;
+use std::iter::Iterator;
+use std::option;
+use std::slice;
+
+/// Define the authentication method that should be used to gain access to a
+/// resource.
+///
+/// A `401 Unauthorized` response must include at least one challenge.
+/// Challenges with an unknown authentication scheme are skipped when parsing.
+///
+/// # Specifications
+///
+/// - [RFC 7235, section 4.1: WWW-Authenticate](https://tools.ietf.org/html/rfc7235#section-4.1)
+/// - [RFC 7617, section 2: The 'Basic' Authentication Scheme](https://tools.ietf.org/html/rfc7617#section-2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::{Response, StatusCode};
+/// use http_types::auth::{AuthenticationScheme, Challenge, WwwAuthenticate};
+///
+/// let mut challenge = Challenge::new(AuthenticationScheme::Basic);
+/// challenge.set_realm("Access to the staging site");
+///
+/// let mut www_auth = WwwAuthenticate::new();
+/// www_auth.push(challenge);
+///
+/// let mut res = Response::new(StatusCode::Unauthorized);
+/// www_auth.apply(&mut res);
+/// assert_eq!(res["WWW-Authenticate"], r#"Basic realm="Access to the staging site""#);
+///
+/// let www_auth = WwwAuthenticate::from_headers(res)?.unwrap();
+/// let challenge = www_auth.get(AuthenticationScheme::Basic).unwrap();
+/// assert_eq!(challenge.realm(), Some("Access to the staging site"));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WwwAuthenticate {
+    challenges: Vec<Challenge>,
+}
+
+impl WwwAuthenticate {
+    /// Create a new instance of `WwwAuthenticate`.
+    pub fn new() -> Self {
+        Self { challenges: vec![] }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(WWW_AUTHENTICATE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut challenges = vec![];
+        for value in headers {
+            parse_challenges(value.as_str(), &mut challenges)?;
+        }
+        Ok(Some(Self { challenges }))
+    }
+
+    /// Sets the header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        WWW_AUTHENTICATE
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::new();
+        for (n, challenge) in self.challenges.iter().enumerate() {
+            match n {
+                0 => write!(output, "{}", challenge).unwrap(),
+                _ => write!(output, ", {}", challenge).unwrap(),
+            };
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Push a challenge into the list of challenges.
+    pub fn push(&mut self, challenge: Challenge) {
+        self.challenges.push(challenge);
+    }
+
+    /// Get the first challenge for a scheme.
+    pub fn get(&self, scheme: AuthenticationScheme) -> Option<&Challenge> {
+        self.challenges.iter().find(|c| c.scheme() == scheme)
+    }
+
+    /// Returns `true` if there are no challenges.
+    pub fn is_empty(&self) -> bool {
+        self.challenges.is_empty()
+    }
+
+    /// An iterator visiting all challenges.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.challenges.iter(),
+        }
+    }
+}
+
+impl Default for WwwAuthenticate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a list of challenges.
+///
+/// Challenges and their parameters are both separated by commas, so an item
+/// starts a new challenge when it begins with a scheme name rather than a
+/// `name=value` pair.
+fn parse_challenges(value: &str, challenges: &mut Vec<Challenge>) -> crate::Result<()> {
+    // `None` while skipping the parameters of a challenge with an unknown scheme.
+    let mut current: Option<Challenge> = None;
+    let mut started = false;
+
+    for item in split_quoted(value, ',') {
+        let end = item
+            .find(|c: char| c == '=' || c.is_ascii_whitespace())
+            .unwrap_or(item.len());
+        let (name, rest) = (&item[..end], item[end..].trim_start());
+
+        if let Some(value) = rest.strip_prefix('=') {
+            if !started {
+                let msg = "Auth params should follow an authentication scheme";
+                return Err(Error::from_str(StatusCode::BadRequest, msg));
+            }
+            let (name, value) = Challenge::parse_param(name, value)?;
+            if let Some(challenge) = current.as_mut() {
+                challenge.insert_param(name, value);
+            }
+            continue;
+        }
+
+        challenges.extend(current.take());
+        started = true;
+        current = name
+            .parse::<AuthenticationScheme>()
+            .ok()
+            .map(Challenge::new);
+
+        if rest.is_empty() {
+            continue;
+        }
+        if is_token68(rest) {
+            if let Some(challenge) = current.as_mut() {
+                challenge.set_token68(rest);
+            }
+            continue;
+        }
+        let (name, value) = match rest.find('=') {
+            Some(index) => Challenge::parse_param(&rest[..index], &rest[index + 1..])?,
+            None => {
+                let msg = format!("Invalid authentication challenge: {}", item);
+                return Err(Error::from_str(StatusCode::BadRequest, msg));
+            }
+        };
+        if let Some(challenge) = current.as_mut() {
+            challenge.insert_param(name, value);
+        }
+    }
+    challenges.extend(current.take());
+    Ok(())
+}
+
+impl IntoIterator for WwwAuthenticate {
+    type Item = Challenge;
+    type IntoIter = std::vec::IntoIter<Challenge>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.challenges.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a WwwAuthenticate {
+    type Item = &'a Challenge;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over entries in `WwwAuthenticate`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, Challenge>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Challenge;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ToHeaderValues for WwwAuthenticate {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut basic = Challenge::new(AuthenticationScheme::Basic);
+        basic.set_realm("staging");
+        basic.insert_param("charset", "UTF-8");
+        let mut bearer = Challenge::new(AuthenticationScheme::Bearer);
+        bearer.set_realm("example");
+        bearer.insert_param("error", "invalid_token");
+
+        let mut www_auth = WwwAuthenticate::new();
+        www_auth.push(basic.clone());
+        www_auth.push(bearer.clone());
+
+        let mut headers = Headers::new();
+        www_auth.apply(&mut headers);
+        assert_eq!(
+            headers[WWW_AUTHENTICATE],
+            r#"Basic realm="staging", charset="UTF-8", Bearer realm="example", error="invalid_token""#
+        );
+
+        let www_auth = WwwAuthenticate::from_headers(headers)?.unwrap();
+        let challenges: Vec<_> = www_auth.iter().cloned().collect();
+        assert_eq!(challenges, vec![basic, bearer]);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_quoted_strings_and_token68() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append(
+            WWW_AUTHENTICATE,
+            r#"Newauth realm="apps", type=1, title="Login to \"apps\"", Basic realm = "simple""#,
+        );
+        headers.append(WWW_AUTHENTICATE, "Negotiate, Negotiate a87421==");
+
+        let www_auth = WwwAuthenticate::from_headers(headers)?.unwrap();
+        let challenges: Vec<_> = www_auth.iter().collect();
+        assert_eq!(challenges.len(), 3);
+        assert_eq!(challenges[0].scheme(), AuthenticationScheme::Basic);
+        assert_eq!(challenges[0].realm(), Some("simple"));
+        assert_eq!(challenges[1].scheme(), AuthenticationScheme::Negotiate);
+        assert_eq!(challenges[1].token68(), None);
+        assert_eq!(challenges[2].token68(), Some("a87421=="));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = [r#"realm="a""#, "Basic realm=a b", r#"Basic realm="a"#];
+        for value in &values {
+            let mut headers = Headers::new();
+            headers.insert(WWW_AUTHENTICATE, *value);
+            let err = WwwAuthenticate::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
@@ -3,9 +3,13 @@ use async_std::io::{self, Cursor};
 use serde::{de::DeserializeOwned, Serialize};
 
 use std::fmt::{self, Debug};
+use std::ops::{Bound, RangeBounds};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+#[cfg(feature = "compression")]
+use crate::content::Encoding;
+use crate::multipart::MultipartReader;
 use crate::{mime, Mime};
 use crate::{Status, StatusCode};
 
@@ -54,7 +58,7 @@ pin_project_lite::pin_project! {
     /// and not rely on the fallback mechanisms. However, they're still there if you need them.
     pub struct Body {
         #[pin]
-        reader: Box<dyn BufRead + Unpin + Send + Sync + 'static>,
+        reader: Reader,
         mime: Mime,
         length: Option<usize>,
     }
@@ -76,7 +80,7 @@ impl Body {
     /// ```
     pub fn empty() -> Self {
         Self {
-            reader: Box::new(io::empty()),
+            reader: Reader::Stream(Box::new(io::empty())),
             mime: mime::BYTE_STREAM,
             length: Some(0),
         }
@@ -106,12 +110,98 @@ impl Body {
         len: Option<usize>,
     ) -> Self {
         Self {
-            reader: Box::new(reader),
+            reader: Reader::Stream(Box::new(reader)),
             mime: mime::BYTE_STREAM,
             length: len,
         }
     }
 
+    /// Create a `Body` from a reader which supports seeking.
+    ///
+    /// Seekable bodies can be sliced without reading the skipped bytes, which
+    /// makes them a good fit for serving range requests. `Body::from_bytes`,
+    /// `Body::from_string` and `Body::from_file` are seekable too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> { async_std::task::block_on(async {
+    /// use http_types::Body;
+    /// use async_std::io::Cursor;
+    ///
+    /// let body = Body::from_seekable(Cursor::new("Hello Nori"), 10);
+    /// assert!(body.is_seekable());
+    /// assert_eq!(body.slice(6..).into_string().await?, "Nori");
+    /// # Ok(()) }) }
+    /// ```
+    pub fn from_seekable(
+        reader: impl BufRead + Seek + Unpin + Send + Sync + 'static,
+        len: usize,
+    ) -> Self {
+        Self {
+            reader: Reader::Seekable(Box::new(reader)),
+            mime: mime::BYTE_STREAM,
+            length: Some(len),
+        }
+    }
+
+    /// Returns `true` if the body supports seeking.
+    pub fn is_seekable(&self) -> bool {
+        matches!(self.reader, Reader::Seekable(_))
+    }
+
+    /// Restrict the body to a range of bytes.
+    ///
+    /// Offsets are relative to the start of the underlying source. Seekable
+    /// bodies seek straight to the start of the range, other bodies read and
+    /// discard the bytes before it. The range is clamped to the length of the
+    /// body if it's known. The mime type is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> { async_std::task::block_on(async {
+    /// use http_types::Body;
+    ///
+    /// let body = Body::from("Hello Nori").slice(2..=4);
+    /// assert_eq!(body.len(), Some(3));
+    /// assert_eq!(body.into_string().await?, "llo");
+    /// # Ok(()) }) }
+    /// ```
+    pub fn slice(self, range: impl RangeBounds<u64>) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => Some(end.saturating_add(1)),
+            Bound::Excluded(end) => Some(*end),
+            Bound::Unbounded => None,
+        };
+        let end = match (end, self.length) {
+            (Some(end), Some(len)) => Some(end.min(len as u64)),
+            (None, Some(len)) => Some(len as u64),
+            (end, None) => end,
+        };
+        let len = end.map(|end| end.saturating_sub(start));
+
+        let limit = len.unwrap_or(u64::MAX);
+        let reader = match self.reader {
+            Reader::Seekable(reader) => {
+                Reader::Seekable(Box::new(Slice::new(reader, start, limit)))
+            }
+            Reader::Stream(reader) => {
+                Reader::Stream(Box::new(Skip::new(reader, start).take(limit)))
+            }
+        };
+        Self {
+            reader,
+            mime: self.mime,
+            length: len.map(|len| len as usize),
+        }
+    }
+
     /// Get the inner reader from the `Body`
     ///
     /// # Examples
@@ -126,7 +216,18 @@ impl Body {
     /// let _ = body.into_reader();
     /// ```
     pub fn into_reader(self) -> Box<dyn BufRead + Unpin + Send + Sync + 'static> {
-        self.reader
+        match self.reader {
+            Reader::Stream(reader) => reader,
+            Reader::Seekable(reader) => Box::new(reader),
+        }
+    }
+
+    /// Get the inner reader from the `Body` if it supports seeking.
+    pub(crate) fn into_seekable(self) -> Option<SeekableReader> {
+        match self.reader {
+            Reader::Seekable(reader) => Some(reader),
+            Reader::Stream(_) => None,
+        }
     }
 
     /// Create a `Body` from a Vec of bytes.
@@ -151,7 +252,7 @@ impl Body {
         Self {
             mime: mime::BYTE_STREAM,
             length: Some(bytes.len()),
-            reader: Box::new(io::Cursor::new(bytes)),
+            reader: Reader::Seekable(Box::new(io::Cursor::new(bytes))),
         }
     }
 
@@ -200,7 +301,7 @@ impl Body {
         Self {
             mime: mime::PLAIN,
             length: Some(s.len()),
-            reader: Box::new(io::Cursor::new(s.into_bytes())),
+            reader: Reader::Seekable(Box::new(io::Cursor::new(s.into_bytes()))),
         }
     }
 
@@ -246,7 +347,7 @@ impl Body {
         let bytes = serde_json::to_vec(&json)?;
         let body = Self {
             length: Some(bytes.len()),
-            reader: Box::new(Cursor::new(bytes)),
+            reader: Reader::Seekable(Box::new(Cursor::new(bytes))),
             mime: mime::JSON,
         };
         Ok(body)
@@ -310,7 +411,7 @@ impl Body {
 
         let body = Self {
             length: Some(bytes.len()),
-            reader: Box::new(Cursor::new(bytes)),
+            reader: Reader::Seekable(Box::new(Cursor::new(bytes))),
             mime: mime::FORM,
         };
         Ok(body)
@@ -345,6 +446,114 @@ impl Body {
         Ok(serde_urlencoded::from_str(&s).status(StatusCode::UnprocessableEntity)?)
     }
 
+    /// Parse the body as `multipart/form-data`, streaming its parts.
+    ///
+    /// The boundary is read from the `boundary` param of the body's Mime type.
+    /// Parts are read from the body as they're requested, so the body is
+    /// never buffered in memory as a whole.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// Mime type is not `multipart/*`, or has no valid boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> { async_std::task::block_on(async {
+    /// use async_std::io::prelude::*;
+    /// use http_types::{Body, Mime};
+    ///
+    /// let mut body = Body::from("--boundary\r\n\r\nchashu\r\n--boundary--");
+    /// body.set_mime(Mime::from("multipart/form-data; boundary=boundary"));
+    ///
+    /// let mut multipart = body.into_multipart()?;
+    /// let mut part = multipart.next_part().await?.unwrap();
+    /// let mut contents = String::new();
+    /// part.read_to_string(&mut contents).await?;
+    /// assert_eq!(contents, "chashu");
+    /// # Ok(()) }) }
+    /// ```
+    pub fn into_multipart(self) -> crate::Result<MultipartReader> {
+        if self.mime.basetype() != "multipart" {
+            let msg = format!("Expected a multipart body, found {}", self.mime);
+            return Err(crate::Error::from_str(StatusCode::BadRequest, msg));
+        }
+
+        let boundary = match self.mime.param("boundary") {
+            Some(boundary) => boundary.as_str().to_owned(),
+            None => {
+                let msg = "Multipart body is missing a boundary";
+                return Err(crate::Error::from_str(StatusCode::BadRequest, msg));
+            }
+        };
+        if boundary.is_empty() || boundary.len() > 70 {
+            let msg = "Multipart boundary should be between 1 and 70 characters";
+            return Err(crate::Error::from_str(StatusCode::BadRequest, msg));
+        }
+
+        Ok(MultipartReader::new(self, &boundary))
+    }
+
+    /// Compress the body using the given encoding.
+    ///
+    /// The body is compressed as it's read, so the length of the resulting
+    /// body is unknown. The mime type is preserved. Compressing with
+    /// `Encoding::Identity` returns the body unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> { async_std::task::block_on(async {
+    /// use http_types::Body;
+    /// use http_types::content::Encoding;
+    ///
+    /// let body = Body::from("Hello Nori").compress(Encoding::Gzip);
+    /// assert_eq!(body.len(), None);
+    ///
+    /// let body = body.decompress(Encoding::Gzip);
+    /// assert_eq!(body.into_string().await?, "Hello Nori");
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn compress(self, encoding: Encoding) -> Self {
+        use async_compression::futures::bufread::{BrotliEncoder, GzipEncoder, ZlibEncoder};
+
+        let reader: Box<dyn BufRead + Unpin + Send + Sync + 'static> = match encoding {
+            Encoding::Gzip => Box::new(io::BufReader::new(GzipEncoder::new(self.reader))),
+            Encoding::Deflate => Box::new(io::BufReader::new(ZlibEncoder::new(self.reader))),
+            Encoding::Brotli => Box::new(io::BufReader::new(BrotliEncoder::new(self.reader))),
+            Encoding::Identity => return self,
+        };
+        Self {
+            reader: Reader::Stream(reader),
+            mime: self.mime,
+            length: None,
+        }
+    }
+
+    /// Decompress a body that was compressed using the given encoding.
+    ///
+    /// The body is decompressed as it's read, so the length of the resulting
+    /// body is unknown. The mime type is preserved. Decompressing with
+    /// `Encoding::Identity` returns the body unchanged.
+    #[cfg(feature = "compression")]
+    pub fn decompress(self, encoding: Encoding) -> Self {
+        use async_compression::futures::bufread::{BrotliDecoder, GzipDecoder, ZlibDecoder};
+
+        let reader: Box<dyn BufRead + Unpin + Send + Sync + 'static> = match encoding {
+            Encoding::Gzip => Box::new(io::BufReader::new(GzipDecoder::new(self.reader))),
+            Encoding::Deflate => Box::new(io::BufReader::new(ZlibDecoder::new(self.reader))),
+            Encoding::Brotli => Box::new(io::BufReader::new(BrotliDecoder::new(self.reader))),
+            Encoding::Identity => return self,
+        };
+        Self {
+            reader: Reader::Stream(reader),
+            mime: self.mime,
+            length: None,
+        }
+    }
+
     /// Create a `Body` from a file.
     ///
     /// The Mime type set to `application/octet-stream` if no other mime type has
@@ -379,7 +588,7 @@ impl Body {
         Ok(Self {
             mime,
             length: Some(len as usize),
-            reader: Box::new(io::BufReader::new(file)),
+            reader: Reader::Seekable(Box::new(io::BufReader::new(file))),
         })
     }
 
@@ -478,6 +687,216 @@ impl BufRead for Body {
     }
 }
 
+/// A reader which supports both buffered reads and seeking.
+pub(crate) trait SeekBufRead: BufRead + Seek {}
+
+impl<T: BufRead + Seek + ?Sized> SeekBufRead for T {}
+
+/// The reader of a seekable `Body`.
+pub(crate) type SeekableReader = Box<dyn SeekBufRead + Unpin + Send + Sync + 'static>;
+
+/// The reader backing a `Body`.
+enum Reader {
+    Stream(Box<dyn BufRead + Unpin + Send + Sync + 'static>),
+    Seekable(SeekableReader),
+}
+
+impl Read for Reader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Reader::Stream(reader) => Pin::new(reader).poll_read(cx, buf),
+            Reader::Seekable(reader) => Pin::new(reader).poll_read(cx, buf),
+        }
+    }
+}
+
+impl BufRead for Reader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&'_ [u8]>> {
+        match self.get_mut() {
+            Reader::Stream(reader) => Pin::new(reader).poll_fill_buf(cx),
+            Reader::Seekable(reader) => Pin::new(reader).poll_fill_buf(cx),
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        match self.get_mut() {
+            Reader::Stream(reader) => Pin::new(reader).consume(amt),
+            Reader::Seekable(reader) => Pin::new(reader).consume(amt),
+        }
+    }
+}
+
+/// A window into a seekable reader.
+///
+/// The inner reader is only moved to the start of the window on the first
+/// read, and positions reported by `Seek` are relative to the window.
+struct Slice<R> {
+    inner: R,
+    start: u64,
+    len: u64,
+    pos: u64,
+    seeked: bool,
+}
+
+impl<R: SeekBufRead + Unpin> Slice<R> {
+    fn new(inner: R, start: u64, len: u64) -> Self {
+        Self {
+            inner,
+            start,
+            len,
+            pos: 0,
+            seeked: false,
+        }
+    }
+}
+
+impl<R: SeekBufRead + Unpin> Read for Slice<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let data = match self.as_mut().poll_fill_buf(cx) {
+            Poll::Ready(Ok(value)) => value,
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        };
+        let amt = data.len().min(buf.len());
+        buf[..amt].copy_from_slice(&data[..amt]);
+        self.consume(amt);
+        Poll::Ready(Ok(amt))
+    }
+}
+
+impl<R: SeekBufRead + Unpin> BufRead for Slice<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&'_ [u8]>> {
+        let this = self.get_mut();
+        if !this.seeked {
+            let pos = io::SeekFrom::Start(this.start.saturating_add(this.pos));
+            match Pin::new(&mut this.inner).poll_seek(cx, pos) {
+                Poll::Ready(Ok(value)) => value,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            };
+            this.seeked = true;
+        }
+        let remaining = this.len.saturating_sub(this.pos);
+        if remaining == 0 {
+            return Poll::Ready(Ok(&[]));
+        }
+        let data = match Pin::new(&mut this.inner).poll_fill_buf(cx) {
+            Poll::Ready(Ok(value)) => value,
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        };
+        let amt = (data.len() as u64).min(remaining) as usize;
+        Poll::Ready(Ok(&data[..amt]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        Pin::new(&mut this.inner).consume(amt);
+        this.pos += amt as u64;
+    }
+}
+
+impl<R: SeekBufRead + Unpin> Seek for Slice<R> {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: io::SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        let pos = match pos {
+            io::SeekFrom::Start(offset) => Some(offset),
+            io::SeekFrom::Current(offset) => offset_by(this.pos, offset),
+            io::SeekFrom::End(offset) => offset_by(this.len, offset),
+        };
+        let pos = match pos {
+            Some(pos) => pos,
+            None => {
+                let msg = "invalid seek to a negative or overflowing position";
+                return Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidInput, msg)));
+            }
+        };
+        let target = io::SeekFrom::Start(this.start.saturating_add(pos));
+        match Pin::new(&mut this.inner).poll_seek(cx, target) {
+            Poll::Ready(Ok(value)) => value,
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        };
+        this.pos = pos;
+        this.seeked = true;
+        Poll::Ready(Ok(pos))
+    }
+}
+
+fn offset_by(base: u64, offset: i64) -> Option<u64> {
+    if offset >= 0 {
+        base.checked_add(offset as u64)
+    } else {
+        base.checked_sub(offset.wrapping_neg() as u64)
+    }
+}
+
+/// A reader which discards a number of bytes before yielding any data.
+struct Skip<R> {
+    inner: R,
+    remaining: u64,
+}
+
+impl<R: BufRead + Unpin> Skip<R> {
+    fn new(inner: R, remaining: u64) -> Self {
+        Self { inner, remaining }
+    }
+}
+
+impl<R: BufRead + Unpin> Read for Skip<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let data = match self.as_mut().poll_fill_buf(cx) {
+            Poll::Ready(Ok(value)) => value,
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        };
+        let amt = data.len().min(buf.len());
+        buf[..amt].copy_from_slice(&data[..amt]);
+        self.consume(amt);
+        Poll::Ready(Ok(amt))
+    }
+}
+
+impl<R: BufRead + Unpin> BufRead for Skip<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&'_ [u8]>> {
+        let this = self.get_mut();
+        while this.remaining > 0 {
+            let data = match Pin::new(&mut this.inner).poll_fill_buf(cx) {
+                Poll::Ready(Ok(value)) => value,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            };
+            if data.is_empty() {
+                return Poll::Ready(Ok(&[]));
+            }
+            let amt = (data.len() as u64).min(this.remaining) as usize;
+            Pin::new(&mut this.inner).consume(amt);
+            this.remaining -= amt as u64;
+        }
+        Pin::new(&mut this.inner).poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        Pin::new(&mut self.get_mut().inner).consume(amt)
+    }
+}
+
 /// Look at first few bytes of a file to determine the mime type.
 /// This is used for various binary formats such as images and videos.
 #[cfg(all(feature = "async_std", not(target_os = "unknown")))]
@@ -526,4 +945,78 @@ mod test {
         let res = body.into_form::<Foo>().await;
         assert_eq!(res.unwrap_err().status(), 422);
     }
+
+    #[cfg(feature = "compression")]
+    #[async_std::test]
+    async fn compression_roundtrip() -> crate::Result<()> {
+        let text = "Hello Nori! ".repeat(100);
+        for encoding in &[Encoding::Gzip, Encoding::Deflate, Encoding::Brotli] {
+            let body = Body::from_string(text.clone()).compress(*encoding);
+            assert_eq!(body.len(), None);
+            assert_eq!(body.mime(), &mime::PLAIN);
+
+            let compressed = body.into_bytes().await?;
+            assert!(compressed.len() < text.len());
+
+            let body = Body::from_bytes(compressed).decompress(*encoding);
+            assert_eq!(body.into_string().await?, text);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "compression")]
+    #[async_std::test]
+    async fn identity_is_a_noop() -> crate::Result<()> {
+        let body = Body::from("chashu").compress(Encoding::Identity);
+        assert_eq!(body.len(), Some(6));
+        assert_eq!(body.into_string().await?, "chashu");
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn seekable_slices() -> crate::Result<()> {
+        let body = Body::from_seekable(Cursor::new("0123456789"), 10);
+        assert!(body.is_seekable());
+
+        let body = body.slice(2..8).slice(1..=3);
+        assert!(body.is_seekable());
+        assert_eq!(body.len(), Some(3));
+        assert_eq!(body.into_string().await?, "345");
+
+        let body = Body::from("0123456789").slice(8..20);
+        assert_eq!(body.len(), Some(2));
+        assert_eq!(body.into_string().await?, "89");
+
+        let body = Body::from("0123456789").slice(20..);
+        assert_eq!(body.len(), Some(0));
+        assert_eq!(body.into_string().await?, "");
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn slice_seeks_within_the_window() -> crate::Result<()> {
+        let mut reader = Slice::new(Cursor::new("0123456789"), 2, 5);
+        assert_eq!(reader.seek(io::SeekFrom::End(-2)).await?, 3);
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).await?;
+        assert_eq!(rest, "56");
+
+        assert!(reader.seek(io::SeekFrom::Current(-10)).await.is_err());
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn streaming_slices() -> crate::Result<()> {
+        let body = Body::from_reader(Cursor::new("0123456789"), None);
+        assert!(!body.is_seekable());
+
+        let body = body.slice(3..6);
+        assert_eq!(body.len(), Some(3));
+        assert_eq!(body.into_string().await?, "345");
+
+        let body = Body::from_reader(Cursor::new("0123456789"), None).slice(7..);
+        assert_eq!(body.len(), None);
+        assert_eq!(body.into_string().await?, "789");
+        Ok(())
+    }
 }
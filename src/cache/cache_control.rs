@@ -0,0 +1,219 @@
+use crate::cache::CacheDirective;
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, CACHE_CONTROL};
+use crate::utils::split_quoted;
+
+use std::fmt::Write;
+use std::iter::Iterator;
+use std::option;
+use std::slice;
+
+/// A Cache-Control header.
+///
+/// Directives this crate doesn't know about are kept as
+/// `CacheDirective::Extension`, so a parsed header can be written back
+/// without losing information.
+///
+/// # Specifications
+///
+/// - [RFC 7234, section 5.2: Cache-Control](https://tools.ietf.org/html/rfc7234#section-5.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::cache::{CacheControl, CacheDirective};
+/// use std::time::Duration;
+///
+/// let mut entries = CacheControl::new();
+/// entries.push(CacheDirective::Immutable);
+/// entries.push(CacheDirective::MaxAge(Duration::from_secs(3600)));
+///
+/// let mut res = Response::new(200);
+/// entries.apply(&mut res);
+/// assert_eq!(res["Cache-Control"], "immutable, max-age=3600");
+///
+/// let entries = CacheControl::from_headers(res)?.unwrap();
+/// let mut entries = entries.iter();
+/// assert_eq!(entries.next().unwrap(), &CacheDirective::Immutable);
+/// assert_eq!(entries.next().unwrap(), &CacheDirective::MaxAge(Duration::from_secs(3600)));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheControl {
+    entries: Vec<CacheDirective>,
+}
+
+impl CacheControl {
+    /// Create a new instance of `CacheControl`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if a
+    /// directive is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(CACHE_CONTROL) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut entries = vec![];
+        for value in headers {
+            for part in split_quoted(value.as_str(), ',') {
+                entries.push(part.parse()?);
+            }
+        }
+        Ok(Some(Self { entries }))
+    }
+
+    /// Sets the `Cache-Control` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(CACHE_CONTROL, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CACHE_CONTROL
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::new();
+        for (n, directive) in self.entries.iter().enumerate() {
+            match n {
+                0 => write!(output, "{}", directive).unwrap(),
+                _ => write!(output, ", {}", directive).unwrap(),
+            };
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Push a directive into the list of entries.
+    pub fn push(&mut self, directive: CacheDirective) {
+        self.entries.push(directive);
+    }
+
+    /// Returns `true` if a directive with the same name is present.
+    pub fn contains(&self, directive: &CacheDirective) -> bool {
+        self.entries.iter().any(|d| d.name() == directive.name())
+    }
+
+    /// Returns `true` if there are no directives.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// An iterator visiting all directives.
+    pub fn iter(&self) -> slice::Iter<'_, CacheDirective> {
+        self.entries.iter()
+    }
+
+    /// An iterator visiting all directives.
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, CacheDirective> {
+        self.entries.iter_mut()
+    }
+}
+
+impl IntoIterator for CacheControl {
+    type Item = CacheDirective;
+    type IntoIter = std::vec::IntoIter<CacheDirective>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a CacheControl {
+    type Item = &'a CacheDirective;
+    type IntoIter = slice::Iter<'a, CacheDirective>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut CacheControl {
+    type Item = &'a mut CacheDirective;
+    type IntoIter = slice::IterMut<'a, CacheDirective>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl ToHeaderValues for CacheControl {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Response;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut entries = CacheControl::new();
+        entries.push(CacheDirective::Private(vec!["Set-Cookie".into()]));
+        entries.push(CacheDirective::StaleWhileRevalidate(Duration::from_secs(
+            30,
+        )));
+
+        let mut res = Response::new(200);
+        entries.apply(&mut res);
+        assert_eq!(
+            res[CACHE_CONTROL],
+            r#"private="Set-Cookie", stale-while-revalidate=30"#
+        );
+
+        let parsed = CacheControl::from_headers(res)?.unwrap();
+        assert_eq!(parsed, entries);
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrips_unknown_directives() -> crate::Result<()> {
+        let value = r#"no-cache="Set-Cookie, Foo", max-age=0, s-maxage=10, stale-if-error=60, community=UCI, x-custom="a b", must-understand, no-store"#;
+        let mut headers = Headers::new();
+        headers.insert(CACHE_CONTROL, value);
+        let entries = CacheControl::from_headers(&headers)?.unwrap();
+        assert_eq!(entries.iter().count(), 8);
+        assert!(entries.contains(&CacheDirective::NoStore));
+
+        let mut res = Response::new(200);
+        entries.apply(&mut res);
+        assert_eq!(res[CACHE_CONTROL], value);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert(CACHE_CONTROL, "public, x-custom=a b");
+        let err = CacheControl::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+
+    #[test]
+    fn merges_multiple_headers() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append(CACHE_CONTROL, "public");
+        headers.append(CACHE_CONTROL, "max-age=60, immutable");
+        let entries = CacheControl::from_headers(headers)?.unwrap();
+        assert_eq!(entries.iter().count(), 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+use crate::utils::{is_token, quote, split_quoted, unquote};
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// An HTTP `Cache-Control` directive.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheDirective {
+    /// The response body will not change over time.
+    Immutable,
+    /// The maximum amount of time a resource is considered fresh.
+    MaxAge(Duration),
+    /// Indicates the client will accept a stale response, optionally only
+    /// if it has been stale for less than the given time.
+    MaxStale(Option<Duration>),
+    /// A response that will still be fresh for at least the specified duration.
+    MinFresh(Duration),
+    /// Once a response is stale, a fresh response must be retrieved.
+    MustRevalidate,
+    /// The response may only be stored by caches that understand the
+    /// requirements for storing a response with this status code.
+    MustUnderstand,
+    /// The response may be stored, but must be validated before each reuse.
+    ///
+    /// When field names are given, only those header fields must be
+    /// validated before reuse.
+    NoCache(Vec<String>),
+    /// The response may not be stored.
+    NoStore,
+    /// An intermediate cache or proxy should not edit the response body,
+    /// `Content-Encoding`, `Content-Range`, or `Content-Type`.
+    NoTransform,
+    /// Do not use the network for a response.
+    OnlyIfCached,
+    /// The response may be stored only by a browser's cache.
+    ///
+    /// When field names are given, only those header fields are private.
+    Private(Vec<String>),
+    /// Like must-revalidate, but only for shared caches.
+    ProxyRevalidate,
+    /// The response may be stored by any cache.
+    Public,
+    /// Overrides max-age or the Expires header, but only for shared caches.
+    SMaxAge(Duration),
+    /// The client will accept a stale response if retrieving a fresh one
+    /// fails.
+    StaleIfError(Duration),
+    /// Indicates the client will accept a stale response, while
+    /// asynchronously checking in the background for a fresh one.
+    StaleWhileRevalidate(Duration),
+    /// A directive this crate doesn't know about, with an optional argument.
+    Extension(String, Option<String>),
+}
+
+impl CacheDirective {
+    /// Get the name of the directive.
+    pub fn name(&self) -> &str {
+        use CacheDirective::*;
+        match self {
+            Immutable => "immutable",
+            MaxAge(_) => "max-age",
+            MaxStale(_) => "max-stale",
+            MinFresh(_) => "min-fresh",
+            MustRevalidate => "must-revalidate",
+            MustUnderstand => "must-understand",
+            NoCache(_) => "no-cache",
+            NoStore => "no-store",
+            NoTransform => "no-transform",
+            OnlyIfCached => "only-if-cached",
+            Private(_) => "private",
+            ProxyRevalidate => "proxy-revalidate",
+            Public => "public",
+            SMaxAge(_) => "s-maxage",
+            StaleIfError(_) => "stale-if-error",
+            StaleWhileRevalidate(_) => "stale-while-revalidate",
+            Extension(name, _) => name,
+        }
+    }
+
+    /// Returns `true` if the directive may be used in a request.
+    ///
+    /// Extension directives are valid in both requests and responses.
+    pub fn valid_in_request(&self) -> bool {
+        use CacheDirective::*;
+        matches!(
+            self,
+            MaxAge(_)
+                | MaxStale(_)
+                | MinFresh(_)
+                | NoCache(_)
+                | NoStore
+                | NoTransform
+                | OnlyIfCached
+                | StaleIfError(_)
+                | Extension(..)
+        )
+    }
+
+    /// Returns `true` if the directive may be used in a response.
+    ///
+    /// Extension directives are valid in both requests and responses.
+    pub fn valid_in_response(&self) -> bool {
+        use CacheDirective::*;
+        !matches!(self, MaxStale(_) | MinFresh(_) | OnlyIfCached)
+    }
+}
+
+impl FromStr for CacheDirective {
+    type Err = Error;
+
+    /// Parse a single directive, such as `max-age=60` or `no-cache="Set-Cookie"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use CacheDirective::*;
+
+        let mut parts = s.trim().splitn(2, '=');
+        let name = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+        if !is_token(&name) {
+            return Err(bad_request(s));
+        }
+
+        let arg = match parts.next().map(str::trim) {
+            Some(arg) if arg.starts_with('"') => Some(unquote(arg).ok_or_else(|| bad_request(s))?),
+            Some(arg) if is_token(arg) => Some(arg.to_owned()),
+            Some(_) => return Err(bad_request(s)),
+            None => None,
+        };
+
+        let seconds = |arg: Option<String>| -> crate::Result<Duration> {
+            match arg.map(|arg| arg.parse::<u64>()) {
+                Some(Ok(secs)) => Ok(Duration::from_secs(secs)),
+                _ => Err(bad_request(s)),
+            }
+        };
+        let fields = |arg: Option<String>| -> Vec<String> {
+            match arg {
+                Some(arg) => split_quoted(&arg, ',')
+                    .into_iter()
+                    .map(|field| field.to_owned())
+                    .collect(),
+                None => vec![],
+            }
+        };
+
+        let directive = match name.as_str() {
+            "immutable" => Immutable,
+            "max-age" => MaxAge(seconds(arg)?),
+            "max-stale" => match arg {
+                Some(_) => MaxStale(Some(seconds(arg)?)),
+                None => MaxStale(None),
+            },
+            "min-fresh" => MinFresh(seconds(arg)?),
+            "must-revalidate" => MustRevalidate,
+            "must-understand" => MustUnderstand,
+            "no-cache" => NoCache(fields(arg)),
+            "no-store" => NoStore,
+            "no-transform" => NoTransform,
+            "only-if-cached" => OnlyIfCached,
+            "private" => Private(fields(arg)),
+            "proxy-revalidate" => ProxyRevalidate,
+            "public" => Public,
+            "s-maxage" => SMaxAge(seconds(arg)?),
+            "stale-if-error" => StaleIfError(seconds(arg)?),
+            "stale-while-revalidate" => StaleWhileRevalidate(seconds(arg)?),
+            _ => Extension(name, arg),
+        };
+        Ok(directive)
+    }
+}
+
+impl Display for CacheDirective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CacheDirective::*;
+        f.write_str(self.name())?;
+        match self {
+            MaxAge(d) | MinFresh(d) | SMaxAge(d) | StaleIfError(d) | StaleWhileRevalidate(d) => {
+                write!(f, "={}", d.as_secs())
+            }
+            MaxStale(Some(d)) => write!(f, "={}", d.as_secs()),
+            NoCache(fields) | Private(fields) if !fields.is_empty() => {
+                write!(f, "={}", quote(&fields.join(", ")))
+            }
+            Extension(_, Some(arg)) if is_token(arg) => write!(f, "={}", arg),
+            Extension(_, Some(arg)) => write!(f, "={}", quote(arg)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn bad_request(directive: &str) -> Error {
+    let msg = format!("Invalid cache directive: {}", directive.trim());
+    Error::from_str(StatusCode::BadRequest, msg)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        use CacheDirective::*;
+        let secs = Duration::from_secs;
+        assert_eq!("MAX-AGE=60".parse::<CacheDirective>()?, MaxAge(secs(60)));
+        assert_eq!(
+            r#"max-age="60""#.parse::<CacheDirective>()?,
+            MaxAge(secs(60))
+        );
+        assert_eq!("max-stale".parse::<CacheDirective>()?, MaxStale(None));
+        assert_eq!("no-cache".parse::<CacheDirective>()?, NoCache(vec![]));
+        assert_eq!(
+            r#"private="Set-Cookie, Authorization""#.parse::<CacheDirective>()?,
+            Private(vec!["Set-Cookie".into(), "Authorization".into()])
+        );
+        assert_eq!(
+            r#"community="UCI""#.parse::<CacheDirective>()?,
+            Extension("community".into(), Some("UCI".into()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_invalid_directive() {
+        for s in &["max-age", "max-age=-1", "s-maxage=a", "no cache", r#"x="a"#] {
+            let err = s.parse::<CacheDirective>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+
+    #[test]
+    fn request_and_response_directives() {
+        assert!(CacheDirective::OnlyIfCached.valid_in_request());
+        assert!(!CacheDirective::OnlyIfCached.valid_in_response());
+        assert!(!CacheDirective::Immutable.valid_in_request());
+        assert!(CacheDirective::Immutable.valid_in_response());
+        assert!(CacheDirective::NoStore.valid_in_request());
+        assert!(CacheDirective::NoStore.valid_in_response());
+    }
+}
@@ -0,0 +1,247 @@
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, ETAG};
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::option;
+use std::str::FromStr;
+
+/// HTTP Entity Tags.
+///
+/// ETags provide an ID for a particular resource, enabling clients and servers
+/// to reason about caches and make conditional requests.
+///
+/// # Specifications
+///
+/// - [RFC 7232, section 2.3: ETag](https://tools.ietf.org/html/rfc7232#section-2.3)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::conditional::ETag;
+///
+/// let etag = ETag::new("0xcafebeef".to_string());
+///
+/// let mut res = Response::new(200);
+/// etag.apply(&mut res);
+///
+/// let etag = ETag::from_headers(res)?.unwrap();
+/// assert_eq!(etag, ETag::Strong(String::from("0xcafebeef")));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ETag {
+    /// An ETag using strong validation.
+    Strong(String),
+    /// An ETag using weak validation.
+    Weak(String),
+}
+
+impl ETag {
+    /// Create a new ETag that uses strong validation.
+    pub fn new(tag: String) -> Self {
+        debug_assert!(
+            is_valid_tag(&tag),
+            "ETags must be valid ASCII without quotes"
+        );
+        Self::Strong(tag)
+    }
+
+    /// Create a new ETag that uses weak validation.
+    pub fn new_weak(tag: String) -> Self {
+        debug_assert!(
+            is_valid_tag(&tag),
+            "ETags must be valid ASCII without quotes"
+        );
+        Self::Weak(tag)
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// Only a single ETag per resource is assumed to exist. If multiple ETag
+    /// headers are found the last one is used.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// ETag is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(ETAG) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If a header is returned we can assume at least one exists.
+        let s = headers.iter().last().unwrap().as_str();
+        Ok(Some(s.parse()?))
+    }
+
+    /// Sets the `ETag` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(ETAG, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        ETAG
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let s = self.to_string();
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(s.into()) }
+    }
+
+    /// Get the opaque tag, without quotes or weakness indicator.
+    pub fn tag(&self) -> &str {
+        match self {
+            Self::Strong(tag) | Self::Weak(tag) => tag,
+        }
+    }
+
+    /// Returns `true` if the ETag is a `Strong` value.
+    pub fn is_strong(&self) -> bool {
+        matches!(self, Self::Strong(_))
+    }
+
+    /// Returns `true` if the ETag is a `Weak` value.
+    pub fn is_weak(&self) -> bool {
+        matches!(self, Self::Weak(_))
+    }
+
+    /// Compare two ETags using the strong comparison function.
+    ///
+    /// Both ETags must be strong, and their opaque tags must be identical.
+    pub fn strong_eq(&self, other: &ETag) -> bool {
+        match (self, other) {
+            (Self::Strong(left), Self::Strong(right)) => left == right,
+            _ => false,
+        }
+    }
+
+    /// Compare two ETags using the weak comparison function.
+    ///
+    /// The opaque tags must be identical, regardless of either ETag being
+    /// weak.
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        self.tag() == other.tag()
+    }
+}
+
+impl FromStr for ETag {
+    type Err = Error;
+
+    /// Parse an entity tag such as `"xyzzy"` or `W/"xyzzy"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (weak, quoted) = match s.strip_prefix("W/") {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+
+        let tag = match quoted.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(tag) if is_valid_tag(tag) => tag,
+            _ => {
+                let msg = format!("Invalid ETag: {}", s);
+                return Err(Error::from_str(StatusCode::BadRequest, msg));
+            }
+        };
+
+        let tag = tag.to_owned();
+        Ok(if weak {
+            Self::Weak(tag)
+        } else {
+            Self::Strong(tag)
+        })
+    }
+}
+
+impl Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Strong(tag) => write!(f, r#""{}""#, tag),
+            Self::Weak(tag) => write!(f, r#"W/"{}""#, tag),
+        }
+    }
+}
+
+impl ToHeaderValues for ETag {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+/// Check the characters of an opaque tag, excluding the quotes.
+fn is_valid_tag(tag: &str) -> bool {
+    tag.bytes().all(|b| b == 0x21 || (0x23..=0x7e).contains(&b))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let etag = ETag::new("0xcafebeef".to_string());
+
+        let mut headers = Headers::new();
+        etag.apply(&mut headers);
+        assert_eq!(headers[ETAG], r#""0xcafebeef""#);
+
+        let etag = ETag::from_headers(headers)?.unwrap();
+        assert_eq!(etag, ETag::Strong(String::from("0xcafebeef")));
+        Ok(())
+    }
+
+    #[test]
+    fn smoke_weak() -> crate::Result<()> {
+        let etag = ETag::new_weak("0xcafebeef".to_string());
+
+        let mut headers = Headers::new();
+        etag.apply(&mut headers);
+        assert_eq!(headers[ETAG], r#"W/"0xcafebeef""#);
+
+        let etag = ETag::from_headers(headers)?.unwrap();
+        assert_eq!(etag, ETag::Weak(String::from("0xcafebeef")));
+        Ok(())
+    }
+
+    #[test]
+    fn comparison() {
+        // Examples from RFC 7232, section 2.3.2.
+        let weak1 = ETag::new_weak("1".into());
+        let weak2 = ETag::new_weak("2".into());
+        let strong1 = ETag::new("1".into());
+        let strong2 = ETag::new("2".into());
+
+        assert!(!weak1.strong_eq(&weak1));
+        assert!(weak1.weak_eq(&weak1));
+        assert!(!weak1.strong_eq(&weak2));
+        assert!(!weak1.weak_eq(&weak2));
+        assert!(!weak1.strong_eq(&strong1));
+        assert!(weak1.weak_eq(&strong1));
+        assert!(strong1.strong_eq(&strong1));
+        assert!(!strong1.strong_eq(&strong2));
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &["", "\"", "nori", r#"W/nori"#, r#""no"ri""#, r#"w/"nori""#] {
+            let err = s.parse::<ETag>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+
+    #[test]
+    fn empty_tag() -> crate::Result<()> {
+        assert_eq!(r#""""#.parse::<ETag>()?, ETag::Strong(String::new()));
+        Ok(())
+    }
+}
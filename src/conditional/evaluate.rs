@@ -0,0 +1,269 @@
+use crate::conditional::{ETag, IfMatch, IfModifiedSince, IfNoneMatch, IfUnmodifiedSince};
+use crate::{Method, Request, StatusCode};
+
+use std::time::SystemTime;
+
+/// The outcome of evaluating the preconditions of a request.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Outcome {
+    /// All preconditions hold; the request should be handled as usual.
+    Proceed,
+    /// The cached representation of the client is still valid; respond with
+    /// `304 Not Modified`.
+    NotModified,
+    /// A precondition failed; respond with `412 Precondition Failed`.
+    PreconditionFailed,
+}
+
+impl Outcome {
+    /// Get the status code to respond with, if the request should not
+    /// proceed.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Outcome::Proceed => None,
+            Outcome::NotModified => Some(StatusCode::NotModified),
+            Outcome::PreconditionFailed => Some(StatusCode::PreconditionFailed),
+        }
+    }
+}
+
+/// Evaluate the conditional headers of a request against the current state of
+/// the target resource.
+///
+/// `etag` and `last_modified` describe the current representation of the
+/// resource. Pass `None` for `etag` when the resource has no current
+/// representation, and `None` for `last_modified` when no modification date
+/// is known. Headers are evaluated in the order mandated by RFC 7232:
+///
+/// 1. `If-Match`, or `If-Unmodified-Since` when `If-Match` is absent. When the
+///    condition is false the outcome is `PreconditionFailed`.
+/// 2. `If-None-Match`, or `If-Modified-Since` when `If-None-Match` is absent
+///    and the method is `GET` or `HEAD`. When the condition is false the
+///    outcome is `NotModified` for `GET` and `HEAD`, and `PreconditionFailed`
+///    otherwise.
+///
+/// `If-Modified-Since` and `If-Unmodified-Since` are ignored when their date
+/// is invalid, as the RFC requires.
+///
+/// # Errors
+///
+/// An error with a status code of `400: Bad Request` is returned if the
+/// `If-Match` or `If-None-Match` header is malformed.
+///
+/// # Specifications
+///
+/// - [RFC 7232, section 6: Precedence](https://tools.ietf.org/html/rfc7232#section-6)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::conditional::{self, ETag, Outcome};
+/// use http_types::{Method, Request, Url};
+///
+/// let mut req = Request::new(Method::Put, Url::parse("https://example.com")?);
+/// req.insert_header("If-Match", r#""v1""#);
+///
+/// let etag = ETag::new("v2".to_owned());
+/// let outcome = conditional::evaluate(&req, Some(&etag), None)?;
+/// assert_eq!(outcome, Outcome::PreconditionFailed);
+/// #
+/// # Ok(()) }
+/// ```
+pub fn evaluate(
+    req: &Request,
+    etag: Option<&ETag>,
+    last_modified: Option<SystemTime>,
+) -> crate::Result<Outcome> {
+    let is_read = matches!(req.method(), Method::Get | Method::Head);
+
+    // Step 1 and 2.
+    if let Some(if_match) = IfMatch::from_headers(req)? {
+        if !if_match.matches(etag) {
+            return Ok(Outcome::PreconditionFailed);
+        }
+    } else if let (Ok(Some(since)), Some(last_modified)) =
+        (IfUnmodifiedSince::from_headers(req), last_modified)
+    {
+        if since.is_modified(last_modified) {
+            return Ok(Outcome::PreconditionFailed);
+        }
+    }
+
+    // Step 3 and 4.
+    if let Some(if_none_match) = IfNoneMatch::from_headers(req)? {
+        if if_none_match.matches(etag) {
+            return match is_read {
+                true => Ok(Outcome::NotModified),
+                false => Ok(Outcome::PreconditionFailed),
+            };
+        }
+    } else if let (true, Ok(Some(since)), Some(last_modified)) =
+        (is_read, IfModifiedSince::from_headers(req), last_modified)
+    {
+        if !since.is_modified(last_modified) {
+            return Ok(Outcome::NotModified);
+        }
+    }
+
+    Ok(Outcome::Proceed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HttpDate;
+    use crate::Url;
+    use std::time::Duration;
+
+    fn request(method: Method, headers: &[(&str, &str)]) -> Request {
+        let mut req = Request::new(method, Url::parse("https://example.com").unwrap());
+        for (name, value) in headers {
+            req.insert_header(*name, *value);
+        }
+        req
+    }
+
+    fn etag(tag: &str) -> ETag {
+        ETag::new(tag.to_owned())
+    }
+
+    #[test]
+    fn proceeds_without_conditions() -> crate::Result<()> {
+        let req = request(Method::Get, &[]);
+        assert_eq!(evaluate(&req, None, None)?, Outcome::Proceed);
+        Ok(())
+    }
+
+    #[test]
+    fn if_match() -> crate::Result<()> {
+        let req = request(Method::Put, &[("If-Match", r#""a", "b""#)]);
+        assert_eq!(evaluate(&req, Some(&etag("b")), None)?, Outcome::Proceed);
+        assert_eq!(
+            evaluate(&req, Some(&etag("c")), None)?,
+            Outcome::PreconditionFailed
+        );
+
+        let req = request(Method::Put, &[("If-Match", "*")]);
+        assert_eq!(evaluate(&req, Some(&etag("c")), None)?, Outcome::Proceed);
+        assert_eq!(evaluate(&req, None, None)?, Outcome::PreconditionFailed);
+        Ok(())
+    }
+
+    #[test]
+    fn if_none_match() -> crate::Result<()> {
+        let header = ("If-None-Match", r#"W/"a""#);
+        let req = request(Method::Get, &[header]);
+        assert_eq!(
+            evaluate(&req, Some(&etag("a")), None)?,
+            Outcome::NotModified
+        );
+        assert_eq!(evaluate(&req, Some(&etag("b")), None)?, Outcome::Proceed);
+
+        let req = request(Method::Post, &[header]);
+        assert_eq!(
+            evaluate(&req, Some(&etag("a")), None)?,
+            Outcome::PreconditionFailed
+        );
+
+        // `*` creates a resource only if it doesn't exist yet.
+        let req = request(Method::Put, &[("If-None-Match", "*")]);
+        assert_eq!(evaluate(&req, None, None)?, Outcome::Proceed);
+        assert_eq!(
+            evaluate(&req, Some(&etag("a")), None)?,
+            Outcome::PreconditionFailed
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn dates() -> crate::Result<()> {
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let date = HttpDate::from(modified).to_string();
+        let later = Some(modified + Duration::from_secs(60));
+
+        let req = request(Method::Get, &[("If-Modified-Since", &date)]);
+        assert_eq!(evaluate(&req, None, Some(modified))?, Outcome::NotModified);
+        assert_eq!(evaluate(&req, None, later)?, Outcome::Proceed);
+        assert_eq!(evaluate(&req, None, None)?, Outcome::Proceed);
+
+        // If-Modified-Since only applies to GET and HEAD.
+        let req = request(Method::Post, &[("If-Modified-Since", &date)]);
+        assert_eq!(evaluate(&req, None, Some(modified))?, Outcome::Proceed);
+
+        let req = request(Method::Put, &[("If-Unmodified-Since", &date)]);
+        assert_eq!(evaluate(&req, None, Some(modified))?, Outcome::Proceed);
+        assert_eq!(evaluate(&req, None, later)?, Outcome::PreconditionFailed);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_dates_are_ignored() -> crate::Result<()> {
+        let modified = Some(SystemTime::UNIX_EPOCH);
+        let req = request(
+            Method::Get,
+            &[
+                ("If-Unmodified-Since", "yesterday"),
+                ("If-Modified-Since", "tomorrow"),
+            ],
+        );
+        assert_eq!(evaluate(&req, None, modified)?, Outcome::Proceed);
+        Ok(())
+    }
+
+    #[test]
+    fn precedence() -> crate::Result<()> {
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        let later = HttpDate::from(modified + Duration::from_secs(60)).to_string();
+        let earlier = HttpDate::from(modified - Duration::from_secs(60)).to_string();
+
+        // If-Match takes precedence over If-Unmodified-Since.
+        let req = request(
+            Method::Put,
+            &[("If-Match", r#""a""#), ("If-Unmodified-Since", &earlier)],
+        );
+        assert_eq!(
+            evaluate(&req, Some(&etag("a")), Some(modified))?,
+            Outcome::Proceed
+        );
+
+        // If-None-Match takes precedence over If-Modified-Since.
+        let req = request(
+            Method::Get,
+            &[("If-None-Match", r#""b""#), ("If-Modified-Since", &later)],
+        );
+        assert_eq!(
+            evaluate(&req, Some(&etag("a")), Some(modified))?,
+            Outcome::Proceed
+        );
+
+        // A failed If-Match wins over a matching If-None-Match.
+        let req = request(
+            Method::Get,
+            &[("If-Match", r#""b""#), ("If-None-Match", r#""a""#)],
+        );
+        assert_eq!(
+            evaluate(&req, Some(&etag("a")), None)?,
+            Outcome::PreconditionFailed
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_malformed_etags() {
+        let req = request(Method::Get, &[("If-None-Match", "a")]);
+        let err = evaluate(&req, None, None).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+
+    #[test]
+    fn outcome_status() {
+        assert_eq!(Outcome::Proceed.status(), None);
+        assert_eq!(Outcome::NotModified.status(), Some(StatusCode::NotModified));
+        assert_eq!(
+            Outcome::PreconditionFailed.status(),
+            Some(StatusCode::PreconditionFailed)
+        );
+    }
+}
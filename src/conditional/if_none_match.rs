@@ -0,0 +1,218 @@
+use crate::conditional::ETag;
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, IF_NONE_MATCH};
+use crate::utils::split_quoted;
+
+use std::fmt::Write;
+use std::option;
+use std::slice;
+
+/// Apply the HTTP method if the ETag does not match.
+///
+/// # Specifications
+///
+/// - [RFC 7232, section 3.2: If-None-Match](https://tools.ietf.org/html/rfc7232#section-3.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::conditional::{IfNoneMatch, ETag};
+///
+/// let mut entries = IfNoneMatch::new();
+/// entries.push(ETag::new("0xcafebeef".to_string()));
+/// entries.push(ETag::new("0xbeefcafe".to_string()));
+///
+/// let mut res = Response::new(200);
+/// entries.apply(&mut res);
+///
+/// let entries = IfNoneMatch::from_headers(res)?.unwrap();
+/// let mut entries = entries.iter();
+/// assert_eq!(entries.next().unwrap(), &ETag::new("0xcafebeef".to_string()));
+/// assert_eq!(entries.next().unwrap(), &ETag::new("0xbeefcafe".to_string()));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IfNoneMatch {
+    entries: Vec<ETag>,
+    wildcard: bool,
+}
+
+impl IfNoneMatch {
+    /// Create a new instance of `IfNoneMatch`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if an
+    /// ETag is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(IF_NONE_MATCH) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut this = Self::new();
+        for value in headers {
+            for part in split_quoted(value.as_str(), ',') {
+                match part {
+                    "*" => this.wildcard = true,
+                    part => this.entries.push(part.parse()?),
+                }
+            }
+        }
+        Ok(Some(this))
+    }
+
+    /// Sets the `If-None-Match` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(IF_NONE_MATCH, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        IF_NONE_MATCH
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::new();
+        for (n, etag) in self.entries.iter().enumerate() {
+            match n {
+                0 => write!(output, "{}", etag).unwrap(),
+                _ => write!(output, ", {}", etag).unwrap(),
+            };
+        }
+
+        if self.wildcard {
+            if !output.is_empty() {
+                output.push_str(", ");
+            }
+            output.push('*');
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Push an ETag into the list of entries.
+    pub fn push(&mut self, etag: impl Into<ETag>) {
+        self.entries.push(etag.into());
+    }
+
+    /// Returns `true` if a wildcard directive was set.
+    pub fn wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    /// Set the wildcard directive.
+    pub fn set_wildcard(&mut self, wildcard: bool) {
+        self.wildcard = wildcard
+    }
+
+    /// Returns `true` if the current ETag of the resource matches one of the
+    /// entries, using the weak comparison function.
+    ///
+    /// The wildcard matches any current ETag. When the resource has no current
+    /// representation, pass `None`; nothing matches it.
+    pub fn matches(&self, current: Option<&ETag>) -> bool {
+        match current {
+            Some(current) => self.wildcard || self.entries.iter().any(|e| e.weak_eq(current)),
+            None => false,
+        }
+    }
+
+    /// An iterator visiting all ETags.
+    pub fn iter(&self) -> slice::Iter<'_, ETag> {
+        self.entries.iter()
+    }
+}
+
+impl IntoIterator for IfNoneMatch {
+    type Item = ETag;
+    type IntoIter = std::vec::IntoIter<ETag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a IfNoneMatch {
+    type Item = &'a ETag;
+    type IntoIter = slice::Iter<'a, ETag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl ToHeaderValues for IfNoneMatch {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Response;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut entries = IfNoneMatch::new();
+        entries.push(ETag::new("0xcafebeef".to_string()));
+        entries.push(ETag::new_weak("0xbeefcafe".to_string()));
+
+        let mut res = Response::new(200);
+        entries.apply(&mut res);
+        assert_eq!(res[IF_NONE_MATCH], r#""0xcafebeef", W/"0xbeefcafe""#);
+
+        let parsed = IfNoneMatch::from_headers(res)?.unwrap();
+        assert_eq!(parsed, entries);
+        Ok(())
+    }
+
+    #[test]
+    fn wildcard() -> crate::Result<()> {
+        let mut entries = IfNoneMatch::new();
+        entries.set_wildcard(true);
+
+        let mut res = Response::new(200);
+        entries.apply(&mut res);
+
+        let entries = IfNoneMatch::from_headers(res)?.unwrap();
+        assert!(entries.wildcard());
+        assert_eq!(entries.iter().next(), None);
+        assert!(entries.matches(Some(&ETag::new_weak("nori".into()))));
+        assert!(!entries.matches(None));
+        Ok(())
+    }
+
+    #[test]
+    fn matches_weakly() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(IF_NONE_MATCH, r#""xyzzy", W/"r2d2xxxx""#);
+        let entries = IfNoneMatch::from_headers(headers)?.unwrap();
+        assert!(entries.matches(Some(&ETag::new("xyzzy".into()))));
+        assert!(entries.matches(Some(&ETag::new_weak("xyzzy".into()))));
+        assert!(entries.matches(Some(&ETag::new("r2d2xxxx".into()))));
+        assert!(!entries.matches(Some(&ETag::new("c3piozzzz".into()))));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert(IF_NONE_MATCH, "xyzzy");
+        let err = IfNoneMatch::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
@@ -0,0 +1,41 @@
+//! HTTP conditional headers.
+//!
+//! Web page performance can be significantly improved by caching resources.
+//! This submodule includes headers and types to communicate how and when to
+//! cache resources, and to evaluate whether a request's preconditions hold.
+//!
+//! # Specifications
+//!
+//! - [RFC 7232: HTTP/1.1: Conditional Requests](https://tools.ietf.org/html/rfc7232)
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> {
+//! #
+//! use http_types::conditional::{self, ETag, Outcome};
+//! use http_types::{Method, Request, Url};
+//!
+//! let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+//! req.insert_header("If-None-Match", r#"W/"67ab43", "54ed21""#);
+//!
+//! let etag = ETag::new("54ed21".to_owned());
+//! let outcome = conditional::evaluate(&req, Some(&etag), None)?;
+//! assert_eq!(outcome, Outcome::NotModified);
+//! #
+//! # Ok(()) }
+//! ```
+
+mod etag;
+mod evaluate;
+mod if_match;
+mod if_modified_since;
+mod if_none_match;
+mod if_unmodified_since;
+
+pub use etag::ETag;
+pub use evaluate::{evaluate, Outcome};
+pub use if_match::IfMatch;
+pub use if_modified_since::IfModifiedSince;
+pub use if_none_match::IfNoneMatch;
+pub use if_unmodified_since::IfUnmodifiedSince;
@@ -0,0 +1,267 @@
+use crate::content::MediaTypeProposal;
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, ACCEPT};
+use crate::utils::split_quoted;
+use crate::{Error, Mime, StatusCode};
+
+use std::fmt::Write;
+use std::option;
+use std::slice;
+
+/// Client header advertising which media types it can understand.
+///
+/// Proposals are ordered by weight, and then by specificity, highest first.
+///
+/// # Specifications
+///
+/// - [RFC 7231, section 5.3.2: Accept](https://tools.ietf.org/html/rfc7231#section-5.3.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::Accept;
+/// use http_types::{mime, Method, Request, Url};
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// req.insert_header("Accept", "text/*;q=0.5, text/html, application/json;q=0.8");
+///
+/// let accept = Accept::from_headers(&req)?.unwrap();
+/// assert_eq!(accept.negotiate(&[mime::PLAIN, mime::JSON])?, mime::JSON);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Accept {
+    entries: Vec<MediaTypeProposal>,
+}
+
+impl Accept {
+    /// Create a new instance of `Accept`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if a
+    /// media range or weight is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(ACCEPT) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut accept = Self::new();
+        for value in headers {
+            for part in split_quoted(value.as_str(), ',') {
+                accept.entries.push(part.parse()?);
+            }
+        }
+        accept.sort();
+        Ok(Some(accept))
+    }
+
+    /// Push a media type proposal.
+    pub fn push(&mut self, proposal: impl Into<MediaTypeProposal>) {
+        self.entries.push(proposal.into());
+    }
+
+    /// Sort the proposals by weight and then by specificity, highest first.
+    ///
+    /// Proposals that are otherwise equal keep their relative order.
+    pub fn sort(&mut self) {
+        self.entries.sort_by(|a, b| {
+            b.weight()
+                .partial_cmp(&a.weight())
+                .unwrap()
+                .then(b.specificity().cmp(&a.specificity()))
+        });
+    }
+
+    /// Determine the most suitable media type out of the media types the
+    /// server has available.
+    ///
+    /// Each media type is weighed by the most specific media range that
+    /// includes it. When several media types are equally acceptable the one
+    /// listed first in `available` wins.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `406: Not Acceptable` is returned if
+    /// none of the available media types are acceptable.
+    pub fn negotiate(&self, available: &[Mime]) -> crate::Result<Mime> {
+        let mut best: Option<(&Mime, f32)> = None;
+        for mime in available {
+            let weight = match self.weight_of(mime) {
+                Some(weight) if weight > 0.0 => weight,
+                _ => continue,
+            };
+            match best {
+                Some((_, best_weight)) if best_weight >= weight => {}
+                _ => best = Some((mime, weight)),
+            }
+        }
+
+        match best {
+            Some((mime, _)) => Ok(mime.clone()),
+            None => {
+                let msg = "No acceptable media type available";
+                Err(Error::from_str(StatusCode::NotAcceptable, msg))
+            }
+        }
+    }
+
+    /// Sets the `Accept` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(ACCEPT, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        ACCEPT
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::new();
+        for (n, proposal) in self.entries.iter().enumerate() {
+            match n {
+                0 => write!(output, "{}", proposal).unwrap(),
+                _ => write!(output, ", {}", proposal).unwrap(),
+            };
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// An iterator visiting all entries.
+    pub fn iter(&self) -> slice::Iter<'_, MediaTypeProposal> {
+        self.entries.iter()
+    }
+
+    /// Get the weight of the most specific media range including `mime`.
+    fn weight_of(&self, mime: &Mime) -> Option<f32> {
+        let mut best: Option<&MediaTypeProposal> = None;
+        for proposal in self.entries.iter().filter(|p| p.matches(mime)) {
+            match best {
+                Some(best) if best.specificity() >= proposal.specificity() => {}
+                _ => best = Some(proposal),
+            }
+        }
+        best.map(|proposal| proposal.weight())
+    }
+}
+
+impl IntoIterator for Accept {
+    type Item = MediaTypeProposal;
+    type IntoIter = std::vec::IntoIter<MediaTypeProposal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Accept {
+    type Item = &'a MediaTypeProposal;
+    type IntoIter = slice::Iter<'a, MediaTypeProposal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl ToHeaderValues for Accept {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mime;
+    use crate::Response;
+
+    fn parse(value: &str) -> crate::Result<Accept> {
+        let mut headers = Headers::new();
+        headers.insert(ACCEPT, value);
+        Ok(Accept::from_headers(headers)?.unwrap())
+    }
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut accept = Accept::new();
+        accept.push(mime::HTML);
+        accept.push(MediaTypeProposal::new(mime::ANY, Some(0.1))?);
+
+        let mut res = Response::new(200);
+        accept.apply(&mut res);
+        assert_eq!(res[ACCEPT], "text/html;charset=utf-8, */*;q=0.1");
+
+        let accept = Accept::from_headers(res)?.unwrap();
+        assert_eq!(accept.iter().count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn orders_by_weight_and_specificity() -> crate::Result<()> {
+        let accept = parse("*/*, text/*, text/html;level=1, text/html, image/png;q=0.5")?;
+        let ranges: Vec<_> = accept.iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            ranges,
+            vec![
+                "text/html;level=1",
+                "text/html",
+                "text/*",
+                "*/*",
+                "image/png;q=0.5"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate_uses_most_specific_range() -> crate::Result<()> {
+        // Example from RFC 7231, section 5.3.2.
+        let accept = parse(
+            "text/*;q=0.3, text/html;q=0.7, text/html;level=1, text/html;level=2;q=0.4, */*;q=0.5",
+        )?;
+        assert_eq!(accept.weight_of(&"text/html;level=1".parse()?), Some(1.0));
+        assert_eq!(accept.weight_of(&"text/html".parse()?), Some(0.7));
+        assert_eq!(accept.weight_of(&"text/plain".parse()?), Some(0.3));
+        assert_eq!(accept.weight_of(&"image/jpeg".parse()?), Some(0.5));
+        assert_eq!(accept.weight_of(&"text/html;level=2".parse()?), Some(0.4));
+        assert_eq!(accept.weight_of(&"text/html;level=3".parse()?), Some(0.7));
+
+        let available = ["image/jpeg".parse()?, "text/html".parse()?];
+        assert_eq!(accept.negotiate(&available)?.essence(), "text/html");
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate_prefers_server_order_on_ties() -> crate::Result<()> {
+        let accept = parse("application/json, text/plain")?;
+        assert_eq!(accept.negotiate(&[mime::PLAIN, mime::JSON])?, mime::PLAIN);
+        Ok(())
+    }
+
+    #[test]
+    fn not_acceptable() -> crate::Result<()> {
+        let accept = parse("application/json, text/*;q=0")?;
+        let err = accept.negotiate(&[mime::PLAIN, mime::HTML]).unwrap_err();
+        assert_eq!(err.status(), 406);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let err = parse("text/html;q=nori").unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
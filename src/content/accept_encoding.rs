@@ -0,0 +1,310 @@
+use crate::content::{Encoding, EncodingProposal};
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, ACCEPT_ENCODING};
+use crate::utils::{parse_weight, split_quoted};
+use crate::{Error, StatusCode};
+
+use std::fmt::Write;
+use std::option;
+use std::slice;
+
+/// Client header advertising which content encodings it understands.
+///
+/// Proposals are ordered by weight, highest first. Encodings this crate
+/// doesn't know about are ignored.
+///
+/// # Specifications
+///
+/// - [RFC 7231, section 5.3.4: Accept-Encoding](https://tools.ietf.org/html/rfc7231#section-5.3.4)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::{AcceptEncoding, Encoding};
+/// use http_types::{Method, Request, Url};
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// req.insert_header("Accept-Encoding", "gzip;q=0.8, br, identity;q=0, *");
+///
+/// let accept = AcceptEncoding::from_headers(&req)?.unwrap();
+/// assert_eq!(accept.iter().next().unwrap().encoding(), Encoding::Brotli);
+/// assert_eq!(accept.negotiate(&[Encoding::Gzip, Encoding::Deflate])?, Encoding::Deflate);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AcceptEncoding {
+    wildcard: Option<f32>,
+    entries: Vec<EncodingProposal>,
+}
+
+impl AcceptEncoding {
+    /// Create a new instance of `AcceptEncoding`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if a
+    /// weight is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(ACCEPT_ENCODING) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut accept = Self::new();
+        for value in headers {
+            for part in split_quoted(value.as_str(), ',') {
+                let name = part.split(';').next().unwrap_or("").trim();
+                if name == "*" {
+                    accept.wildcard = Some(parse_wildcard(part)?);
+                } else if name.parse::<Encoding>().is_ok() {
+                    accept.entries.push(part.parse()?);
+                }
+            }
+        }
+        accept.sort();
+        Ok(Some(accept))
+    }
+
+    /// Push an encoding proposal.
+    pub fn push(&mut self, proposal: impl Into<EncodingProposal>) {
+        self.entries.push(proposal.into());
+    }
+
+    /// Get the weight of the `*` wildcard, if present.
+    pub fn wildcard(&self) -> Option<f32> {
+        self.wildcard
+    }
+
+    /// Set the weight of the `*` wildcard. Passing `None` removes it.
+    pub fn set_wildcard(&mut self, weight: Option<f32>) {
+        self.wildcard = weight;
+    }
+
+    /// Sort the proposals by weight, highest first.
+    ///
+    /// Proposals with the same weight keep their relative order.
+    pub fn sort(&mut self) {
+        self.entries
+            .sort_by(|a, b| b.weight().partial_cmp(&a.weight()).unwrap());
+    }
+
+    /// Determine the most suitable encoding out of the encodings the server
+    /// has available.
+    ///
+    /// When several encodings are equally acceptable the one listed first in
+    /// `available` wins. If none of them are acceptable, `Encoding::Identity`
+    /// is returned.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `406: Not Acceptable` is returned if no
+    /// encoding is acceptable and the client explicitly forbade the identity
+    /// encoding, either with `identity;q=0` or with `*;q=0`.
+    pub fn negotiate(&self, available: &[Encoding]) -> crate::Result<Encoding> {
+        let mut best: Option<(Encoding, f32)> = None;
+        for &encoding in available {
+            let weight = match self.weight_of(encoding) {
+                Some(weight) if weight > 0.0 => weight,
+                _ => continue,
+            };
+            match best {
+                Some((_, best_weight)) if best_weight >= weight => {}
+                _ => best = Some((encoding, weight)),
+            }
+        }
+
+        match best {
+            Some((encoding, _)) => Ok(encoding),
+            None if self.weight_of(Encoding::Identity) == Some(0.0) => {
+                let msg = "No acceptable content encoding available";
+                Err(Error::from_str(StatusCode::NotAcceptable, msg))
+            }
+            None => Ok(Encoding::Identity),
+        }
+    }
+
+    /// Sets the `Accept-Encoding` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(ACCEPT_ENCODING, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        ACCEPT_ENCODING
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::new();
+        for (n, proposal) in self.entries.iter().enumerate() {
+            match n {
+                0 => write!(output, "{}", proposal).unwrap(),
+                _ => write!(output, ", {}", proposal).unwrap(),
+            };
+        }
+
+        if let Some(weight) = self.wildcard {
+            if !output.is_empty() {
+                output.push_str(", ");
+            }
+            if weight == 1.0 {
+                output.push('*');
+            } else {
+                write!(output, "*;q={}", weight).unwrap();
+            }
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// An iterator visiting all entries.
+    pub fn iter(&self) -> slice::Iter<'_, EncodingProposal> {
+        self.entries.iter()
+    }
+
+    /// Get the weight the client assigned to an encoding, either directly or
+    /// through the wildcard.
+    fn weight_of(&self, encoding: Encoding) -> Option<f32> {
+        self.entries
+            .iter()
+            .find(|proposal| proposal.encoding() == encoding)
+            .map(|proposal| proposal.weight())
+            .or(self.wildcard)
+    }
+}
+
+/// Parse the weight of a `*` proposal.
+fn parse_wildcard(s: &str) -> crate::Result<f32> {
+    let mut weight = 1.0;
+    for param in s.split(';').skip(1) {
+        let mut param = param.splitn(2, '=');
+        if param.next().unwrap_or("").trim().eq_ignore_ascii_case("q") {
+            weight = parse_weight(param.next().unwrap_or(""))?;
+        }
+    }
+    Ok(weight)
+}
+
+impl IntoIterator for AcceptEncoding {
+    type Item = EncodingProposal;
+    type IntoIter = std::vec::IntoIter<EncodingProposal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a AcceptEncoding {
+    type Item = &'a EncodingProposal;
+    type IntoIter = slice::Iter<'a, EncodingProposal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl ToHeaderValues for AcceptEncoding {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Response;
+
+    fn parse(value: &str) -> crate::Result<AcceptEncoding> {
+        let mut headers = Headers::new();
+        headers.insert(ACCEPT_ENCODING, value);
+        Ok(AcceptEncoding::from_headers(headers)?.unwrap())
+    }
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut accept = AcceptEncoding::new();
+        accept.push(Encoding::Gzip);
+        accept.push(EncodingProposal::new(Encoding::Brotli, Some(0.5))?);
+        accept.set_wildcard(Some(0.1));
+
+        let mut res = Response::new(200);
+        accept.apply(&mut res);
+        assert_eq!(res[ACCEPT_ENCODING], "gzip, br;q=0.5, *;q=0.1");
+
+        let accept = AcceptEncoding::from_headers(res)?.unwrap();
+        assert_eq!(accept.wildcard(), Some(0.1));
+        let encodings: Vec<_> = accept.iter().map(|p| p.encoding()).collect();
+        assert_eq!(encodings, vec![Encoding::Gzip, Encoding::Brotli]);
+        Ok(())
+    }
+
+    #[test]
+    fn orders_by_weight_and_skips_unknown() -> crate::Result<()> {
+        let accept = parse("compress, gzip;q=0.8, br, identity;q=0, *")?;
+        let proposals: Vec<_> = accept.iter().map(|p| (p.encoding(), p.weight())).collect();
+        assert_eq!(
+            proposals,
+            vec![
+                (Encoding::Brotli, 1.0),
+                (Encoding::Gzip, 0.8),
+                (Encoding::Identity, 0.0)
+            ]
+        );
+        assert_eq!(accept.wildcard(), Some(1.0));
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate() -> crate::Result<()> {
+        let accept = parse("gzip;q=0.8, br")?;
+        let available = [Encoding::Gzip, Encoding::Brotli];
+        assert_eq!(accept.negotiate(&available)?, Encoding::Brotli);
+        assert_eq!(accept.negotiate(&[Encoding::Gzip])?, Encoding::Gzip);
+        assert_eq!(accept.negotiate(&[Encoding::Deflate])?, Encoding::Identity);
+
+        // Ties are broken by the server's order.
+        let accept = parse("gzip, br")?;
+        assert_eq!(accept.negotiate(&available)?, Encoding::Gzip);
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate_wildcard() -> crate::Result<()> {
+        let accept = parse("gzip;q=0, *;q=0.5")?;
+        let available = [Encoding::Gzip, Encoding::Brotli];
+        assert_eq!(accept.negotiate(&available)?, Encoding::Brotli);
+        Ok(())
+    }
+
+    #[test]
+    fn not_acceptable_when_identity_forbidden() -> crate::Result<()> {
+        let accept = parse("br, identity;q=0")?;
+        let err = accept.negotiate(&[Encoding::Gzip]).unwrap_err();
+        assert_eq!(err.status(), 406);
+
+        let accept = parse("br, *;q=0")?;
+        let err = accept.negotiate(&[Encoding::Gzip]).unwrap_err();
+        assert_eq!(err.status(), 406);
+
+        // An explicit identity entry overrides the wildcard.
+        let accept = parse("identity, *;q=0")?;
+        assert_eq!(accept.negotiate(&[Encoding::Gzip])?, Encoding::Identity);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_invalid_weight() {
+        let err = parse("gzip;q=2").unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
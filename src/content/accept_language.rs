@@ -0,0 +1,330 @@
+use crate::content::{LanguageProposal, LanguageTag};
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, ACCEPT_LANGUAGE};
+use crate::utils::split_quoted;
+use crate::{Error, StatusCode};
+
+use std::fmt::Write;
+use std::option;
+use std::slice;
+
+/// Client header advertising which natural languages it prefers.
+///
+/// Proposals are ordered by weight, highest first.
+///
+/// # Specifications
+///
+/// - [RFC 7231, section 5.3.5: Accept-Language](https://tools.ietf.org/html/rfc7231#section-5.3.5)
+/// - [RFC 4647, section 3: Matching Schemes](https://tools.ietf.org/html/rfc4647#section-3)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::{AcceptLanguage, LanguageTag};
+/// use http_types::{Method, Request, Url};
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// req.insert_header("Accept-Language", "fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5");
+///
+/// let accept = AcceptLanguage::from_headers(&req)?.unwrap();
+/// let available = ["en-US".parse()?, "fr-FR".parse()?];
+/// assert_eq!(accept.negotiate(&available)?, "fr-FR".parse::<LanguageTag>()?);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AcceptLanguage {
+    entries: Vec<LanguageProposal>,
+}
+
+impl AcceptLanguage {
+    /// Create a new instance of `AcceptLanguage`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if a
+    /// language range or weight is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(ACCEPT_LANGUAGE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut accept = Self::new();
+        for value in headers {
+            for part in split_quoted(value.as_str(), ',') {
+                accept.entries.push(part.parse()?);
+            }
+        }
+        accept.sort();
+        Ok(Some(accept))
+    }
+
+    /// Push a language proposal.
+    pub fn push(&mut self, proposal: impl Into<LanguageProposal>) {
+        self.entries.push(proposal.into());
+    }
+
+    /// Sort the proposals by weight, highest first.
+    ///
+    /// Proposals with the same weight keep their relative order.
+    pub fn sort(&mut self) {
+        self.entries
+            .sort_by(|a, b| b.weight().partial_cmp(&a.weight()).unwrap());
+    }
+
+    /// Select the available languages matched by the client's language
+    /// ranges, using RFC 4647 basic filtering.
+    ///
+    /// Each language is weighed by the most specific range that matches it.
+    /// Languages with a weight of zero are excluded, and the rest are
+    /// returned ordered by weight.
+    pub fn filter(&self, available: &[LanguageTag]) -> Vec<LanguageTag> {
+        let mut matches: Vec<(&LanguageTag, f32)> = available
+            .iter()
+            .filter_map(|tag| match self.weight_of(tag) {
+                Some(weight) if weight > 0.0 => Some((tag, weight)),
+                _ => None,
+            })
+            .collect();
+        matches.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        matches.into_iter().map(|(tag, _)| tag.clone()).collect()
+    }
+
+    /// Select the single available language that best matches the client's
+    /// language ranges, using RFC 4647 lookup.
+    ///
+    /// Ranges are tried in order of weight. Each range is progressively
+    /// truncated, so `zh-Hant-CN` falls back to `zh-Hant` and then `zh`, until
+    /// it equals one of the available languages. The `*` range and ranges
+    /// with a weight of zero are skipped.
+    pub fn lookup(&self, available: &[LanguageTag]) -> Option<LanguageTag> {
+        self.entries
+            .iter()
+            .filter(|proposal| proposal.weight() > 0.0 && !proposal.language().is_wildcard())
+            .find_map(|proposal| lookup_range(proposal.language().as_str(), available))
+            .cloned()
+    }
+
+    /// Determine the most suitable language out of the languages the server
+    /// has available.
+    ///
+    /// Ranges are tried in order of weight. For each range the first
+    /// available language it matches using `filter` is used, falling back to
+    /// `lookup` of that range, before moving on to the next range. Languages
+    /// which the client gave a weight of zero are never selected.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `406: Not Acceptable` is returned if
+    /// none of the available languages are acceptable.
+    pub fn negotiate(&self, available: &[LanguageTag]) -> crate::Result<LanguageTag> {
+        let acceptable = |tag: &&LanguageTag| !matches!(self.weight_of(tag), Some(w) if w <= 0.0);
+
+        let mut proposals: Vec<&LanguageProposal> = self.entries.iter().collect();
+        proposals.sort_by(|a, b| b.weight().partial_cmp(&a.weight()).unwrap());
+        for proposal in proposals.into_iter().filter(|p| p.weight() > 0.0) {
+            let range = proposal.language();
+            let language = available
+                .iter()
+                .filter(|tag| range.matches(tag))
+                .find(acceptable)
+                .or_else(|| match range.is_wildcard() {
+                    true => None,
+                    false => lookup_range(range.as_str(), available).filter(acceptable),
+                });
+            if let Some(language) = language {
+                return Ok(language.clone());
+            }
+        }
+
+        let msg = "No acceptable language available";
+        Err(Error::from_str(StatusCode::NotAcceptable, msg))
+    }
+
+    /// Sets the `Accept-Language` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(ACCEPT_LANGUAGE, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        ACCEPT_LANGUAGE
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::new();
+        for (n, proposal) in self.entries.iter().enumerate() {
+            match n {
+                0 => write!(output, "{}", proposal).unwrap(),
+                _ => write!(output, ", {}", proposal).unwrap(),
+            };
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// An iterator visiting all entries.
+    pub fn iter(&self) -> slice::Iter<'_, LanguageProposal> {
+        self.entries.iter()
+    }
+
+    /// Get the weight of the most specific range matching `tag`.
+    fn weight_of(&self, tag: &LanguageTag) -> Option<f32> {
+        let mut best: Option<&LanguageProposal> = None;
+        for proposal in self.entries.iter().filter(|p| p.language().matches(tag)) {
+            match best {
+                Some(best)
+                    if best.language().specificity() >= proposal.language().specificity() => {}
+                _ => best = Some(proposal),
+            }
+        }
+        best.map(|proposal| proposal.weight())
+    }
+}
+
+/// Find the available language a range falls back to, using RFC 4647 lookup.
+fn lookup_range<'a>(mut range: &str, available: &'a [LanguageTag]) -> Option<&'a LanguageTag> {
+    loop {
+        if let Some(tag) = available.iter().find(|tag| tag == &&range) {
+            return Some(tag);
+        }
+        range = &range[..range.rfind('-')?];
+        // Single character subtags can't stand on their own.
+        if range.len() >= 2 && range.as_bytes()[range.len() - 2] == b'-' {
+            range = &range[..range.len() - 2];
+        }
+    }
+}
+
+impl IntoIterator for AcceptLanguage {
+    type Item = LanguageProposal;
+    type IntoIter = std::vec::IntoIter<LanguageProposal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a AcceptLanguage {
+    type Item = &'a LanguageProposal;
+    type IntoIter = slice::Iter<'a, LanguageProposal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl ToHeaderValues for AcceptLanguage {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::content::LanguageRange;
+    use crate::Response;
+
+    fn parse(value: &str) -> crate::Result<AcceptLanguage> {
+        let mut headers = Headers::new();
+        headers.insert(ACCEPT_LANGUAGE, value);
+        Ok(AcceptLanguage::from_headers(headers)?.unwrap())
+    }
+
+    fn tags(tags: &[&str]) -> Vec<LanguageTag> {
+        tags.iter().map(|tag| tag.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut accept = AcceptLanguage::new();
+        accept.push("en-US".parse::<LanguageRange>()?);
+        accept.push(LanguageProposal::new(
+            "en".parse::<LanguageRange>()?,
+            Some(0.5),
+        )?);
+
+        let mut res = Response::new(200);
+        accept.apply(&mut res);
+        assert_eq!(res[ACCEPT_LANGUAGE], "en-US, en;q=0.5");
+
+        let accept = AcceptLanguage::from_headers(res)?.unwrap();
+        let ranges: Vec<_> = accept.iter().map(|p| p.language().as_str()).collect();
+        assert_eq!(ranges, vec!["en-US", "en"]);
+        Ok(())
+    }
+
+    #[test]
+    fn filter() -> crate::Result<()> {
+        let accept = parse("de-CH;q=0.5, de, en;q=0")?;
+        let available = tags(&["en-GB", "de-CH", "de-DE", "fr"]);
+        assert_eq!(accept.filter(&available), tags(&["de-DE", "de-CH"]));
+
+        let accept = parse("*, fr;q=0")?;
+        assert_eq!(
+            accept.filter(&available),
+            tags(&["en-GB", "de-CH", "de-DE"])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lookup() -> crate::Result<()> {
+        let accept = parse("zh-Hant-CN-x-private1, fr;q=0.5")?;
+        assert_eq!(
+            accept.lookup(&tags(&["fr", "zh-Hant"])),
+            Some("zh-Hant".parse()?)
+        );
+        assert_eq!(
+            accept.lookup(&tags(&["fr", "zh-Hans"])),
+            Some("fr".parse()?)
+        );
+        assert_eq!(accept.lookup(&tags(&["de"])), None);
+        Ok(())
+    }
+
+    #[test]
+    fn negotiate() -> crate::Result<()> {
+        // Filtering doesn't match `en`, so lookup of `en-US` is used before
+        // trying `de`.
+        let accept = parse("en-US, de;q=0.5")?;
+        let available = tags(&["de", "en"]);
+        assert_eq!(accept.negotiate(&available)?, "en".parse::<LanguageTag>()?);
+        assert_eq!(
+            accept.negotiate(&tags(&["de", "en-US-x-y"]))?,
+            "en-US-x-y".parse::<LanguageTag>()?
+        );
+        assert_eq!(
+            accept.negotiate(&tags(&["en"]))?,
+            "en".parse::<LanguageTag>()?
+        );
+
+        let err = accept.negotiate(&tags(&["fr"])).unwrap_err();
+        assert_eq!(err.status(), 406);
+
+        let accept = parse("en-US, *;q=0.5, en;q=0")?;
+        assert_eq!(
+            accept.negotiate(&tags(&["en", "fr"]))?,
+            "fr".parse::<LanguageTag>()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        assert_eq!(parse("en_US").unwrap_err().status(), 400);
+        assert_eq!(parse("en;q=1.1").unwrap_err().status(), 400);
+    }
+}
@@ -0,0 +1,307 @@
+use crate::content::DispositionType;
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, CONTENT_DISPOSITION};
+use crate::utils::{
+    decode_ext_value, encode_ext_value, is_printable, is_token, quote, split_quoted, unquote,
+};
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::option;
+use std::str::FromStr;
+
+/// Indicate whether content should be displayed inline or downloaded, and
+/// which file name to use.
+///
+/// File names which aren't printable ASCII, such as ones containing non-ASCII
+/// or control characters, are sent in the `filename*` parameter, along with
+/// an ASCII fallback in the `filename` parameter. When parsing,
+/// `filename*` takes precedence over `filename`.
+///
+/// # Specifications
+///
+/// - [RFC 6266: Use of the Content-Disposition Header Field in HTTP](https://tools.ietf.org/html/rfc6266)
+/// - [RFC 8187: Indicating Character Encoding and Language for HTTP Header Field Parameters](https://tools.ietf.org/html/rfc8187)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::content::{ContentDisposition, DispositionType};
+///
+/// let mut disposition = ContentDisposition::new(DispositionType::Attachment);
+/// disposition.set_filename(Some("€ rates.csv".into()));
+///
+/// let mut res = Response::new(200);
+/// disposition.apply(&mut res);
+/// assert_eq!(
+///     res["Content-Disposition"],
+///     r#"attachment; filename="_ rates.csv"; filename*=UTF-8''%E2%82%AC%20rates.csv"#
+/// );
+///
+/// let disposition = ContentDisposition::from_headers(res)?.unwrap();
+/// assert_eq!(disposition.filename(), Some("€ rates.csv"));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ContentDisposition {
+    disposition: DispositionType,
+    field_name: Option<String>,
+    filename: Option<String>,
+}
+
+impl ContentDisposition {
+    /// Create a new instance of `ContentDisposition`.
+    pub fn new(disposition: DispositionType) -> Self {
+        Self {
+            disposition,
+            field_name: None,
+            filename: None,
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(CONTENT_DISPOSITION) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If we successfully parsed the header then there's always at least one
+        // entry. We want the last entry.
+        let value = headers.iter().last().unwrap();
+        value.as_str().parse().map(Some)
+    }
+
+    /// Sets the `Content-Disposition` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(CONTENT_DISPOSITION, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CONTENT_DISPOSITION
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Get the disposition type.
+    pub fn disposition(&self) -> DispositionType {
+        self.disposition
+    }
+
+    /// Set the disposition type.
+    pub fn set_disposition(&mut self, disposition: DispositionType) {
+        self.disposition = disposition;
+    }
+
+    /// Get the name of the form field, for `form-data` dispositions.
+    pub fn field_name(&self) -> Option<&str> {
+        self.field_name.as_deref()
+    }
+
+    /// Set the name of the form field, for `form-data` dispositions.
+    pub fn set_field_name(&mut self, field_name: Option<String>) {
+        self.field_name = field_name;
+    }
+
+    /// Get the file name.
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// Set the file name.
+    pub fn set_filename(&mut self, filename: Option<String>) {
+        self.filename = filename;
+    }
+}
+
+impl FromStr for ContentDisposition {
+    type Err = Error;
+
+    /// Parse a `Content-Disposition` header value.
+    ///
+    /// Unknown disposition types are treated as `attachment`, and unknown
+    /// parameters are skipped. Raw UTF-8 in quoted strings is accepted, as
+    /// browsers send file names in `multipart/form-data` bodies that way.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            let msg = format!("Invalid content disposition: {}", s);
+            Error::from_str(StatusCode::BadRequest, msg)
+        };
+
+        let (disposition, params) = match s.find(';') {
+            Some(index) => (s[..index].trim(), &s[index + 1..]),
+            None => (s.trim(), ""),
+        };
+        if !is_token(disposition) {
+            return Err(invalid());
+        }
+        let disposition = disposition.parse().unwrap_or(DispositionType::Attachment);
+
+        let mut output = Self::new(disposition);
+        let (mut ext_field_name, mut ext_filename) = (None, None);
+        for param in split_quoted(params, ';') {
+            let index = param.find('=').ok_or_else(invalid)?;
+            let (name, value) = (param[..index].trim(), param[index + 1..].trim());
+
+            if name.eq_ignore_ascii_case("name*") {
+                ext_field_name = Some(decode_ext_value(value).ok_or_else(invalid)?);
+                continue;
+            } else if name.eq_ignore_ascii_case("filename*") {
+                ext_filename = Some(decode_ext_value(value).ok_or_else(invalid)?);
+                continue;
+            }
+            let value = match value.starts_with('"') {
+                true => unquote(value).ok_or_else(invalid)?,
+                false => value.to_owned(),
+            };
+            if name.eq_ignore_ascii_case("name") {
+                output.field_name = Some(value);
+            } else if name.eq_ignore_ascii_case("filename") {
+                output.filename = Some(value);
+            }
+        }
+        if ext_field_name.is_some() {
+            output.field_name = ext_field_name;
+        }
+        if ext_filename.is_some() {
+            output.filename = ext_filename;
+        }
+        Ok(output)
+    }
+}
+
+impl Display for ContentDisposition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.disposition.as_str())?;
+        if let Some(field_name) = &self.field_name {
+            match is_printable(field_name) {
+                true => write!(f, "; name={}", quote(field_name))?,
+                false => write!(f, "; name*={}", encode_ext_value(field_name))?,
+            }
+        }
+        if let Some(filename) = &self.filename {
+            if is_printable(filename) {
+                write!(f, "; filename={}", quote(filename))?;
+            } else {
+                // Older clients ignore `filename*`, so include an ASCII fallback.
+                let fallback: String = filename
+                    .chars()
+                    .map(|c| match c.is_ascii_graphic() || c == ' ' {
+                        true => c,
+                        false => '_',
+                    })
+                    .collect();
+                write!(f, "; filename={}", quote(&fallback))?;
+                write!(f, "; filename*={}", encode_ext_value(filename))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ToHeaderValues for ContentDisposition {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut disposition = ContentDisposition::new(DispositionType::FormData);
+        disposition.set_field_name(Some("avatar".into()));
+        disposition.set_filename(Some(r#"nori "the cat".png"#.into()));
+
+        let mut headers = Headers::new();
+        disposition.apply(&mut headers);
+        assert_eq!(
+            headers[CONTENT_DISPOSITION],
+            r#"form-data; name="avatar"; filename="nori \"the cat\".png""#
+        );
+
+        let parsed = ContentDisposition::from_headers(headers)?.unwrap();
+        assert_eq!(parsed, disposition);
+        Ok(())
+    }
+
+    #[test]
+    fn control_characters_are_encoded() -> crate::Result<()> {
+        let mut disposition = ContentDisposition::new(DispositionType::FormData);
+        disposition.set_field_name(Some("a\nb".into()));
+        disposition.set_filename(Some("a.txt\r\nSet-Cookie: x=1".into()));
+
+        let mut headers = Headers::new();
+        disposition.apply(&mut headers);
+        assert_eq!(
+            headers[CONTENT_DISPOSITION],
+            concat!(
+                r#"form-data; name*=UTF-8''a%0Ab; filename="a.txt__Set-Cookie: x=1"; "#,
+                "filename*=UTF-8''a.txt%0D%0ASet-Cookie%3A%20x%3D1"
+            )
+        );
+
+        let parsed = ContentDisposition::from_headers(headers)?.unwrap();
+        assert_eq!(parsed, disposition);
+        Ok(())
+    }
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let disposition: ContentDisposition = "INLINE".parse()?;
+        assert_eq!(disposition.disposition(), DispositionType::Inline);
+        assert_eq!(disposition.filename(), None);
+
+        let disposition: ContentDisposition = concat!(
+            r#"attachment; filename*=UTF-8''%e2%82%ac%20rates.csv; "#,
+            r#"filename="EURO rates.csv""#
+        )
+        .parse()?;
+        assert_eq!(disposition.filename(), Some("€ rates.csv"));
+
+        let disposition: ContentDisposition = "x-custom; filename=plain.txt; size=10".parse()?;
+        assert_eq!(disposition.disposition(), DispositionType::Attachment);
+        assert_eq!(disposition.filename(), Some("plain.txt"));
+
+        let disposition: ContentDisposition =
+            r#"form-data; name="file"; filename="ノリ.txt""#.parse()?;
+        assert_eq!(disposition.field_name(), Some("file"));
+        assert_eq!(disposition.filename(), Some("ノリ.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = [
+            "",
+            "attachment filename=a.txt",
+            "attachment; filename",
+            r#"attachment; filename="a.txt"#,
+            "attachment; filename*=a.txt",
+        ];
+        for value in &values {
+            let err = value.parse::<ContentDisposition>().unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
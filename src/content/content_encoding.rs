@@ -0,0 +1,160 @@
+use crate::content::Encoding;
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, CONTENT_ENCODING};
+use crate::{Error, StatusCode};
+
+use std::option;
+
+/// Specify the compression algorithm applied to the body.
+///
+/// # Specifications
+///
+/// - [RFC 7231, section 3.1.2.2: Content-Encoding](https://tools.ietf.org/html/rfc7231#section-3.1.2.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::content::{ContentEncoding, Encoding};
+///
+/// let mut res = Response::new(200);
+/// ContentEncoding::new(Encoding::Gzip).apply(&mut res);
+///
+/// let encoding = ContentEncoding::from_headers(res)?.unwrap();
+/// assert_eq!(encoding.encoding(), Encoding::Gzip);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ContentEncoding {
+    inner: Encoding,
+}
+
+impl ContentEncoding {
+    /// Create a new instance of `ContentEncoding`.
+    pub fn new(encoding: Encoding) -> Self {
+        Self { inner: encoding }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `415: Unsupported Media Type` is
+    /// returned if the encoding is unknown, or if more than one encoding was
+    /// applied.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(CONTENT_ENCODING) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut inner = None;
+        for value in headers {
+            for part in value.as_str().split(',') {
+                if part.trim().is_empty() {
+                    continue;
+                }
+                let encoding: Encoding = part.parse().map_err(|mut err: Error| {
+                    err.set_status(StatusCode::UnsupportedMediaType);
+                    err
+                })?;
+                if encoding == Encoding::Identity {
+                    continue;
+                }
+                if inner.is_some() {
+                    let msg = "Multiple content encodings are not supported";
+                    return Err(Error::from_str(StatusCode::UnsupportedMediaType, msg));
+                }
+                inner = Some(encoding);
+            }
+        }
+
+        let inner = inner.unwrap_or(Encoding::Identity);
+        Ok(Some(Self { inner }))
+    }
+
+    /// Sets the `Content-Encoding` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(CONTENT_ENCODING, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CONTENT_ENCODING
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        self.inner.into()
+    }
+
+    /// Access the encoding kind.
+    pub fn encoding(&self) -> Encoding {
+        self.inner
+    }
+}
+
+impl ToHeaderValues for ContentEncoding {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+impl From<Encoding> for ContentEncoding {
+    fn from(encoding: Encoding) -> Self {
+        Self { inner: encoding }
+    }
+}
+
+impl PartialEq<Encoding> for ContentEncoding {
+    fn eq(&self, other: &Encoding) -> bool {
+        &self.inner == other
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        ContentEncoding::new(Encoding::Brotli).apply(&mut headers);
+        assert_eq!(headers[CONTENT_ENCODING], "br");
+
+        let encoding = ContentEncoding::from_headers(headers)?.unwrap();
+        assert_eq!(encoding, Encoding::Brotli);
+        Ok(())
+    }
+
+    #[test]
+    fn skips_identity() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(CONTENT_ENCODING, "identity, gzip");
+        let encoding = ContentEncoding::from_headers(headers)?.unwrap();
+        assert_eq!(encoding, Encoding::Gzip);
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_media_type_on_unknown_encoding() {
+        let mut headers = Headers::new();
+        headers.insert(CONTENT_ENCODING, "compress");
+        let err = ContentEncoding::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 415);
+    }
+
+    #[test]
+    fn unsupported_media_type_on_multiple_encodings() {
+        let mut headers = Headers::new();
+        headers.append(CONTENT_ENCODING, "gzip");
+        headers.append(CONTENT_ENCODING, "br");
+        let err = ContentEncoding::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 415);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::content::LanguageTag;
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, CONTENT_LANGUAGE};
+use crate::utils::split_quoted;
+
+use std::fmt::Write;
+use std::option;
+use std::slice;
+
+/// The natural languages of the intended audience.
+///
+/// # Specifications
+///
+/// - [RFC 7231, section 3.1.3.2: Content-Language](https://tools.ietf.org/html/rfc7231#section-3.1.3.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::ContentLanguage;
+/// use http_types::Response;
+///
+/// let mut languages = ContentLanguage::new();
+/// languages.push("en-GB".parse()?);
+///
+/// let mut res = Response::new(200);
+/// languages.apply(&mut res);
+///
+/// let languages = ContentLanguage::from_headers(res)?.unwrap();
+/// assert_eq!(languages.iter().next().unwrap(), &"en-GB");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContentLanguage {
+    languages: Vec<LanguageTag>,
+}
+
+impl ContentLanguage {
+    /// Create a new instance of `ContentLanguage`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if a
+    /// language tag is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(CONTENT_LANGUAGE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut languages = vec![];
+        for value in headers {
+            for part in split_quoted(value.as_str(), ',') {
+                languages.push(part.parse()?);
+            }
+        }
+        Ok(Some(Self { languages }))
+    }
+
+    /// Sets the `Content-Language` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(CONTENT_LANGUAGE, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CONTENT_LANGUAGE
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::new();
+        for (n, language) in self.languages.iter().enumerate() {
+            match n {
+                0 => write!(output, "{}", language).unwrap(),
+                _ => write!(output, ", {}", language).unwrap(),
+            };
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Push a language tag.
+    pub fn push(&mut self, language: LanguageTag) {
+        self.languages.push(language);
+    }
+
+    /// An iterator visiting all language tags.
+    pub fn iter(&self) -> slice::Iter<'_, LanguageTag> {
+        self.languages.iter()
+    }
+}
+
+impl From<LanguageTag> for ContentLanguage {
+    fn from(language: LanguageTag) -> Self {
+        Self {
+            languages: vec![language],
+        }
+    }
+}
+
+impl IntoIterator for ContentLanguage {
+    type Item = LanguageTag;
+    type IntoIter = std::vec::IntoIter<LanguageTag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.languages.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ContentLanguage {
+    type Item = &'a LanguageTag;
+    type IntoIter = slice::Iter<'a, LanguageTag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl ToHeaderValues for ContentLanguage {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Response;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut languages = ContentLanguage::new();
+        languages.push("de-DE".parse()?);
+        languages.push("en-CA".parse()?);
+
+        let mut res = Response::new(200);
+        languages.apply(&mut res);
+        assert_eq!(res[CONTENT_LANGUAGE], "de-DE, en-CA");
+
+        let parsed = ContentLanguage::from_headers(res)?.unwrap();
+        assert_eq!(parsed, languages);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert(CONTENT_LANGUAGE, "en, *");
+        let err = ContentLanguage::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// How the content of a message should be presented.
+///
+/// # Specifications
+///
+/// - [RFC 6266, section 4.2: Disposition Type](https://tools.ietf.org/html/rfc6266#section-4.2)
+/// - [RFC 7578, section 4.2: Content-Disposition Header Field for Each Part](https://tools.ietf.org/html/rfc7578#section-4.2)
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DispositionType {
+    /// Display the content as part of a web page.
+    Inline,
+    /// Download the content, usually after prompting the user.
+    Attachment,
+    /// A field in a `multipart/form-data` body.
+    FormData,
+}
+
+impl DispositionType {
+    /// Get the name of the disposition type as it's used in headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DispositionType::Inline => "inline",
+            DispositionType::Attachment => "attachment",
+            DispositionType::FormData => "form-data",
+        }
+    }
+}
+
+impl FromStr for DispositionType {
+    type Err = Error;
+
+    /// Parse a disposition type. Names are matched case-insensitively.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("inline") {
+            Ok(DispositionType::Inline)
+        } else if s.eq_ignore_ascii_case("attachment") {
+            Ok(DispositionType::Attachment)
+        } else if s.eq_ignore_ascii_case("form-data") {
+            Ok(DispositionType::FormData)
+        } else {
+            let msg = format!("Unknown disposition type: {}", s);
+            Err(Error::from_str(StatusCode::BadRequest, msg))
+        }
+    }
+}
+
+impl Display for DispositionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,102 @@
+use crate::headers::HeaderValue;
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Available compression algorithms.
+///
+/// # Specifications
+///
+/// - [IANA HTTP Content Coding Registry](https://www.iana.org/assignments/http-parameters/http-parameters.xml#content-coding)
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Encoding {
+    /// The Gzip encoding.
+    Gzip,
+    /// The Deflate encoding, which is the zlib format in HTTP.
+    Deflate,
+    /// The Brotli encoding.
+    Brotli,
+    /// No encoding.
+    Identity,
+}
+
+impl Encoding {
+    /// Get the name of the encoding as it's used in headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+            Encoding::Brotli => "br",
+            Encoding::Identity => "identity",
+        }
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = Error;
+
+    /// Parse an encoding name.
+    ///
+    /// Names are matched case-insensitively, and the legacy `x-gzip` alias is
+    /// accepted as well.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let encoding = if s.eq_ignore_ascii_case("gzip") || s.eq_ignore_ascii_case("x-gzip") {
+            Encoding::Gzip
+        } else if s.eq_ignore_ascii_case("deflate") {
+            Encoding::Deflate
+        } else if s.eq_ignore_ascii_case("br") {
+            Encoding::Brotli
+        } else if s.eq_ignore_ascii_case("identity") {
+            Encoding::Identity
+        } else {
+            let msg = format!("Unsupported content encoding: {}", s);
+            return Err(Error::from_str(StatusCode::BadRequest, msg));
+        };
+        Ok(encoding)
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<Encoding> for HeaderValue {
+    fn from(encoding: Encoding) -> Self {
+        // SAFETY: all encoding names are valid ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(encoding.as_str().as_bytes().to_vec()) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        assert_eq!("gzip".parse::<Encoding>()?, Encoding::Gzip);
+        assert_eq!("X-GZIP".parse::<Encoding>()?, Encoding::Gzip);
+        assert_eq!(" br ".parse::<Encoding>()?, Encoding::Brotli);
+        assert_eq!("Deflate".parse::<Encoding>()?, Encoding::Deflate);
+        assert_eq!("identity".parse::<Encoding>()?, Encoding::Identity);
+        assert!("compress".parse::<Encoding>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> crate::Result<()> {
+        for encoding in &[
+            Encoding::Gzip,
+            Encoding::Deflate,
+            Encoding::Brotli,
+            Encoding::Identity,
+        ] {
+            assert_eq!(encoding.to_string().parse::<Encoding>()?, *encoding);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use crate::content::Encoding;
+use crate::headers::HeaderValue;
+use crate::utils::parse_weight;
+use crate::{ensure, Error};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// A proposed `Encoding` in `AcceptEncoding`.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::{Encoding, EncodingProposal};
+///
+/// let proposal: EncodingProposal = "gzip;q=0.8".parse()?;
+/// assert_eq!(proposal.encoding(), Encoding::Gzip);
+/// assert_eq!(proposal.weight(), 0.8);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EncodingProposal {
+    encoding: Encoding,
+    weight: Option<f32>,
+}
+
+impl EncodingProposal {
+    /// Create a new instance of `EncodingProposal`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the weight is not between 0 and 1.
+    pub fn new(encoding: Encoding, weight: Option<f32>) -> crate::Result<Self> {
+        if let Some(weight) = weight {
+            ensure!(
+                (0.0..=1.0).contains(&weight),
+                "EncodingProposal should have a weight between 0 and 1"
+            );
+        }
+        Ok(Self { encoding, weight })
+    }
+
+    /// Get the proposed encoding.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Get the weight of the proposal, defaulting to 1.
+    pub fn weight(&self) -> f32 {
+        self.weight.unwrap_or(1.0)
+    }
+}
+
+impl FromStr for EncodingProposal {
+    type Err = Error;
+
+    /// Parse a proposal such as `gzip;q=0.8`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(';');
+        let encoding = parts.next().unwrap_or("").parse()?;
+
+        let mut weight = None;
+        for param in parts {
+            let mut param = param.splitn(2, '=');
+            let name = param.next().unwrap_or("").trim();
+            if name.eq_ignore_ascii_case("q") {
+                weight = Some(parse_weight(param.next().unwrap_or(""))?);
+            }
+        }
+        Ok(Self { encoding, weight })
+    }
+}
+
+impl Display for EncodingProposal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.weight {
+            Some(weight) => write!(f, "{};q={}", self.encoding, weight),
+            None => write!(f, "{}", self.encoding),
+        }
+    }
+}
+
+impl From<Encoding> for EncodingProposal {
+    fn from(encoding: Encoding) -> Self {
+        Self {
+            encoding,
+            weight: None,
+        }
+    }
+}
+
+impl From<EncodingProposal> for HeaderValue {
+    fn from(proposal: EncodingProposal) -> Self {
+        // SAFETY: encodings and weights are always valid ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(proposal.to_string().into_bytes()) }
+    }
+}
+
+impl PartialEq<Encoding> for EncodingProposal {
+    fn eq(&self, other: &Encoding) -> bool {
+        &self.encoding == other
+    }
+}
@@ -0,0 +1,103 @@
+use crate::content::LanguageRange;
+use crate::headers::HeaderValue;
+use crate::utils::parse_weight;
+use crate::{ensure, Error};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// A proposed `LanguageRange` in `AcceptLanguage`.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::LanguageProposal;
+///
+/// let proposal: LanguageProposal = "en-GB;q=0.8".parse()?;
+/// assert_eq!(proposal.language(), &"en-GB");
+/// assert_eq!(proposal.weight(), 0.8);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageProposal {
+    language: LanguageRange,
+    weight: Option<f32>,
+}
+
+impl LanguageProposal {
+    /// Create a new instance of `LanguageProposal`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the weight is not between 0 and 1.
+    pub fn new(language: impl Into<LanguageRange>, weight: Option<f32>) -> crate::Result<Self> {
+        if let Some(weight) = weight {
+            ensure!(
+                (0.0..=1.0).contains(&weight),
+                "LanguageProposal should have a weight between 0 and 1"
+            );
+        }
+        Ok(Self {
+            language: language.into(),
+            weight,
+        })
+    }
+
+    /// Get the proposed language range.
+    pub fn language(&self) -> &LanguageRange {
+        &self.language
+    }
+
+    /// Get the weight of the proposal, defaulting to 1.
+    pub fn weight(&self) -> f32 {
+        self.weight.unwrap_or(1.0)
+    }
+}
+
+impl FromStr for LanguageProposal {
+    type Err = Error;
+
+    /// Parse a proposal such as `en-GB;q=0.8`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(';');
+        let language = parts.next().unwrap_or("").parse()?;
+
+        let mut weight = None;
+        for param in parts {
+            let mut param = param.splitn(2, '=');
+            let name = param.next().unwrap_or("").trim();
+            if name.eq_ignore_ascii_case("q") {
+                weight = Some(parse_weight(param.next().unwrap_or(""))?);
+            }
+        }
+        Ok(Self { language, weight })
+    }
+}
+
+impl Display for LanguageProposal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.weight {
+            Some(weight) => write!(f, "{};q={}", self.language, weight),
+            None => write!(f, "{}", self.language),
+        }
+    }
+}
+
+impl From<LanguageRange> for LanguageProposal {
+    fn from(language: LanguageRange) -> Self {
+        Self {
+            language,
+            weight: None,
+        }
+    }
+}
+
+impl From<LanguageProposal> for HeaderValue {
+    fn from(proposal: LanguageProposal) -> Self {
+        // SAFETY: language ranges and weights are always valid ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(proposal.to_string().into_bytes()) }
+    }
+}
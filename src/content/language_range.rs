@@ -0,0 +1,135 @@
+use crate::content::language_tag::is_well_formed;
+use crate::content::LanguageTag;
+use crate::headers::HeaderValue;
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// A basic language range, such as `en`, `de-CH` or `*`.
+///
+/// # Specifications
+///
+/// - [RFC 4647, section 2.1: Basic Language Range](https://tools.ietf.org/html/rfc4647#section-2.1)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::{LanguageRange, LanguageTag};
+///
+/// let range: LanguageRange = "de".parse()?;
+/// assert!(range.matches(&"de-CH".parse::<LanguageTag>()?));
+/// assert!(!range.matches(&"deu".parse::<LanguageTag>()?));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq)]
+pub struct LanguageRange {
+    inner: String,
+}
+
+impl LanguageRange {
+    /// Get the range as a `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
+    /// Returns `true` if this is the `*` range.
+    pub fn is_wildcard(&self) -> bool {
+        self.inner == "*"
+    }
+
+    /// Returns `true` if the language tag falls within this range.
+    ///
+    /// A range matches a tag if it equals the tag, or if it equals a prefix of
+    /// the tag such that the first character following the prefix is `-`. The
+    /// `*` range matches every tag. This is the RFC 4647 basic filtering
+    /// scheme.
+    pub fn matches(&self, tag: &LanguageTag) -> bool {
+        if self.is_wildcard() {
+            return true;
+        }
+        let tag = tag.as_str();
+        let len = self.inner.len();
+        tag.len() >= len
+            && tag[..len].eq_ignore_ascii_case(&self.inner)
+            && (tag.len() == len || tag.as_bytes()[len] == b'-')
+    }
+
+    /// Get the number of subtags in the range. The `*` range has none.
+    pub(crate) fn specificity(&self) -> usize {
+        match self.is_wildcard() {
+            true => 0,
+            false => self.inner.split('-').count(),
+        }
+    }
+}
+
+impl FromStr for LanguageRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s != "*" && !is_well_formed(s) {
+            let msg = format!("{} is not a valid language range", s);
+            return Err(Error::from_str(StatusCode::BadRequest, msg));
+        }
+        Ok(Self {
+            inner: s.to_owned(),
+        })
+    }
+}
+
+impl PartialEq for LanguageRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.eq_ignore_ascii_case(&other.inner)
+    }
+}
+
+impl<'a> PartialEq<&'a str> for LanguageRange {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.inner.eq_ignore_ascii_case(other)
+    }
+}
+
+impl Display for LanguageRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.inner)
+    }
+}
+
+impl From<LanguageTag> for LanguageRange {
+    fn from(tag: LanguageTag) -> Self {
+        Self {
+            inner: tag.as_str().to_owned(),
+        }
+    }
+}
+
+impl From<LanguageRange> for HeaderValue {
+    fn from(range: LanguageRange) -> Self {
+        // SAFETY: language ranges are validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(range.inner.into_bytes()) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches() -> crate::Result<()> {
+        let range = |s: &str| s.parse::<LanguageRange>().unwrap();
+        let tag: LanguageTag = "de-de-1996".parse()?;
+        assert!(range("*").matches(&tag));
+        assert!(range("de").matches(&tag));
+        assert!(range("DE-DE").matches(&tag));
+        assert!(range("de-de-1996").matches(&tag));
+        assert!(!range("de-d").matches(&tag));
+        assert!(!range("de-de-1996-x").matches(&tag));
+        assert!(!range("en").matches(&tag));
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use crate::headers::HeaderValue;
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// A BCP 47 language tag, such as `en-US`.
+///
+/// Only the syntax shared by all language tags is checked: one to eight
+/// letters, optionally followed by subtags of one to eight letters or digits,
+/// separated by hyphens. Tags compare case-insensitively.
+///
+/// # Specifications
+///
+/// - [RFC 5646: Tags for Identifying Languages](https://tools.ietf.org/html/rfc5646)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::LanguageTag;
+///
+/// let tag: LanguageTag = "en-US".parse()?;
+/// assert_eq!(tag, "en-us".parse::<LanguageTag>()?);
+/// assert_eq!(tag.subtags().collect::<Vec<_>>(), vec!["en", "US"]);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq)]
+pub struct LanguageTag {
+    inner: String,
+}
+
+impl LanguageTag {
+    /// Get the tag as a `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
+    /// An iterator visiting the subtags, starting with the primary language.
+    pub fn subtags(&self) -> impl Iterator<Item = &str> {
+        self.inner.split('-')
+    }
+}
+
+impl FromStr for LanguageTag {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if !is_well_formed(s) {
+            let msg = format!("{} is not a valid language tag", s);
+            return Err(Error::from_str(StatusCode::BadRequest, msg));
+        }
+        Ok(Self {
+            inner: s.to_owned(),
+        })
+    }
+}
+
+impl PartialEq for LanguageTag {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.eq_ignore_ascii_case(&other.inner)
+    }
+}
+
+impl std::hash::Hash for LanguageTag {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.inner.to_ascii_lowercase().hash(state);
+    }
+}
+
+impl<'a> PartialEq<&'a str> for LanguageTag {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.inner.eq_ignore_ascii_case(other)
+    }
+}
+
+impl Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.inner)
+    }
+}
+
+impl From<LanguageTag> for HeaderValue {
+    fn from(tag: LanguageTag) -> Self {
+        // SAFETY: language tags are validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(tag.inner.into_bytes()) }
+    }
+}
+
+/// Check a tag against the RFC 4647 language-range syntax, without the
+/// wildcard.
+pub(super) fn is_well_formed(s: &str) -> bool {
+    let mut subtags = s.split('-');
+    let primary = subtags.next().unwrap_or("");
+    let valid = |subtag: &str, f: fn(&u8) -> bool| {
+        (1..=8).contains(&subtag.len()) && subtag.as_bytes().iter().all(f)
+    };
+    valid(primary, u8::is_ascii_alphabetic)
+        && subtags.all(|subtag| valid(subtag, u8::is_ascii_alphanumeric))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert!("en".parse::<LanguageTag>().is_ok());
+        assert!("zh-Hant-TW".parse::<LanguageTag>().is_ok());
+        assert!("es-419".parse::<LanguageTag>().is_ok());
+        assert!("x-klingon".parse::<LanguageTag>().is_ok());
+        assert_eq!("".parse::<LanguageTag>().unwrap_err().status(), 400);
+        assert_eq!("*".parse::<LanguageTag>().unwrap_err().status(), 400);
+        assert_eq!("en_US".parse::<LanguageTag>().unwrap_err().status(), 400);
+        assert_eq!("419".parse::<LanguageTag>().unwrap_err().status(), 400);
+        assert_eq!("en-".parse::<LanguageTag>().unwrap_err().status(), 400);
+        assert_eq!(
+            "en-abcdefghi".parse::<LanguageTag>().unwrap_err().status(),
+            400
+        );
+    }
+}
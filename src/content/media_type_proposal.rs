@@ -0,0 +1,184 @@
+use crate::headers::HeaderValue;
+use crate::utils::{parse_weight, split_quoted};
+use crate::{ensure, Error, Mime, StatusCode};
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// A proposed media range in `Accept`.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::content::MediaTypeProposal;
+///
+/// let proposal: MediaTypeProposal = "text/html;level=1;q=0.5".parse()?;
+/// assert_eq!(proposal.media_type().essence(), "text/html");
+/// assert_eq!(proposal.media_type().param("level").unwrap(), "1");
+/// assert_eq!(proposal.weight(), 0.5);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaTypeProposal {
+    media_type: Mime,
+    weight: Option<f32>,
+}
+
+impl MediaTypeProposal {
+    /// Create a new instance of `MediaTypeProposal`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the weight is not between 0 and 1.
+    pub fn new(media_type: impl Into<Mime>, weight: Option<f32>) -> crate::Result<Self> {
+        if let Some(weight) = weight {
+            ensure!(
+                (0.0..=1.0).contains(&weight),
+                "MediaTypeProposal should have a weight between 0 and 1"
+            );
+        }
+        Ok(Self {
+            media_type: media_type.into(),
+            weight,
+        })
+    }
+
+    /// Get the proposed media range.
+    pub fn media_type(&self) -> &Mime {
+        &self.media_type
+    }
+
+    /// Get the weight of the proposal, defaulting to 1.
+    pub fn weight(&self) -> f32 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    /// Get the specificity of the media range.
+    ///
+    /// `text/html;level=1` is more specific than `text/html`, which is more
+    /// specific than `text/*`, which is more specific than `*/*`.
+    pub fn specificity(&self) -> u8 {
+        match (self.media_type.basetype(), self.media_type.subtype()) {
+            ("*", _) => 0,
+            (_, "*") => 1,
+            _ if self.media_type.params().is_empty() => 2,
+            _ => 3,
+        }
+    }
+
+    /// Returns `true` if the media range includes the given media type.
+    ///
+    /// Every parameter of the media range must be present on the media type
+    /// with the same value.
+    pub fn matches(&self, mime: &Mime) -> bool {
+        let range = &self.media_type;
+        match (range.basetype(), range.subtype()) {
+            ("*", _) => true,
+            (basetype, "*") => basetype == mime.basetype(),
+            _ => range.essence() == mime.essence() && range.params().iter().all(|(name, value)| {
+                let other = mime.param(name.as_str());
+                matches!(other, Some(other) if other.as_str().eq_ignore_ascii_case(value.as_str()))
+            }),
+        }
+    }
+}
+
+impl FromStr for MediaTypeProposal {
+    type Err = Error;
+
+    /// Parse a media range such as `text/html;level=1;q=0.5`.
+    ///
+    /// Any parameters following the weight are accept extensions, and are
+    /// ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = split_quoted(s, ';').into_iter();
+        let mut media_type = parts.next().unwrap_or("").to_owned();
+
+        let mut weight = None;
+        for param in parts {
+            let mut pair = param.splitn(2, '=');
+            let name = pair.next().unwrap_or("").trim();
+            if name.eq_ignore_ascii_case("q") {
+                weight = Some(parse_weight(pair.next().unwrap_or(""))?);
+                break;
+            }
+            media_type.push(';');
+            media_type.push_str(param);
+        }
+
+        let media_type = media_type.parse().map_err(|_| {
+            let msg = format!("{} is not a valid media range", s);
+            Error::from_str(StatusCode::BadRequest, msg)
+        })?;
+        Ok(Self { media_type, weight })
+    }
+}
+
+impl Display for MediaTypeProposal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.weight {
+            Some(weight) => write!(f, "{};q={}", self.media_type, weight),
+            None => write!(f, "{}", self.media_type),
+        }
+    }
+}
+
+impl From<Mime> for MediaTypeProposal {
+    fn from(media_type: Mime) -> Self {
+        Self {
+            media_type,
+            weight: None,
+        }
+    }
+}
+
+impl From<MediaTypeProposal> for HeaderValue {
+    fn from(proposal: MediaTypeProposal) -> Self {
+        // SAFETY: media types and weights are always valid ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(proposal.to_string().into_bytes()) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn specificity() -> crate::Result<()> {
+        let specificity = |s: &str| s.parse::<MediaTypeProposal>().unwrap().specificity();
+        assert!(specificity("text/html;level=1") > specificity("text/html"));
+        assert!(specificity("text/html") > specificity("text/*"));
+        assert!(specificity("text/*") > specificity("*/*"));
+        Ok(())
+    }
+
+    #[test]
+    fn matches() -> crate::Result<()> {
+        let html: Mime = "text/html;level=1".parse()?;
+        let proposal = |s: &str| s.parse::<MediaTypeProposal>().unwrap();
+        assert!(proposal("*/*").matches(&html));
+        assert!(proposal("text/*").matches(&html));
+        assert!(proposal("text/html").matches(&html));
+        assert!(proposal("text/html;level=1").matches(&html));
+        assert!(!proposal("text/html;level=2").matches(&html));
+        assert!(!proposal("image/*").matches(&html));
+        Ok(())
+    }
+
+    #[test]
+    fn ignores_accept_extensions() -> crate::Result<()> {
+        let proposal: MediaTypeProposal = "text/plain; q=0.3; ext=1".parse()?;
+        assert_eq!(proposal.weight(), 0.3);
+        assert!(proposal.media_type().params().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_invalid_media_range() {
+        let err = "text".parse::<MediaTypeProposal>().unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
@@ -0,0 +1,37 @@
+//! HTTP content headers.
+//!
+//! These headers describe the content of a message, and are used for
+//! [proactive content negotiation](https://developer.mozilla.org/en-US/docs/Web/HTTP/Content_negotiation).
+//!
+//! # Specifications
+//!
+//! - [RFC 7231, section 3.1: Representation Metadata](https://tools.ietf.org/html/rfc7231#section-3.1)
+//! - [RFC 7231, section 5.3: Content Negotiation](https://tools.ietf.org/html/rfc7231#section-5.3)
+
+mod accept;
+mod accept_encoding;
+mod accept_language;
+mod content_disposition;
+mod content_encoding;
+mod content_language;
+mod disposition_type;
+mod encoding;
+mod encoding_proposal;
+mod language_proposal;
+mod language_range;
+mod language_tag;
+mod media_type_proposal;
+
+pub use accept::Accept;
+pub use accept_encoding::AcceptEncoding;
+pub use accept_language::AcceptLanguage;
+pub use content_disposition::ContentDisposition;
+pub use content_encoding::ContentEncoding;
+pub use content_language::ContentLanguage;
+pub use disposition_type::DispositionType;
+pub use encoding::Encoding;
+pub use encoding_proposal::EncodingProposal;
+pub use language_proposal::LanguageProposal;
+pub use language_range::LanguageRange;
+pub use language_tag::LanguageTag;
+pub use media_type_proposal::MediaTypeProposal;
@@ -0,0 +1,37 @@
+//! Cross-Origin Resource Sharing (CORS).
+//!
+//! CORS allows a server to indicate which origins other than its own may
+//! read its responses from a browser. Requests which aren't CORS-safelisted
+//! are preceded by a "preflight" `OPTIONS` request to check whether the
+//! actual request is permitted.
+//!
+//! # Specifications
+//!
+//! - [Fetch Standard, section 3.2: CORS protocol](https://fetch.spec.whatwg.org/#http-cors-protocol)
+//!
+//! # Examples
+//!
+//! ```
+//! use http_types::cors::CorsPolicy;
+//! use http_types::{Method, Request, Response, StatusCode, Url};
+//!
+//! let mut policy = CorsPolicy::new();
+//! policy
+//!     .allow_origin("https://example.com")
+//!     .allow_method(Method::Put)
+//!     .allow_header("X-Request-Id");
+//!
+//! let mut req = Request::new(Method::Options, Url::parse("https://api.example.com").unwrap());
+//! req.insert_header("Origin", "https://example.com");
+//! req.insert_header("Access-Control-Request-Method", "PUT");
+//! assert!(policy.is_preflight(&req));
+//!
+//! let res = policy.preflight_response(&req);
+//! assert_eq!(res.status(), StatusCode::NoContent);
+//! assert_eq!(res["Access-Control-Allow-Origin"], "https://example.com");
+//! assert_eq!(res["Access-Control-Allow-Methods"], "GET, HEAD, POST, PUT");
+//! ```
+
+mod policy;
+
+pub use policy::CorsPolicy;
@@ -0,0 +1,472 @@
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::headers::{
+    HeaderName, ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS,
+    ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
+    ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN,
+};
+use crate::utils::append_vary;
+use crate::{Method, Request, Response, StatusCode};
+
+type OriginPredicate = Arc<dyn Fn(&str) -> bool + Send + Sync + 'static>;
+
+/// A policy describing which cross-origin requests are allowed.
+///
+/// A new policy allows no origins, and allows the `GET`, `HEAD` and `POST`
+/// methods.
+///
+/// Credentials are forbidden together with the `*` origin. A policy which
+/// allows any origin always sends `*`, and never allows credentials, even if
+/// [`allow_credentials`](#method.allow_credentials) is set. Echoing the
+/// request's `Origin` instead would let every site make credentialed requests.
+///
+/// # Specifications
+///
+/// - [Fetch Standard, section 3.2: CORS protocol](https://fetch.spec.whatwg.org/#http-cors-protocol)
+///
+/// # Examples
+///
+/// ```
+/// use http_types::cors::CorsPolicy;
+/// use http_types::{Method, Request, Response, StatusCode, Url};
+///
+/// let mut policy = CorsPolicy::new();
+/// policy
+///     .allow_origin_fn(|origin| origin.ends_with(".example.com"))
+///     .expose_header("X-Request-Id")
+///     .allow_credentials(true);
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://api.example.com").unwrap());
+/// req.insert_header("Origin", "https://app.example.com");
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// policy.apply(&req, &mut res);
+///
+/// assert_eq!(res["Access-Control-Allow-Origin"], "https://app.example.com");
+/// assert_eq!(res["Access-Control-Allow-Credentials"], "true");
+/// assert_eq!(res["Access-Control-Expose-Headers"], "x-request-id");
+/// assert_eq!(res["Vary"], "Origin");
+/// ```
+#[derive(Clone)]
+pub struct CorsPolicy {
+    any_origin: bool,
+    origins: Vec<String>,
+    predicates: Vec<OriginPredicate>,
+    methods: Vec<Method>,
+    any_header: bool,
+    headers: Vec<HeaderName>,
+    exposed_headers: Vec<HeaderName>,
+    credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl CorsPolicy {
+    /// Create a new instance.
+    pub fn new() -> Self {
+        Self {
+            any_origin: false,
+            origins: vec![],
+            predicates: vec![],
+            methods: vec![Method::Get, Method::Head, Method::Post],
+            any_header: false,
+            headers: vec![],
+            exposed_headers: vec![],
+            credentials: false,
+            max_age: None,
+        }
+    }
+
+    /// Allow requests from an origin such as `https://example.com`.
+    ///
+    /// Origins are compared case-insensitively.
+    pub fn allow_origin(&mut self, origin: impl Into<String>) -> &mut Self {
+        self.origins.push(origin.into());
+        self
+    }
+
+    /// Allow requests from any origin.
+    ///
+    /// Credentials are never allowed for policies which allow any origin.
+    pub fn allow_any_origin(&mut self) -> &mut Self {
+        self.any_origin = true;
+        self
+    }
+
+    /// Allow requests from every origin for which the predicate returns `true`.
+    pub fn allow_origin_fn<F>(&mut self, predicate: F) -> &mut Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.predicates.push(Arc::new(predicate));
+        self
+    }
+
+    /// Allow a request method.
+    pub fn allow_method(&mut self, method: Method) -> &mut Self {
+        if !self.methods.contains(&method) {
+            self.methods.push(method);
+        }
+        self
+    }
+
+    /// Allow a request header.
+    pub fn allow_header(&mut self, name: impl Into<HeaderName>) -> &mut Self {
+        let name = name.into();
+        if !self.headers.contains(&name) {
+            self.headers.push(name);
+        }
+        self
+    }
+
+    /// Allow any request header.
+    pub fn allow_any_header(&mut self) -> &mut Self {
+        self.any_header = true;
+        self
+    }
+
+    /// Expose a response header to the requesting origin.
+    pub fn expose_header(&mut self, name: impl Into<HeaderName>) -> &mut Self {
+        let name = name.into();
+        if !self.exposed_headers.contains(&name) {
+            self.exposed_headers.push(name);
+        }
+        self
+    }
+
+    /// Set whether requests may include credentials such as cookies.
+    ///
+    /// This has no effect if the policy allows any origin.
+    pub fn allow_credentials(&mut self, allow: bool) -> &mut Self {
+        self.credentials = allow;
+        self
+    }
+
+    /// Set how long the result of a preflight request may be cached.
+    pub fn max_age(&mut self, max_age: Duration) -> &mut Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Returns `true` if requests from the origin are allowed.
+    pub fn is_origin_allowed(&self, origin: &str) -> bool {
+        self.any_origin
+            || self.origins.iter().any(|o| o.eq_ignore_ascii_case(origin))
+            || self.predicates.iter().any(|predicate| predicate(origin))
+    }
+
+    /// Returns `true` if the request is a CORS preflight request.
+    pub fn is_preflight(&self, req: &Request) -> bool {
+        req.method() == Method::Options
+            && req.header(ORIGIN).is_some()
+            && req.header(ACCESS_CONTROL_REQUEST_METHOD).is_some()
+    }
+
+    /// Create the response to a preflight request.
+    ///
+    /// The response is a `204 No Content` response. If the origin, method or
+    /// any of the headers of the request aren't allowed, no CORS headers are
+    /// set, which causes the browser to reject the actual request.
+    pub fn preflight_response(&self, req: &Request) -> Response {
+        let mut res = Response::new(StatusCode::NoContent);
+        self.vary(&mut res);
+        if self.any_header {
+            append_vary(res.as_mut(), "Access-Control-Request-Headers");
+        }
+
+        let origin = match self.allowed_origin(req) {
+            Some(origin) => origin,
+            None => return res,
+        };
+
+        let method = req
+            .header(ACCESS_CONTROL_REQUEST_METHOD)
+            .and_then(|values| values.last().as_str().trim().parse::<Method>().ok());
+        match method {
+            Some(method) if self.methods.contains(&method) => {}
+            _ => return res,
+        }
+
+        let requested_headers: Vec<&str> = req
+            .header(ACCESS_CONTROL_REQUEST_HEADERS)
+            .into_iter()
+            .flat_map(|values| values.iter())
+            .flat_map(|value| value.as_str().split(','))
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .collect();
+        let headers_allowed = self.any_header
+            || requested_headers
+                .iter()
+                .all(|name| self.headers.iter().any(|allowed| allowed == name));
+        if !headers_allowed {
+            return res;
+        }
+
+        self.apply_origin(&mut res, origin);
+        res.insert_header(ACCESS_CONTROL_ALLOW_METHODS, join(&self.methods));
+        if self.any_header && !requested_headers.is_empty() {
+            res.insert_header(ACCESS_CONTROL_ALLOW_HEADERS, requested_headers.join(", "));
+        } else if !self.any_header && !self.headers.is_empty() {
+            res.insert_header(ACCESS_CONTROL_ALLOW_HEADERS, join(&self.headers));
+        }
+        if let Some(max_age) = self.max_age {
+            res.insert_header(ACCESS_CONTROL_MAX_AGE, max_age.as_secs().to_string());
+        }
+        res
+    }
+
+    /// Apply the policy to the response to an actual request.
+    ///
+    /// If the request's origin is allowed this sets the
+    /// `Access-Control-Allow-Origin`, `Access-Control-Allow-Credentials` and
+    /// `Access-Control-Expose-Headers` headers. `Origin` is added to the
+    /// `Vary` header whenever the response depends on the request's origin.
+    pub fn apply(&self, req: &Request, res: &mut Response) {
+        self.vary(res);
+
+        if let Some(origin) = self.allowed_origin(req) {
+            self.apply_origin(res, origin);
+            if !self.exposed_headers.is_empty() {
+                res.insert_header(ACCESS_CONTROL_EXPOSE_HEADERS, join(&self.exposed_headers));
+            }
+        }
+    }
+
+    /// Get the request's origin, if it's allowed.
+    fn allowed_origin<'a>(&self, req: &'a Request) -> Option<&'a str> {
+        let origin = req.header(ORIGIN)?.last().as_str();
+        match self.is_origin_allowed(origin) {
+            true => Some(origin),
+            false => None,
+        }
+    }
+
+    /// Returns `true` if the policy responds with the `*` origin.
+    fn sends_wildcard(&self) -> bool {
+        self.any_origin
+    }
+
+    /// Returns `true` if the policy allows credentials.
+    fn sends_credentials(&self) -> bool {
+        self.credentials && !self.any_origin
+    }
+
+    fn vary(&self, res: &mut Response) {
+        if !self.sends_wildcard() {
+            append_vary(res.as_mut(), "Origin");
+        }
+    }
+
+    fn apply_origin(&self, res: &mut Response, origin: &str) {
+        if self.sends_wildcard() {
+            res.insert_header(ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+        } else {
+            res.insert_header(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+        }
+        if self.sends_credentials() {
+            res.insert_header(ACCESS_CONTROL_ALLOW_CREDENTIALS, "true");
+        }
+    }
+}
+
+impl Default for CorsPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for CorsPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CorsPolicy")
+            .field("any_origin", &self.any_origin)
+            .field("origins", &self.origins)
+            .field("predicates", &self.predicates.len())
+            .field("methods", &self.methods)
+            .field("any_header", &self.any_header)
+            .field("headers", &self.headers)
+            .field("exposed_headers", &self.exposed_headers)
+            .field("credentials", &self.credentials)
+            .field("max_age", &self.max_age)
+            .finish()
+    }
+}
+
+/// Join a list of items into a comma separated header value.
+fn join<T: fmt::Display>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Url;
+
+    fn request(method: Method, headers: &[(&str, &str)]) -> Request {
+        let mut req = Request::new(method, Url::parse("https://api.example.com").unwrap());
+        for (name, value) in headers {
+            req.insert_header(*name, *value);
+        }
+        req
+    }
+
+    fn preflight(headers: &[(&str, &str)]) -> Request {
+        request(Method::Options, headers)
+    }
+
+    #[test]
+    fn detects_preflight_requests() {
+        let policy = CorsPolicy::new();
+        let req = preflight(&[
+            ("Origin", "https://example.com"),
+            ("Access-Control-Request-Method", "PUT"),
+        ]);
+        assert!(policy.is_preflight(&req));
+
+        let req = preflight(&[("Origin", "https://example.com")]);
+        assert!(!policy.is_preflight(&req));
+
+        let req = request(
+            Method::Get,
+            &[
+                ("Origin", "https://example.com"),
+                ("Access-Control-Request-Method", "PUT"),
+            ],
+        );
+        assert!(!policy.is_preflight(&req));
+    }
+
+    #[test]
+    fn preflight_allows_listed_methods_and_headers() {
+        let mut policy = CorsPolicy::new();
+        policy
+            .allow_origin("https://example.com")
+            .allow_method(Method::Delete)
+            .allow_header("X-Request-Id")
+            .max_age(Duration::from_secs(600));
+
+        let req = preflight(&[
+            ("Origin", "https://EXAMPLE.com"),
+            ("Access-Control-Request-Method", "DELETE"),
+            ("Access-Control-Request-Headers", "x-request-id"),
+        ]);
+        let res = policy.preflight_response(&req);
+        assert_eq!(res.status(), StatusCode::NoContent);
+        assert_eq!(res["Access-Control-Allow-Origin"], "https://EXAMPLE.com");
+        assert_eq!(
+            res["Access-Control-Allow-Methods"],
+            "GET, HEAD, POST, DELETE"
+        );
+        assert_eq!(res["Access-Control-Allow-Headers"], "x-request-id");
+        assert_eq!(res["Access-Control-Max-Age"], "600");
+        assert_eq!(res["Vary"], "Origin");
+        assert!(res.header("Access-Control-Allow-Credentials").is_none());
+    }
+
+    #[test]
+    fn preflight_rejects_disallowed_requests() {
+        let mut policy = CorsPolicy::new();
+        policy.allow_origin("https://example.com");
+
+        let rejected = [
+            preflight(&[
+                ("Origin", "https://evil.com"),
+                ("Access-Control-Request-Method", "GET"),
+            ]),
+            preflight(&[
+                ("Origin", "https://example.com"),
+                ("Access-Control-Request-Method", "PUT"),
+            ]),
+            preflight(&[
+                ("Origin", "https://example.com"),
+                ("Access-Control-Request-Method", "GET"),
+                ("Access-Control-Request-Headers", "x-secret"),
+            ]),
+        ];
+        for req in &rejected {
+            let res = policy.preflight_response(req);
+            assert_eq!(res.status(), StatusCode::NoContent);
+            assert!(res.header("Access-Control-Allow-Origin").is_none());
+            assert!(res.header("Access-Control-Allow-Methods").is_none());
+            assert_eq!(res["Vary"], "Origin");
+        }
+    }
+
+    #[test]
+    fn any_header_reflects_requested_headers() {
+        let mut policy = CorsPolicy::new();
+        policy.allow_any_origin().allow_any_header();
+
+        let req = preflight(&[
+            ("Origin", "https://example.com"),
+            ("Access-Control-Request-Method", "POST"),
+            ("Access-Control-Request-Headers", "x-a, x-b"),
+        ]);
+        let res = policy.preflight_response(&req);
+        assert_eq!(res["Access-Control-Allow-Origin"], "*");
+        assert_eq!(res["Access-Control-Allow-Headers"], "x-a, x-b");
+        assert_eq!(res["Vary"], "Access-Control-Request-Headers");
+    }
+
+    #[test]
+    fn wildcard_origin() {
+        let mut policy = CorsPolicy::new();
+        policy.allow_any_origin();
+
+        let req = request(Method::Get, &[("Origin", "https://example.com")]);
+        let mut res = Response::new(StatusCode::Ok);
+        policy.apply(&req, &mut res);
+        assert_eq!(res["Access-Control-Allow-Origin"], "*");
+        assert!(res.header("Vary").is_none());
+    }
+
+    #[test]
+    fn credentials_are_never_sent_with_the_wildcard_origin() {
+        let mut policy = CorsPolicy::new();
+        policy.allow_any_origin().allow_credentials(true);
+
+        for origin in &["https://evil.example", "null"] {
+            let req = request(Method::Get, &[("Origin", origin)]);
+            let mut res = Response::new(StatusCode::Ok);
+            policy.apply(&req, &mut res);
+            assert_eq!(res["Access-Control-Allow-Origin"], "*");
+            assert!(res.header("Access-Control-Allow-Credentials").is_none());
+            assert!(res.header("Vary").is_none());
+        }
+
+        let req = preflight(&[
+            ("Origin", "https://evil.example"),
+            ("Access-Control-Request-Method", "GET"),
+        ]);
+        let res = policy.preflight_response(&req);
+        assert_eq!(res["Access-Control-Allow-Origin"], "*");
+        assert!(res.header("Access-Control-Allow-Credentials").is_none());
+    }
+
+    #[test]
+    fn apply_to_disallowed_or_same_origin_requests() {
+        let mut policy = CorsPolicy::new();
+        policy
+            .allow_origin_fn(|origin| origin.ends_with(".example.com"))
+            .expose_header("X-Request-Id");
+
+        let req = request(Method::Get, &[("Origin", "https://evil.com")]);
+        let mut res = Response::new(StatusCode::Ok);
+        res.insert_header("Vary", "Accept-Encoding");
+        policy.apply(&req, &mut res);
+        assert!(res.header("Access-Control-Allow-Origin").is_none());
+        assert!(res.header("Access-Control-Expose-Headers").is_none());
+        assert_eq!(res["Vary"], ["Accept-Encoding", "Origin"][..]);
+
+        let req = request(Method::Get, &[]);
+        let mut res = Response::new(StatusCode::Ok);
+        policy.apply(&req, &mut res);
+        assert!(res.header("Access-Control-Allow-Origin").is_none());
+        assert_eq!(res["Vary"], "Origin");
+    }
+}
@@ -0,0 +1,159 @@
+use crate::headers::{HeaderValue, ToHeaderValues};
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::option;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The number of seconds from the epoch to the end of the year 9999.
+const MAX_SECS: u64 = 253_402_300_799;
+
+/// An HTTP-date, as used by headers such as `Date`, `Expires`,
+/// `Last-Modified` and `Retry-After`.
+///
+/// All three formats allowed by RFC 7231 are parsed: IMF-fixdate, the obsolete
+/// RFC 850 format, and ANSI C's `asctime()` format. Dates are always
+/// formatted as IMF-fixdate. HTTP-dates have second precision, so anything
+/// smaller is discarded. Times outside of the years 1970 to 9999 can't be
+/// formatted, and are clamped to that range.
+///
+/// # Specifications
+///
+/// - [RFC 7231, section 7.1.1.1: Date/Time Formats](https://tools.ietf.org/html/rfc7231#section-7.1.1.1)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::HttpDate;
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+///
+/// let date: HttpDate = "Sunday, 06-Nov-94 08:49:37 GMT".parse()?;
+/// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+///
+/// let time: SystemTime = date.into();
+/// assert_eq!(time, UNIX_EPOCH + Duration::from_secs(784111777));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HttpDate {
+    time: SystemTime,
+}
+
+impl HttpDate {
+    /// Create a new instance for the current time.
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+}
+
+impl FromStr for HttpDate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match httpdate::parse_http_date(s.trim()) {
+            Ok(time) => Ok(Self { time }),
+            Err(_) => {
+                let msg = format!("{} is not a valid HTTP date", s);
+                Err(Error::from_str(StatusCode::BadRequest, msg))
+            }
+        }
+    }
+}
+
+impl Display for HttpDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&httpdate::fmt_http_date(self.time))
+    }
+}
+
+impl From<SystemTime> for HttpDate {
+    fn from(time: SystemTime) -> Self {
+        // Clamp to the range `httpdate` can format without panicking.
+        let secs = match time.duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs().min(MAX_SECS),
+            Err(_) => 0,
+        };
+        Self {
+            time: UNIX_EPOCH + Duration::from_secs(secs),
+        }
+    }
+}
+
+impl From<HttpDate> for SystemTime {
+    fn from(date: HttpDate) -> Self {
+        date.time
+    }
+}
+
+impl From<HttpDate> for HeaderValue {
+    fn from(date: HttpDate) -> Self {
+        // SAFETY: IMF-fixdates are always valid ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(date.to_string().into_bytes()) }
+    }
+}
+
+impl ToHeaderValues for HttpDate {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        Ok(Some(HeaderValue::from(*self)).into_iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::{Headers, LAST_MODIFIED};
+
+    fn nov_6_1994() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(784111777)
+    }
+
+    #[test]
+    fn parse_all_formats() -> crate::Result<()> {
+        for s in &[
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ] {
+            let date: HttpDate = s.parse()?;
+            assert_eq!(SystemTime::from(date), nov_6_1994(), "{}", s);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn formats_as_imf_fixdate() {
+        let date = HttpDate::from(nov_6_1994() + Duration::from_millis(999));
+        assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(SystemTime::from(date), nov_6_1994());
+    }
+
+    #[test]
+    fn clamps_unformattable_times() {
+        let date = HttpDate::from(UNIX_EPOCH - Duration::from_secs(1));
+        assert_eq!(date.to_string(), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(SystemTime::from(date), UNIX_EPOCH);
+
+        let date = HttpDate::from(UNIX_EPOCH + Duration::from_secs(MAX_SECS + 10));
+        assert_eq!(date.to_string(), "Fri, 31 Dec 9999 23:59:59 GMT");
+    }
+
+    #[test]
+    fn to_header_values() {
+        let mut headers = Headers::new();
+        headers.insert(LAST_MODIFIED, HttpDate::from(nov_6_1994()));
+        assert_eq!(headers[LAST_MODIFIED], "Sun, 06 Nov 1994 08:49:37 GMT");
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &["", "yesterday", "Sun, 06 Nov 1994 08:49:37 CET"] {
+            let err = s.parse::<HttpDate>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
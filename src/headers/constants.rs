@@ -1,5 +1,7 @@
 use super::HeaderName;
 
+/// The `Content-Disposition` Header
+pub const CONTENT_DISPOSITION: HeaderName = HeaderName::from_lowercase_str("content-disposition");
 /// The `Content-Encoding` Header
 pub const CONTENT_ENCODING: HeaderName = HeaderName::from_lowercase_str("content-encoding");
 /// The `Content-Language` Header
@@ -12,6 +14,12 @@ pub const CONTENT_LOCATION: HeaderName = HeaderName::from_lowercase_str("content
 pub const CONTENT_MD5: HeaderName = HeaderName::from_lowercase_str("content-md5");
 /// The `Content-Range` Header
 pub const CONTENT_RANGE: HeaderName = HeaderName::from_lowercase_str("content-range");
+/// The `Content-Security-Policy` Header
+pub const CONTENT_SECURITY_POLICY: HeaderName =
+    HeaderName::from_lowercase_str("content-security-policy");
+/// The `Content-Security-Policy-Report-Only` Header
+pub const CONTENT_SECURITY_POLICY_REPORT_ONLY: HeaderName =
+    HeaderName::from_lowercase_str("content-security-policy-report-only");
 /// The `Content-Type` Header
 pub const CONTENT_TYPE: HeaderName = HeaderName::from_lowercase_str("content-type");
 
@@ -84,6 +92,26 @@ pub const CACHE_CONTROL: HeaderName = HeaderName::from_lowercase_str("cache-cont
 ///  The `Connection` Header
 pub const CONNECTION: HeaderName = HeaderName::from_lowercase_str("connection");
 
+///  The `Cross-Origin-Embedder-Policy` Header
+pub const CROSS_ORIGIN_EMBEDDER_POLICY: HeaderName =
+    HeaderName::from_lowercase_str("cross-origin-embedder-policy");
+
+///  The `Cross-Origin-Embedder-Policy-Report-Only` Header
+pub const CROSS_ORIGIN_EMBEDDER_POLICY_REPORT_ONLY: HeaderName =
+    HeaderName::from_lowercase_str("cross-origin-embedder-policy-report-only");
+
+///  The `Cross-Origin-Opener-Policy` Header
+pub const CROSS_ORIGIN_OPENER_POLICY: HeaderName =
+    HeaderName::from_lowercase_str("cross-origin-opener-policy");
+
+///  The `Cross-Origin-Opener-Policy-Report-Only` Header
+pub const CROSS_ORIGIN_OPENER_POLICY_REPORT_ONLY: HeaderName =
+    HeaderName::from_lowercase_str("cross-origin-opener-policy-report-only");
+
+///  The `Cross-Origin-Resource-Policy` Header
+pub const CROSS_ORIGIN_RESOURCE_POLICY: HeaderName =
+    HeaderName::from_lowercase_str("cross-origin-resource-policy");
+
 ///  The `ETag` Header
 pub const ETAG: HeaderName = HeaderName::from_lowercase_str("etag");
 
@@ -93,6 +121,9 @@ pub const EXPECT: HeaderName = HeaderName::from_lowercase_str("expect");
 ///  The `Expires` Header
 pub const EXPIRES: HeaderName = HeaderName::from_lowercase_str("expires");
 
+///  The `Forwarded` Header
+pub const FORWARDED: HeaderName = HeaderName::from_lowercase_str("forwarded");
+
 ///  The `From` Header
 pub const FROM: HeaderName = HeaderName::from_lowercase_str("from");
 
@@ -114,12 +145,18 @@ pub const IF_UNMODIFIED_SINCE: HeaderName = HeaderName::from_lowercase_str("if-u
 ///  The `Last-Modified` Header
 pub const LAST_MODIFIED: HeaderName = HeaderName::from_lowercase_str("last-modified");
 
+///  The `Link` Header
+pub const LINK: HeaderName = HeaderName::from_lowercase_str("link");
+
 ///  The `Location` Header
 pub const LOCATION: HeaderName = HeaderName::from_lowercase_str("location");
 
 ///  The `Max-Forwards` Header
 pub const MAX_FORWARDS: HeaderName = HeaderName::from_lowercase_str("max-forwards");
 
+///  The `Permissions-Policy` Header
+pub const PERMISSIONS_POLICY: HeaderName = HeaderName::from_lowercase_str("permissions-policy");
+
 ///  The `Pragma` Header
 pub const PRAGMA: HeaderName = HeaderName::from_lowercase_str("pragma");
 
@@ -129,9 +166,15 @@ pub const PROXY_AUTHENTICATE: HeaderName = HeaderName::from_lowercase_str("proxy
 ///  The `Proxy-Authorization` Header
 pub const PROXY_AUTHORIZATION: HeaderName = HeaderName::from_lowercase_str("proxy-authorization");
 
+///  The `Range` Header
+pub const RANGE: HeaderName = HeaderName::from_lowercase_str("range");
+
 ///  The `Referer` Header
 pub const REFERER: HeaderName = HeaderName::from_lowercase_str("referer");
 
+///  The `Reporting-Endpoints` Header
+pub const REPORTING_ENDPOINTS: HeaderName = HeaderName::from_lowercase_str("reporting-endpoints");
+
 ///  The `Retry-After` Header
 pub const RETRY_AFTER: HeaderName = HeaderName::from_lowercase_str("retry-after");
 
@@ -141,6 +184,10 @@ pub const SERVER: HeaderName = HeaderName::from_lowercase_str("server");
 ///  The `Server` Header
 pub const SERVER_TIMING: HeaderName = HeaderName::from_lowercase_str("server-timing");
 
+///  The `Strict-Transport-Security` Header
+pub const STRICT_TRANSPORT_SECURITY: HeaderName =
+    HeaderName::from_lowercase_str("strict-transport-security");
+
 ///  The `Te` Header
 pub const TE: HeaderName = HeaderName::from_lowercase_str("te");
 
@@ -117,10 +117,20 @@ pub mod url {
 #[macro_use]
 mod utils;
 
+pub mod auth;
+pub mod cache;
+pub mod conditional;
+pub mod content;
+pub mod cors;
 pub mod headers;
 pub mod mime;
+pub mod multipart;
+pub mod other;
+pub mod proxies;
+pub mod range;
 
 mod body;
+mod date;
 mod error;
 mod extensions;
 mod macros;
@@ -144,6 +154,7 @@ cfg_unstable! {
 }
 
 pub use body::Body;
+pub use date::HttpDate;
 pub use error::{Error, Result};
 pub use method::Method;
 pub use request::Request;
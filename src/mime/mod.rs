@@ -109,6 +109,18 @@ impl Mime {
             })
             .flatten()
     }
+
+    /// Get all params.
+    pub(crate) fn params(&self) -> Vec<(&ParamName, &ParamValue)> {
+        match &self.params {
+            Some(ParamKind::Vec(v)) => v.iter().map(|(k, v)| (k, v)).collect(),
+            Some(ParamKind::Utf8) => vec![(
+                &ParamName(Cow::Borrowed("charset")),
+                &ParamValue(Cow::Borrowed("utf8")),
+            )],
+            None => vec![],
+        }
+    }
 }
 
 impl PartialEq<Mime> for Mime {
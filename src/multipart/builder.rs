@@ -0,0 +1,307 @@
+use async_std::io::{self, BufRead, Cursor, Read};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Write};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::{Body, Mime};
+
+type Reader = Box<dyn BufRead + Unpin + Send + Sync + 'static>;
+
+/// Build a `multipart/form-data` body.
+///
+/// Every part is streamed straight from its own `Body` when the resulting
+/// `Body` is read, so large files are never buffered in memory.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> { async_std::task::block_on(async {
+/// #
+/// use http_types::multipart::Multipart;
+/// use http_types::{Body, Method, Request, Url};
+///
+/// let mut multipart = Multipart::new();
+/// multipart
+///     .text("name", "chashu")
+///     .file("avatar", "chashu.png", Body::from_bytes(vec![1, 2, 3]));
+///
+/// let mut req = Request::new(Method::Post, Url::parse("https://example.com")?);
+/// req.set_body(multipart);
+///
+/// let content_type = req.content_type().unwrap();
+/// assert_eq!(content_type.essence(), "multipart/form-data");
+/// assert!(content_type.param("boundary").is_some());
+/// assert!(req.len().is_some());
+/// #
+/// # Ok(()) }) }
+/// ```
+pub struct Multipart {
+    boundary: String,
+    parts: Vec<(String, Body)>,
+}
+
+impl Multipart {
+    /// Create a new instance with a randomly generated boundary.
+    pub fn new() -> Self {
+        let boundary = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .collect();
+        Self {
+            boundary,
+            parts: vec![],
+        }
+    }
+
+    /// Get the boundary that separates the parts.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Get the `multipart/form-data` Mime type, including the boundary.
+    pub fn mime(&self) -> Mime {
+        format!("multipart/form-data; boundary={}", self.boundary)
+            .parse()
+            .unwrap()
+    }
+
+    /// Add a text field.
+    pub fn text(&mut self, name: impl AsRef<str>, value: impl Into<String>) -> &mut Self {
+        let headers = self.part_headers(name.as_ref(), None, None);
+        self.parts.push((headers, Body::from_string(value.into())));
+        self
+    }
+
+    /// Add a file field.
+    ///
+    /// The part's `Content-Type` is taken from the body, which makes this
+    /// convenient to use with `Body::from_file`.
+    pub fn file(
+        &mut self,
+        name: impl AsRef<str>,
+        filename: impl AsRef<str>,
+        body: impl Into<Body>,
+    ) -> &mut Self {
+        let body = body.into();
+        let headers = self.part_headers(name.as_ref(), Some(filename.as_ref()), Some(body.mime()));
+        self.parts.push((headers, body));
+        self
+    }
+
+    /// Add a field with an arbitrary body.
+    ///
+    /// The part's `Content-Type` is taken from the body.
+    pub fn part(&mut self, name: impl AsRef<str>, body: impl Into<Body>) -> &mut Self {
+        let body = body.into();
+        let headers = self.part_headers(name.as_ref(), None, Some(body.mime()));
+        self.parts.push((headers, body));
+        self
+    }
+
+    /// Get the length of the encoded body, if the length of every part is known.
+    pub fn len(&self) -> Option<usize> {
+        let mut len = self.closing_delimiter().len();
+        for (headers, body) in &self.parts {
+            len += headers.len() + body.len()? + 2;
+        }
+        Some(len)
+    }
+
+    /// Returns `true` if no parts have been added.
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Convert into a streaming `Body`.
+    pub fn into_body(self) -> Body {
+        let len = self.len();
+        let mime = self.mime();
+        let closing = self.closing_delimiter();
+
+        let mut readers: VecDeque<Reader> = VecDeque::new();
+        for (headers, body) in self.parts {
+            readers.push_back(Box::new(Cursor::new(headers.into_bytes())));
+            readers.push_back(body.into_reader());
+            readers.push_back(Box::new(Cursor::new(b"\r\n".to_vec())));
+        }
+        readers.push_back(Box::new(Cursor::new(closing.into_bytes())));
+
+        let mut body = Body::from_reader(Concat { readers }, len);
+        body.set_mime(mime);
+        body
+    }
+
+    /// Encode the delimiter and headers which precede a part.
+    fn part_headers(&self, name: &str, filename: Option<&str>, mime: Option<&Mime>) -> String {
+        let mut output = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"",
+            self.boundary,
+            escape(name)
+        );
+        if let Some(filename) = filename {
+            write!(output, "; filename=\"{}\"", escape(filename)).unwrap();
+        }
+        output.push_str("\r\n");
+        if let Some(mime) = mime {
+            write!(output, "Content-Type: {}\r\n", mime).unwrap();
+        }
+        output.push_str("\r\n");
+        output
+    }
+
+    fn closing_delimiter(&self) -> String {
+        format!("--{}--\r\n", self.boundary)
+    }
+}
+
+impl Debug for Multipart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Multipart")
+            .field("boundary", &self.boundary)
+            .field("parts", &self.parts.len())
+            .finish()
+    }
+}
+
+impl From<Multipart> for Body {
+    fn from(multipart: Multipart) -> Self {
+        multipart.into_body()
+    }
+}
+
+/// Escape a field name or file name the way browsers do.
+///
+/// See: https://html.spec.whatwg.org/multipage/form-control-infrastructure.html#multipart-form-data
+fn escape(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Read from a list of readers, one after the other.
+struct Concat {
+    readers: VecDeque<Reader>,
+}
+
+impl Read for Concat {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        while let Some(reader) = self.readers.front_mut() {
+            match Pin::new(reader).poll_read(cx, buf) {
+                Poll::Ready(Ok(0)) if !buf.is_empty() => {
+                    self.readers.pop_front();
+                }
+                other => return other,
+            }
+        }
+        Poll::Ready(Ok(0))
+    }
+}
+
+impl BufRead for Concat {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&'_ [u8]>> {
+        let this = self.get_mut();
+        loop {
+            let reader = match this.readers.front_mut() {
+                Some(reader) => reader,
+                None => return Poll::Ready(Ok(&[])),
+            };
+            match Pin::new(reader).poll_fill_buf(cx) {
+                Poll::Ready(Ok([])) => this.readers.pop_front(),
+                Poll::Ready(Ok(_)) => break,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            };
+        }
+        // The front reader has data buffered, so this resolves immediately.
+        Pin::new(this.readers.front_mut().unwrap()).poll_fill_buf(cx)
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        if let Some(reader) = self.readers.front_mut() {
+            Pin::new(reader).consume(amt);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use async_std::io::prelude::*;
+
+    #[async_std::test]
+    async fn encodes_parts() -> crate::Result<()> {
+        let mut multipart = Multipart::new();
+        multipart
+            .text("name", "chashu")
+            .file("avatar", "nori \"the cat\".txt", Body::from("meow"))
+            .part("data", Body::from_bytes(vec![1, 2, 3]));
+        let boundary = multipart.boundary().to_owned();
+
+        let body = multipart.into_body();
+        let len = body.len();
+        let bytes = body.into_bytes().await?;
+        assert_eq!(len, Some(bytes.len()));
+
+        let expected = format!(
+            concat!(
+                "--{b}\r\n",
+                "Content-Disposition: form-data; name=\"name\"\r\n",
+                "\r\n",
+                "chashu\r\n",
+                "--{b}\r\n",
+                "Content-Disposition: form-data; name=\"avatar\"; filename=\"nori %22the cat%22.txt\"\r\n",
+                "Content-Type: text/plain;charset=utf-8\r\n",
+                "\r\n",
+                "meow\r\n",
+                "--{b}\r\n",
+                "Content-Disposition: form-data; name=\"data\"\r\n",
+                "Content-Type: application/octet-stream\r\n",
+                "\r\n",
+                "\u{1}\u{2}\u{3}\r\n",
+                "--{b}--\r\n",
+            ),
+            b = boundary
+        );
+        assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn unknown_length() {
+        let mut multipart = Multipart::new();
+        multipart.part("stream", Body::from_reader(Cursor::new("meow"), None));
+        assert_eq!(multipart.len(), None);
+        assert_eq!(multipart.into_body().len(), None);
+    }
+
+    #[async_std::test]
+    async fn roundtrip() -> crate::Result<()> {
+        let mut multipart = Multipart::new();
+        multipart.text("a", "first").text("b", "second");
+
+        let mut reader = multipart.into_body().into_multipart()?;
+        let mut values = vec![];
+        while let Some(mut part) = reader.next_part().await? {
+            let mut value = String::new();
+            part.read_to_string(&mut value).await?;
+            values.push((part.name().unwrap().to_owned(), value));
+        }
+        assert_eq!(
+            values,
+            vec![
+                ("a".to_owned(), "first".to_owned()),
+                ("b".to_owned(), "second".to_owned())
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+//! Multipart/form-data bodies.
+//!
+//! # Specifications
+//!
+//! - [RFC 7578, section 4: Definition of multipart/form-data](https://tools.ietf.org/html/rfc7578#section-4)
+//! - [RFC 2046, section 5.1: Multipart Media Type](https://tools.ietf.org/html/rfc2046#section-5.1)
+//!
+//! # Examples
+//!
+//! Parsing a multipart body:
+//!
+//! ```
+//! # fn main() -> http_types::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::io::prelude::*;
+//! use http_types::{Body, Mime};
+//!
+//! let mut body = Body::from_string(concat!(
+//!     "--boundary\r\n",
+//!     "Content-Disposition: form-data; name=\"cat\"\r\n",
+//!     "\r\n",
+//!     "chashu\r\n",
+//!     "--boundary--\r\n",
+//! ).to_owned());
+//! body.set_mime(Mime::from("multipart/form-data; boundary=boundary"));
+//!
+//! let mut multipart = body.into_multipart()?;
+//! while let Some(mut part) = multipart.next_part().await? {
+//!     let mut value = String::new();
+//!     part.read_to_string(&mut value).await?;
+//!     assert_eq!(part.name(), Some("cat"));
+//!     assert_eq!(value, "chashu");
+//! }
+//! #
+//! # Ok(()) }) }
+//! ```
+//!
+//! Building a multipart body:
+//!
+//! ```
+//! use http_types::multipart::Multipart;
+//! use http_types::{Body, Response, StatusCode};
+//!
+//! let mut multipart = Multipart::new();
+//! multipart.text("cat", "chashu");
+//!
+//! let mut res = Response::new(StatusCode::Ok);
+//! res.set_body(multipart);
+//! assert_eq!(res.content_type().unwrap().essence(), "multipart/form-data");
+//! ```
+
+mod builder;
+mod reader;
+
+pub use builder::Multipart;
+pub use reader::{MultipartReader, Part};
@@ -0,0 +1,472 @@
+use async_std::future;
+use async_std::io::{self, BufRead, Read};
+
+use std::fmt::{self, Debug};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::content::ContentDisposition;
+use crate::headers::{HeaderName, HeaderValue, Headers, CONTENT_DISPOSITION, CONTENT_TYPE};
+use crate::{Body, Error, Mime, StatusCode};
+
+/// The maximum size of the headers of a single part.
+const MAX_HEADERS_LEN: usize = 8 * 1024;
+
+/// A streaming `multipart/form-data` parser.
+///
+/// Parts are read one at a time using [`next_part`](#method.next_part). Each
+/// part borrows the reader, and streams its contents straight from the
+/// underlying `Body`; at no point is the whole body buffered in memory.
+///
+/// If a part is dropped before it's been read to the end, the remainder of
+/// its contents is skipped when the next part is requested.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::io::prelude::*;
+/// use http_types::{Method, Request, Url};
+///
+/// let mut req = Request::new(Method::Post, Url::parse("https://example.com")?);
+/// req.insert_header("Content-Type", "multipart/form-data; boundary=boundary");
+/// req.set_body(concat!(
+///     "--boundary\r\n",
+///     "Content-Disposition: form-data; name=\"file\"; filename=\"nori.txt\"\r\n",
+///     "Content-Type: text/plain\r\n",
+///     "\r\n",
+///     "meow\r\n",
+///     "--boundary--\r\n",
+/// ));
+///
+/// let mut multipart = req.body_multipart()?;
+/// let mut part = multipart.next_part().await?.unwrap();
+/// assert_eq!(part.name(), Some("file"));
+/// assert_eq!(part.filename(), Some("nori.txt"));
+/// assert_eq!(part.content_type().unwrap().essence(), "text/plain");
+///
+/// let mut contents = String::new();
+/// part.read_to_string(&mut contents).await?;
+/// assert_eq!(contents, "meow");
+///
+/// assert!(multipart.next_part().await?.is_none());
+/// #
+/// # Ok(()) }) }
+/// ```
+pub struct MultipartReader {
+    body: Body,
+    /// Bytes read from the body that have not been handed out yet.
+    buf: Vec<u8>,
+    /// The delimiter that separates parts: `CRLF "--" boundary`.
+    delimiter: Vec<u8>,
+    done: bool,
+}
+
+impl MultipartReader {
+    /// Create a new instance from a body and a boundary.
+    pub(crate) fn new(body: Body, boundary: &str) -> Self {
+        let mut delimiter = b"\r\n--".to_vec();
+        delimiter.extend_from_slice(boundary.as_bytes());
+
+        Self {
+            body,
+            // The first delimiter isn't required to be preceded by a CRLF.
+            // Prefixing the stream with one lets us treat all delimiters alike.
+            buf: b"\r\n".to_vec(),
+            delimiter,
+            done: false,
+        }
+    }
+
+    /// Get the next part of the body.
+    ///
+    /// Returns `Ok(None)` once the closing delimiter has been read.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// body is not valid `multipart/form-data`, or ends prematurely.
+    pub async fn next_part(&mut self) -> crate::Result<Option<Part<'_>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        // Skip the preamble, or whatever is left of the previous part.
+        self.skip_past_delimiter().await?;
+
+        // A delimiter directly followed by "--" closes the body.
+        self.fill_to(2).await?;
+        if self.buf.starts_with(b"--") {
+            self.done = true;
+            return Ok(None);
+        }
+
+        // Skip the transport padding and the line break after the delimiter.
+        let end = self.find_within(b"\r\n", 1024).await?;
+        if !self.buf[..end].iter().all(|b| *b == b' ' || *b == b'\t') {
+            return Err(bad_request("Malformed multipart delimiter"));
+        }
+        self.buf.drain(..end + 2);
+
+        let (headers, name, filename) = self.read_headers().await?;
+        Ok(Some(Part {
+            reader: self,
+            headers,
+            name,
+            filename,
+        }))
+    }
+
+    /// Read the headers of a part, leaving the buffer at the start of its
+    /// contents.
+    async fn read_headers(&mut self) -> crate::Result<(Headers, Option<String>, Option<String>)> {
+        let mut headers = Headers::new();
+        let mut name = None;
+        let mut filename = None;
+
+        self.fill_to(2).await?;
+        if self.buf.starts_with(b"\r\n") {
+            self.buf.drain(..2);
+            return Ok((headers, name, filename));
+        }
+
+        let end = self.find_within(b"\r\n\r\n", MAX_HEADERS_LEN).await?;
+        let raw = String::from_utf8(self.buf[..end].to_vec())
+            .map_err(|_| bad_request("Multipart headers should be valid UTF-8"))?;
+        self.buf.drain(..end + 4);
+
+        for line in raw.split("\r\n") {
+            let mut parts = line.splitn(2, ':');
+            let (key, value) = match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => (key.trim(), value.trim()),
+                _ => return Err(bad_request("Malformed multipart header")),
+            };
+            let key: HeaderName = key
+                .parse()
+                .map_err(|_| bad_request("Invalid multipart header name"))?;
+
+            if key == CONTENT_DISPOSITION {
+                let disposition: ContentDisposition = value.parse()?;
+                name = disposition.field_name().map(ToOwned::to_owned);
+                filename = disposition.filename().map(ToOwned::to_owned);
+            }
+
+            // Browsers send file names as raw UTF-8, which isn't valid in a
+            // `HeaderValue`. Those bytes are stored percent-encoded instead.
+            headers.append(key, to_header_value(value));
+        }
+
+        Ok((headers, name, filename))
+    }
+
+    /// Discard all bytes up to and including the next delimiter.
+    async fn skip_past_delimiter(&mut self) -> crate::Result<()> {
+        loop {
+            if let Some(index) = find(&self.buf, &self.delimiter) {
+                self.buf.drain(..index + self.delimiter.len());
+                return Ok(());
+            }
+            let keep = self.delimiter.len() - 1;
+            let len = self.buf.len().saturating_sub(keep);
+            self.buf.drain(..len);
+            self.fill().await?;
+        }
+    }
+
+    /// Find `needle` in the buffer, reading more bytes from the body if needed.
+    async fn find_within(&mut self, needle: &[u8], limit: usize) -> crate::Result<usize> {
+        loop {
+            if let Some(index) = find(&self.buf, needle) {
+                return Ok(index);
+            }
+            if self.buf.len() > limit {
+                return Err(bad_request("Multipart headers are too large"));
+            }
+            self.fill().await?;
+        }
+    }
+
+    /// Make sure the buffer holds at least `len` bytes.
+    async fn fill_to(&mut self, len: usize) -> crate::Result<()> {
+        while self.buf.len() < len {
+            self.fill().await?;
+        }
+        Ok(())
+    }
+
+    /// Read more bytes from the body into the buffer.
+    async fn fill(&mut self) -> crate::Result<()> {
+        future::poll_fn(|cx| self.poll_fill(cx))
+            .await
+            .map_err(|err| Error::new(StatusCode::BadRequest, err))
+    }
+
+    /// Read more bytes from the body into the buffer.
+    ///
+    /// Reaching the end of the body before the closing delimiter is an error.
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut body = Pin::new(&mut self.body);
+        let data = match body.as_mut().poll_fill_buf(cx) {
+            Poll::Ready(Ok(data)) => data,
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        };
+        if data.is_empty() {
+            let err = io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Unexpected end of multipart body",
+            );
+            return Poll::Ready(Err(err));
+        }
+        let len = data.len();
+        self.buf.extend_from_slice(data);
+        body.consume(len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Debug for MultipartReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultipartReader")
+            .field("body", &self.body)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+/// A single part of a `multipart/form-data` body.
+///
+/// `Part` implements `AsyncBufRead`, and reading from it streams the part's
+/// contents until the next delimiter is reached.
+pub struct Part<'a> {
+    reader: &'a mut MultipartReader,
+    headers: Headers,
+    name: Option<String>,
+    filename: Option<String>,
+}
+
+impl<'a> Part<'a> {
+    /// Get the headers of this part.
+    pub fn headers(&self) -> &Headers {
+        &self.headers
+    }
+
+    /// Get the field name from the `Content-Disposition` header.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Get the file name from the `Content-Disposition` header.
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// Get the `Content-Type` of this part.
+    ///
+    /// Per the spec parts without a `Content-Type` header should be treated as
+    /// `text/plain`, but that's left up to the caller to decide.
+    pub fn content_type(&self) -> Option<Mime> {
+        self.headers.get(CONTENT_TYPE)?.last().as_str().parse().ok()
+    }
+}
+
+impl Debug for Part<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Part")
+            .field("headers", &self.headers)
+            .field("name", &self.name)
+            .field("filename", &self.filename)
+            .finish()
+    }
+}
+
+impl Read for Part<'_> {
+    #[allow(missing_doc_code_examples)]
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let data = match self.as_mut().poll_fill_buf(cx) {
+            Poll::Ready(Ok(data)) => data,
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        };
+        let len = std::cmp::min(data.len(), buf.len());
+        buf[..len].copy_from_slice(&data[..len]);
+        self.consume(len);
+        Poll::Ready(Ok(len))
+    }
+}
+
+impl BufRead for Part<'_> {
+    #[allow(missing_doc_code_examples)]
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&'_ [u8]>> {
+        let reader = &mut *self.get_mut().reader;
+        let end = loop {
+            // Everything up to the delimiter belongs to this part. Reaching
+            // the delimiter means the part has been read to the end.
+            if let Some(index) = find(&reader.buf, &reader.delimiter) {
+                break index;
+            }
+
+            // The tail of the buffer could be the start of a delimiter, so we
+            // only hand out the bytes before it.
+            let safe = reader.buf.len().saturating_sub(reader.delimiter.len() - 1);
+            if safe > 0 {
+                break safe;
+            }
+
+            match reader.poll_fill(cx) {
+                Poll::Ready(Ok(())) => continue,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        };
+        Poll::Ready(Ok(&reader.buf[..end]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().reader.buf.drain(..amt);
+    }
+}
+
+/// Find the first occurrence of `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Convert a raw header value into a `HeaderValue`, percent-encoding any
+/// non-ASCII bytes.
+fn to_header_value(value: &str) -> HeaderValue {
+    let mut output = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii() {
+            output.push(byte as char);
+        } else {
+            output.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    // SAFETY: all non-ASCII bytes have been percent-encoded.
+    unsafe { HeaderValue::from_bytes_unchecked(output.into_bytes()) }
+}
+
+fn bad_request(msg: &'static str) -> Error {
+    Error::from_str(StatusCode::BadRequest, msg)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use async_std::io::prelude::*;
+    use async_std::io::Cursor;
+
+    fn reader(body: &'static str) -> MultipartReader {
+        MultipartReader::new(Body::from(body), "boundary")
+    }
+
+    /// A reader that hands out one byte at a time, to exercise delimiters
+    /// spanning multiple reads.
+    fn trickle(body: &'static str) -> MultipartReader {
+        let reader = io::BufReader::with_capacity(1, Cursor::new(body));
+        MultipartReader::new(Body::from_reader(reader, None), "boundary")
+    }
+
+    async fn read_all(multipart: &mut MultipartReader) -> crate::Result<Vec<(String, String)>> {
+        let mut parts = vec![];
+        while let Some(mut part) = multipart.next_part().await? {
+            let mut contents = String::new();
+            part.read_to_string(&mut contents).await?;
+            parts.push((part.name().unwrap_or("").to_owned(), contents));
+        }
+        Ok(parts)
+    }
+
+    const BODY: &str = concat!(
+        "preamble\r\n",
+        "--boundary\r\n",
+        "Content-Disposition: form-data; name=\"a\"\r\n",
+        "\r\n",
+        "first\r\n",
+        "--boundary  \r\n",
+        "Content-Disposition: form-data; name=b\r\n",
+        "\r\n",
+        "second\r\n--boundar\r\n",
+        "--boundary--\r\n",
+        "epilogue",
+    );
+
+    #[async_std::test]
+    async fn parses_parts() -> crate::Result<()> {
+        let parts = read_all(&mut reader(BODY)).await?;
+        assert_eq!(
+            parts,
+            vec![
+                ("a".to_owned(), "first".to_owned()),
+                ("b".to_owned(), "second\r\n--boundar".to_owned()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn parses_parts_split_across_reads() -> crate::Result<()> {
+        let parts = read_all(&mut trickle(BODY)).await?;
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1].1, "second\r\n--boundar");
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn skips_unread_parts() -> crate::Result<()> {
+        let mut multipart = reader(BODY);
+        let part = multipart.next_part().await?.unwrap();
+        assert_eq!(part.name(), Some("a"));
+        let mut part = multipart.next_part().await?.unwrap();
+        assert_eq!(part.name(), Some("b"));
+        let mut contents = String::new();
+        part.read_to_string(&mut contents).await?;
+        assert!(multipart.next_part().await?.is_none());
+        assert!(multipart.next_part().await?.is_none());
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn utf8_filenames() -> crate::Result<()> {
+        let mut multipart = reader(concat!(
+            "--boundary\r\n",
+            "Content-Disposition: form-data; name=\"file\"; filename=\"ノリ.txt\"\r\n",
+            "\r\n",
+            "\r\n",
+            "--boundary--",
+        ));
+        let part = multipart.next_part().await?.unwrap();
+        assert_eq!(part.filename(), Some("ノリ.txt"));
+        assert!(part.headers()[CONTENT_DISPOSITION].as_str().is_ascii());
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn bad_request_on_premature_end() {
+        let mut multipart = reader("--boundary\r\n\r\nunterminated");
+        let mut part = multipart.next_part().await.unwrap().unwrap();
+        let mut contents = String::new();
+        assert!(part.read_to_string(&mut contents).await.is_err());
+
+        let mut multipart = reader("--boundary\r\n\r\nunterminated");
+        drop(multipart.next_part().await.unwrap().unwrap());
+        let err = multipart.next_part().await.unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+
+    #[async_std::test]
+    async fn bad_request_on_missing_delimiter() {
+        let err = read_all(&mut reader("no delimiter here"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
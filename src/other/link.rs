@@ -0,0 +1,515 @@
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, LINK};
+use crate::utils::{
+    decode_ext_value, encode_ext_value, is_printable, is_token, quote, split_quoted, unquote,
+};
+use crate::{Error, StatusCode, Url};
+
+use std::fmt::{self, Display, Write};
+use std::iter::Iterator;
+use std::option;
+use std::slice;
+
+/// Typed links to related resources.
+///
+/// Link targets are resolved against a base URL when parsing, which is
+/// usually the URL of the request.
+///
+/// # Specifications
+///
+/// - [RFC 8288: Web Linking](https://tools.ietf.org/html/rfc8288)
+/// - [RFC 8187: Indicating Character Encoding and Language for HTTP Header Field Parameters](https://tools.ietf.org/html/rfc8187)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::{Response, Url};
+/// use http_types::other::{Link, LinkValue};
+///
+/// let base = Url::parse("https://example.com/cats?page=2")?;
+///
+/// let mut link = Link::new();
+/// link.push(LinkValue::new(base.join("?page=3")?, "next"));
+/// link.push(LinkValue::new(base.join("?page=1")?, "prev"));
+///
+/// let mut res = Response::new(200);
+/// link.apply(&mut res);
+///
+/// let link = Link::from_headers(&base, res)?.unwrap();
+/// let next = link.get("next").unwrap();
+/// assert_eq!(next.target().as_str(), "https://example.com/cats?page=3");
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Link {
+    links: Vec<LinkValue>,
+}
+
+impl Link {
+    /// Create a new instance of `Link`.
+    pub fn new() -> Self {
+        Self { links: vec![] }
+    }
+
+    /// Create a new instance from headers, resolving link targets against
+    /// the base URL.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed.
+    pub fn from_headers(base: &Url, headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(LINK) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut links = vec![];
+        for value in headers {
+            for item in split_links(value.as_str()) {
+                links.push(LinkValue::parse(base, item)?);
+            }
+        }
+        Ok(Some(Self { links }))
+    }
+
+    /// Sets the `Link` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(LINK, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        LINK
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::new();
+        for (n, link) in self.links.iter().enumerate() {
+            match n {
+                0 => write!(output, "{}", link).unwrap(),
+                _ => write!(output, ", {}", link).unwrap(),
+            };
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Push a link into the list of links.
+    pub fn push(&mut self, link: LinkValue) {
+        self.links.push(link);
+    }
+
+    /// Get the first link with a relation type.
+    pub fn get(&self, rel: &str) -> Option<&LinkValue> {
+        self.links.iter().find(|link| link.has_rel(rel))
+    }
+
+    /// An iterator visiting all links with a relation type.
+    pub fn get_all<'a>(&'a self, rel: &'a str) -> impl Iterator<Item = &'a LinkValue> + 'a {
+        self.links.iter().filter(move |link| link.has_rel(rel))
+    }
+
+    /// Returns `true` if there are no links.
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// An iterator visiting all links.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.links.iter(),
+        }
+    }
+}
+
+impl Default for Link {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Split a header value into link values.
+///
+/// Commas separate links, but they may also appear inside the link target
+/// and in quoted parameter values.
+fn split_links(s: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut start = 0;
+    let mut in_target = false;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' if !in_target => quoted = !quoted,
+            '<' if !quoted => in_target = true,
+            '>' if !quoted => in_target = false,
+            ',' if !quoted && !in_target => {
+                items.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&s[start..]);
+    items
+        .into_iter()
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+impl IntoIterator for Link {
+    type Item = LinkValue;
+    type IntoIter = std::vec::IntoIter<LinkValue>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.links.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Link {
+    type Item = &'a LinkValue;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over entries in `Link`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, LinkValue>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a LinkValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ToHeaderValues for Link {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+/// A single link in the `Link` header.
+///
+/// Parameter names are case-insensitive, and are stored lowercased.
+/// Parameters sent in the RFC 8187 extended notation, such as `title*`, are
+/// decoded and stored under their plain name. They take precedence over
+/// plain parameters with the same name. Values which aren't ASCII are encoded
+/// in the extended notation again when the header is written.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LinkValue {
+    target: Url,
+    rels: Vec<String>,
+    params: Vec<(String, String)>,
+}
+
+impl LinkValue {
+    /// Create a new link with a relation type.
+    pub fn new(target: Url, rel: impl Into<String>) -> Self {
+        let mut link = Self {
+            target,
+            rels: vec![],
+            params: vec![],
+        };
+        link.push_rel(rel);
+        link
+    }
+
+    /// Get the link target.
+    pub fn target(&self) -> &Url {
+        &self.target
+    }
+
+    /// Set the link target.
+    pub fn set_target(&mut self, target: Url) {
+        self.target = target;
+    }
+
+    /// Get the relation types.
+    pub fn rels(&self) -> impl Iterator<Item = &str> + '_ {
+        self.rels.iter().map(|rel| rel.as_str())
+    }
+
+    /// Returns `true` if the link has the relation type.
+    ///
+    /// Relation types are compared case-insensitively.
+    pub fn has_rel(&self, rel: &str) -> bool {
+        self.rels.iter().any(|r| r.eq_ignore_ascii_case(rel))
+    }
+
+    /// Add a relation type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the relation type is empty or contains whitespace.
+    pub fn push_rel(&mut self, rel: impl Into<String>) {
+        let rel = rel.into();
+        assert!(
+            !rel.is_empty() && !rel.contains(char::is_whitespace),
+            "Relation types should not be empty or contain whitespace"
+        );
+        if !self.has_rel(&rel) {
+            self.rels.push(rel);
+        }
+    }
+
+    /// Get the human-readable title of the link.
+    pub fn title(&self) -> Option<&str> {
+        self.param("title")
+    }
+
+    /// Set the human-readable title of the link.
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.insert_param("title", title);
+    }
+
+    /// Get the value of a target attribute.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Insert a target attribute, replacing any attribute with the same name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name isn't a token, or if it's `rel`.
+    pub fn insert_param(&mut self, name: impl AsRef<str>, value: impl Into<String>) {
+        let name = name.as_ref().trim_end_matches('*').to_ascii_lowercase();
+        assert!(is_token(&name), "Link param names should be tokens");
+        assert!(
+            name != "rel",
+            "Use `LinkValue::push_rel` to add relation types"
+        );
+
+        let value = value.into();
+        match self.params.iter_mut().find(|(key, _)| *key == name) {
+            Some(param) => param.1 = value,
+            None => self.params.push((name, value)),
+        }
+    }
+
+    /// Parse a link value, resolving its target against the base URL.
+    fn parse(base: &Url, s: &str) -> crate::Result<Self> {
+        let invalid = || {
+            let msg = format!("Invalid link: {}", s);
+            Error::from_str(StatusCode::BadRequest, msg)
+        };
+
+        let s = s.trim();
+        let end = match (s.starts_with('<'), s.find('>')) {
+            (true, Some(end)) => end,
+            _ => return Err(invalid()),
+        };
+        let target = base.join(&s[1..end]).map_err(|_| invalid())?;
+
+        let mut link = Self {
+            target,
+            rels: vec![],
+            params: vec![],
+        };
+        let mut extended = vec![];
+        let mut has_rel = false;
+        let params = &s[end + 1..];
+        if !params.trim().is_empty() && !params.trim_start().starts_with(';') {
+            return Err(invalid());
+        }
+        for param in split_quoted(params, ';') {
+            let (name, value) = match param.find('=') {
+                Some(index) => (param[..index].trim(), param[index + 1..].trim()),
+                None => (param, ""),
+            };
+            let name = name.to_ascii_lowercase();
+
+            if let Some(name) = name.strip_suffix('*') {
+                // `insert_param` strips any further `*`, so reject those here.
+                if !is_token(name) || name.ends_with('*') || name == "rel" {
+                    return Err(invalid());
+                }
+                let value = decode_ext_value(value).ok_or_else(invalid)?;
+                link.insert_param(name, value);
+                extended.push(name.to_owned());
+                continue;
+            }
+
+            let value = match value.starts_with('"') {
+                true => unquote(value).ok_or_else(invalid)?,
+                false => value.to_owned(),
+            };
+            if !is_token(&name) {
+                return Err(invalid());
+            }
+            if name == "rel" {
+                // Only the first `rel` parameter is used.
+                if !has_rel {
+                    value.split_whitespace().for_each(|rel| link.push_rel(rel));
+                    has_rel = true;
+                }
+            } else if !extended.contains(&name) {
+                link.insert_param(name, value);
+            }
+        }
+        Ok(link)
+    }
+}
+
+impl Display for LinkValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.target)?;
+        if !self.rels.is_empty() {
+            write!(f, "; rel={}", quote(&self.rels.join(" ")))?;
+        }
+        for (name, value) in &self.params {
+            if is_printable(value) {
+                write!(f, "; {}={}", name, quote(value))?;
+            } else {
+                write!(f, "; {}*={}", name, encode_ext_value(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    fn base() -> Url {
+        Url::parse("https://example.com/api/cats?page=2").unwrap()
+    }
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut next = LinkValue::new(base().join("?page=3")?, "next");
+        next.set_title("Next page");
+        let mut last = LinkValue::new(base().join("?page=9")?, "last");
+        last.set_title("Letzte Seite →");
+
+        let mut link = Link::new();
+        link.push(next.clone());
+        link.push(last.clone());
+
+        let mut headers = Headers::new();
+        link.apply(&mut headers);
+        assert_eq!(
+            headers[LINK],
+            concat!(
+                r#"<https://example.com/api/cats?page=3>; rel="next"; title="Next page", "#,
+                r#"<https://example.com/api/cats?page=9>; rel="last"; "#,
+                r#"title*=UTF-8''Letzte%20Seite%20%E2%86%92"#,
+            )
+        );
+
+        let link = Link::from_headers(&base(), headers)?.unwrap();
+        assert_eq!(link.get("next"), Some(&next));
+        assert_eq!(link.get("LAST"), Some(&last));
+        assert_eq!(link.get("prev"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn control_characters_are_encoded() -> crate::Result<()> {
+        let mut next = LinkValue::new(base().join("?page=3")?, "next");
+        next.set_title("a\r\nSet-Cookie: x=1");
+
+        let mut link = Link::new();
+        link.push(next.clone());
+
+        let mut headers = Headers::new();
+        link.apply(&mut headers);
+        assert_eq!(
+            headers[LINK],
+            concat!(
+                r#"<https://example.com/api/cats?page=3>; rel="next"; "#,
+                "title*=UTF-8''a%0D%0ASet-Cookie%3A%20x%3D1"
+            )
+        );
+
+        let link = Link::from_headers(&base(), headers)?.unwrap();
+        assert_eq!(link.get("next"), Some(&next));
+        Ok(())
+    }
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append(
+            LINK,
+            concat!(
+                r#"</TheBook/chapter2>; rel="previous"; title*=UTF-8'de'letztes%20Kapitel, "#,
+                r#"</TheBook/chapter4>; rel="next start"; title="next, chapter"; "#,
+                r#"title*=UTF-8'de'n%c3%a4chstes%20Kapitel; type=text/html; rel=ignored"#,
+            ),
+        );
+        headers.append(
+            LINK,
+            "<https://example.org/?a=1,2>; rel=alternate; hreflang=en",
+        );
+
+        let link = Link::from_headers(&base(), headers)?.unwrap();
+        let links: Vec<_> = link.iter().collect();
+        assert_eq!(links.len(), 3);
+
+        assert_eq!(
+            links[0].target().as_str(),
+            "https://example.com/TheBook/chapter2"
+        );
+        assert_eq!(links[0].title(), Some("letztes Kapitel"));
+
+        assert_eq!(links[1].rels().collect::<Vec<_>>(), vec!["next", "start"]);
+        assert_eq!(links[1].title(), Some("nächstes Kapitel"));
+        assert_eq!(links[1].param("type"), Some("text/html"));
+        assert_eq!(link.get_all("start").count(), 1);
+
+        assert_eq!(links[2].target().as_str(), "https://example.org/?a=1,2");
+        assert_eq!(links[2].param("hreflang"), Some("en"));
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = [
+            "https://example.com; rel=next",
+            "<https://example.com; rel=next",
+            "<https://example.com> rel=next",
+            "<https://example.com>; title*=UTF-8''a b",
+            "<https://example.com>; rel*=UTF-8''next",
+            "<https://example.com>; *=UTF-8''x",
+            "<https://example.com>; rel**=UTF-8''next",
+            "<https://example.com>; title**=UTF-8''x",
+            "<https://example.com>; rel=\"next",
+        ];
+        for value in &values {
+            let mut headers = Headers::new();
+            headers.insert(LINK, *value);
+            let err = Link::from_headers(&base(), headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
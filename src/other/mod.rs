@@ -0,0 +1,7 @@
+//! Miscellaneous HTTP headers.
+
+mod link;
+mod retry_after;
+
+pub use link::{Iter, Link, LinkValue};
+pub use retry_after::RetryAfter;
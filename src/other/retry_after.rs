@@ -0,0 +1,165 @@
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, RETRY_AFTER};
+use crate::HttpDate;
+
+use std::option;
+use std::time::{Duration, SystemTime};
+
+/// Indicate how long the user agent should wait before making a follow-up
+/// request.
+///
+/// The value is either an HTTP-date, or a number of seconds to delay after
+/// the response is received.
+///
+/// # Specifications
+///
+/// - [RFC 7231, section 7.1.3: Retry-After](https://tools.ietf.org/html/rfc7231#section-7.1.3)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::other::RetryAfter;
+/// use http_types::Response;
+/// use std::time::{Duration, SystemTime};
+///
+/// let retry = RetryAfter::Delay(Duration::from_secs(10));
+///
+/// let mut res = Response::new(429);
+/// retry.apply(&mut res);
+/// assert_eq!(res["Retry-After"], "10");
+///
+/// let retry = RetryAfter::from_headers(res)?.unwrap();
+/// assert_eq!(retry.delay_from(SystemTime::now()), Duration::from_secs(10));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAfter {
+    /// Retry after the given number of seconds.
+    Delay(Duration),
+    /// Retry at the given date.
+    Date(HttpDate),
+}
+
+impl RetryAfter {
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// value is neither a number of seconds nor an HTTP-date.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(RETRY_AFTER) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If we successfully parsed the header then there's always at least one
+        // entry. We want the last entry.
+        let value = headers.iter().last().unwrap().as_str().trim();
+        let retry = match value.parse::<u64>() {
+            Ok(secs) => RetryAfter::Delay(Duration::from_secs(secs)),
+            Err(_) => RetryAfter::Date(value.parse()?),
+        };
+        Ok(Some(retry))
+    }
+
+    /// Get the delay relative to `now`.
+    ///
+    /// Dates in the past result in a delay of zero.
+    pub fn delay_from(&self, now: SystemTime) -> Duration {
+        match self {
+            RetryAfter::Delay(delay) => *delay,
+            RetryAfter::Date(date) => SystemTime::from(*date)
+                .duration_since(now)
+                .unwrap_or_else(|_| Duration::from_secs(0)),
+        }
+    }
+
+    /// Sets the `Retry-After` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(RETRY_AFTER, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        RETRY_AFTER
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = match self {
+            RetryAfter::Delay(delay) => delay.as_secs().to_string(),
+            RetryAfter::Date(date) => date.to_string(),
+        };
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+}
+
+impl From<Duration> for RetryAfter {
+    fn from(delay: Duration) -> Self {
+        RetryAfter::Delay(delay)
+    }
+}
+
+impl From<SystemTime> for RetryAfter {
+    fn from(time: SystemTime) -> Self {
+        RetryAfter::Date(time.into())
+    }
+}
+
+impl ToHeaderValues for RetryAfter {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn delay() -> crate::Result<()> {
+        let retry = RetryAfter::from(Duration::from_secs(120));
+
+        let mut headers = Headers::new();
+        retry.apply(&mut headers);
+        assert_eq!(headers[RETRY_AFTER], "120");
+
+        let retry = RetryAfter::from_headers(headers)?.unwrap();
+        assert_eq!(retry, RetryAfter::Delay(Duration::from_secs(120)));
+        Ok(())
+    }
+
+    #[test]
+    fn date() -> crate::Result<()> {
+        let now = SystemTime::now();
+        let retry = RetryAfter::from(now + Duration::from_secs(60));
+
+        let mut headers = Headers::new();
+        retry.apply(&mut headers);
+
+        let retry = RetryAfter::from_headers(headers)?.unwrap();
+        let delay = retry.delay_from(now);
+        assert!(delay <= Duration::from_secs(60) && delay > Duration::from_secs(58));
+        assert_eq!(
+            retry.delay_from(now + Duration::from_secs(120)),
+            Duration::from_secs(0)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let mut headers = Headers::new();
+        headers.insert(RETRY_AFTER, "-1");
+        let err = RetryAfter::from_headers(headers).unwrap_err();
+        assert_eq!(err.status(), 400);
+    }
+}
@@ -0,0 +1,518 @@
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, FORWARDED};
+use crate::proxies::Node;
+use crate::utils::{is_token, quote, split_quoted, unquote};
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display, Write};
+use std::iter::Iterator;
+use std::option;
+use std::slice;
+
+/// Disclose information about the proxies a request passed through.
+///
+/// Every proxy appends an element describing the request it received, so the
+/// first element is the closest to the client. Any element may have been sent
+/// by the client itself though, so use `ProxyTrust` to decide which elements
+/// can be relied upon.
+///
+/// # Specifications
+///
+/// - [RFC 7239: Forwarded HTTP Extension](https://tools.ietf.org/html/rfc7239)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::{Method, Request};
+/// use http_types::proxies::{Forwarded, ForwardedElement};
+///
+/// let mut element = ForwardedElement::new();
+/// element.set_forwarded_for(Some("[2001:db8:cafe::17]:4711".parse()?));
+/// element.set_proto(Some("https".into()));
+///
+/// let mut forwarded = Forwarded::new();
+/// forwarded.push(element);
+///
+/// let mut req = Request::new(Method::Get, "http://example.com");
+/// forwarded.apply(&mut req);
+/// assert_eq!(req["Forwarded"], r#"for="[2001:db8:cafe::17]:4711";proto=https"#);
+///
+/// let forwarded = Forwarded::from_headers(req)?.unwrap();
+/// let element = forwarded.iter().next().unwrap();
+/// assert_eq!(element.proto(), Some("https"));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Forwarded {
+    elements: Vec<ForwardedElement>,
+}
+
+impl Forwarded {
+    /// Create a new instance of `Forwarded`.
+    pub fn new() -> Self {
+        Self { elements: vec![] }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// The `Forwarded` header is used if it's present. Otherwise the
+    /// `X-Forwarded-For`, `X-Forwarded-Host` and `X-Forwarded-Proto` headers
+    /// are combined, matching up their values from the last to the first.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// headers are malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = headers.as_ref();
+        let values = match headers.get(FORWARDED) {
+            Some(values) => values,
+            None => return Self::from_x_forwarded_headers(headers),
+        };
+
+        let mut elements = vec![];
+        for value in values {
+            for pairs in split_elements(value.as_str()) {
+                elements.push(ForwardedElement::from_pairs(&pairs)?);
+            }
+        }
+        Ok(Some(Self { elements }))
+    }
+
+    fn from_x_forwarded_headers(headers: &Headers) -> crate::Result<Option<Self>> {
+        let hops = x_forwarded_hops(headers);
+        if hops.is_empty() {
+            return Ok(None);
+        }
+
+        let mut elements = vec![];
+        for (forwarded_for, host, proto) in hops {
+            let mut element = ForwardedElement::new();
+            if let Some(node) = forwarded_for {
+                element.set_forwarded_for(Some(Node::from_x_forwarded(node)?));
+            }
+            // The setters panic on invalid values, so validate them here.
+            if let Some(host) = host {
+                if !host.is_ascii() {
+                    let msg = format!("Invalid forwarded host: {}", host);
+                    return Err(Error::from_str(StatusCode::BadRequest, msg));
+                }
+                element.host = Some(host.to_owned());
+            }
+            if let Some(proto) = proto {
+                if !is_token(proto) {
+                    let msg = format!("Invalid forwarded protocol: {}", proto);
+                    return Err(Error::from_str(StatusCode::BadRequest, msg));
+                }
+                element.proto = Some(proto.to_ascii_lowercase());
+            }
+            elements.push(element);
+        }
+        Ok(Some(Self { elements }))
+    }
+
+    /// Sets the `Forwarded` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(FORWARDED, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        FORWARDED
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::new();
+        for (n, element) in self.elements.iter().enumerate() {
+            match n {
+                0 => write!(output, "{}", element).unwrap(),
+                _ => write!(output, ", {}", element).unwrap(),
+            };
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Push an element, as a proxy does when it forwards a request.
+    pub fn push(&mut self, element: ForwardedElement) {
+        self.elements.push(element);
+    }
+
+    /// Returns `true` if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// An iterator visiting all elements, starting with the one closest to
+    /// the client.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.elements.iter(),
+        }
+    }
+}
+
+impl Default for Forwarded {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Split `Forwarded` header values into elements of key-value pairs.
+///
+/// Values are borrowed from the header, with any surrounding quotes removed.
+pub(crate) fn split_elements(value: &str) -> Vec<Vec<(&str, &str)>> {
+    split_quoted(value, ',')
+        .into_iter()
+        .map(|element| {
+            split_quoted(element, ';')
+                .into_iter()
+                .map(|pair| match pair.find('=') {
+                    Some(index) => {
+                        let value = pair[index + 1..].trim();
+                        let value = match value.strip_prefix('"') {
+                            Some(inner) => inner.strip_suffix('"').unwrap_or(value),
+                            None => value,
+                        };
+                        (pair[..index].trim(), value)
+                    }
+                    None => (pair, ""),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Combine the `X-Forwarded-For`, `X-Forwarded-Host` and `X-Forwarded-Proto`
+/// headers into a list of hops.
+///
+/// Proxies append to these headers independently of each other, so the lists
+/// are aligned on their last values.
+pub(crate) fn x_forwarded_hops(
+    headers: &Headers,
+) -> Vec<(Option<&str>, Option<&str>, Option<&str>)> {
+    let list = |name: &str| -> Vec<&str> {
+        headers
+            .get(name)
+            .into_iter()
+            .flat_map(|values| values.iter())
+            .flat_map(|value| value.as_str().split(','))
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty())
+            .collect()
+    };
+    let (fors, hosts, protos) = (
+        list("X-Forwarded-For"),
+        list("X-Forwarded-Host"),
+        list("X-Forwarded-Proto"),
+    );
+
+    let len = fors.len().max(hosts.len()).max(protos.len());
+    (0..len)
+        .map(|n| {
+            (
+                nth(&fors, len, n),
+                nth(&hosts, len, n),
+                nth(&protos, len, n),
+            )
+        })
+        .collect()
+}
+
+/// Get the nth value of a list which is aligned to the end of a longer list.
+fn nth<'a>(list: &[&'a str], len: usize, n: usize) -> Option<&'a str> {
+    let offset = len - list.len();
+    n.checked_sub(offset).map(|n| list[n])
+}
+
+impl IntoIterator for Forwarded {
+    type Item = ForwardedElement;
+    type IntoIter = std::vec::IntoIter<ForwardedElement>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Forwarded {
+    type Item = &'a ForwardedElement;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over entries in `Forwarded`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, ForwardedElement>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a ForwardedElement;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ToHeaderValues for Forwarded {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+/// A single element in the `Forwarded` header, describing one hop.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ForwardedElement {
+    by: Option<Node>,
+    forwarded_for: Option<Node>,
+    host: Option<String>,
+    proto: Option<String>,
+}
+
+impl ForwardedElement {
+    /// Create a new, empty element.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse an element from its key-value pairs. Unknown parameters are
+    /// skipped.
+    fn from_pairs(pairs: &[(&str, &str)]) -> crate::Result<Self> {
+        let mut element = Self::new();
+        for (n, (name, value)) in pairs.iter().enumerate() {
+            if pairs[..n]
+                .iter()
+                .any(|(other, _)| other.eq_ignore_ascii_case(name))
+            {
+                let msg = format!("Duplicate forwarded parameter: {}", name);
+                return Err(Error::from_str(StatusCode::BadRequest, msg));
+            }
+            if !is_token(name) {
+                let msg = format!("Invalid forwarded parameter: {}", name);
+                return Err(Error::from_str(StatusCode::BadRequest, msg));
+            }
+
+            let name = name.to_ascii_lowercase();
+            match name.as_str() {
+                "by" => element.by = Some(value.parse()?),
+                "for" => element.forwarded_for = Some(value.parse()?),
+                "host" => element.host = Some(unescape(value)),
+                "proto" if is_token(value) => element.proto = Some(value.to_ascii_lowercase()),
+                "proto" => {
+                    let msg = format!("Invalid forwarded protocol: {}", value);
+                    return Err(Error::from_str(StatusCode::BadRequest, msg));
+                }
+                _ => {}
+            }
+        }
+        Ok(element)
+    }
+
+    /// Get the interface where the request came in to the proxy.
+    pub fn by(&self) -> Option<&Node> {
+        self.by.as_ref()
+    }
+
+    /// Set the interface where the request came in to the proxy.
+    pub fn set_by(&mut self, by: Option<Node>) {
+        self.by = by;
+    }
+
+    /// Get the node making the request to the proxy.
+    pub fn forwarded_for(&self) -> Option<&Node> {
+        self.forwarded_for.as_ref()
+    }
+
+    /// Set the node making the request to the proxy.
+    pub fn set_forwarded_for(&mut self, forwarded_for: Option<Node>) {
+        self.forwarded_for = forwarded_for;
+    }
+
+    /// Get the `Host` header of the request received by the proxy.
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// Set the `Host` header of the request received by the proxy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the host isn't ASCII.
+    pub fn set_host(&mut self, host: Option<String>) {
+        if let Some(host) = &host {
+            assert!(host.is_ascii(), "Forwarded hosts should be ASCII");
+        }
+        self.host = host;
+    }
+
+    /// Get the protocol used to make the request to the proxy.
+    pub fn proto(&self) -> Option<&str> {
+        self.proto.as_deref()
+    }
+
+    /// Set the protocol used to make the request to the proxy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the protocol isn't a token.
+    pub fn set_proto(&mut self, proto: Option<String>) {
+        if let Some(proto) = &proto {
+            assert!(is_token(proto), "Forwarded protocols should be tokens");
+        }
+        self.proto = proto;
+    }
+}
+
+/// Unescape a value which had its surrounding quotes removed.
+fn unescape(value: &str) -> String {
+    match value.contains('\\') {
+        true => unquote(&format!("\"{}\"", value)).unwrap_or_else(|| value.to_owned()),
+        false => value.to_owned(),
+    }
+}
+
+impl Display for ForwardedElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let by = self.by.as_ref().map(|by| ("by", by.to_string()));
+        let forwarded_for = self
+            .forwarded_for
+            .as_ref()
+            .map(|node| ("for", node.to_string()));
+        let host = self.host.as_ref().map(|host| ("host", host.clone()));
+        let proto = self.proto.as_ref().map(|proto| ("proto", proto.clone()));
+
+        let pairs = by.into_iter().chain(forwarded_for).chain(host).chain(proto);
+        for (n, (name, value)) in pairs.enumerate() {
+            if n > 0 {
+                f.write_str(";")?;
+            }
+            // IPv6 addresses and ports aren't tokens, so they need quoting.
+            match is_token(&value) {
+                true => write!(f, "{}={}", name, value)?,
+                false => write!(f, "{}={}", name, quote(&value))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+    use crate::proxies::{NodeName, NodePort};
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut first = ForwardedElement::new();
+        first.set_forwarded_for(Some("_gazonk".parse()?));
+        first.set_host(Some("example.com".into()));
+        let mut second = ForwardedElement::new();
+        second.set_by(Some("203.0.113.43".parse()?));
+        second.set_forwarded_for(Some("[2001:db8:cafe::17]:4711".parse()?));
+        second.set_proto(Some("https".into()));
+
+        let mut forwarded = Forwarded::new();
+        forwarded.push(first.clone());
+        forwarded.push(second.clone());
+
+        let mut headers = Headers::new();
+        forwarded.apply(&mut headers);
+        assert_eq!(
+            headers[FORWARDED],
+            r#"for=_gazonk;host=example.com, by=203.0.113.43;for="[2001:db8:cafe::17]:4711";proto=https"#
+        );
+
+        let forwarded = Forwarded::from_headers(headers)?.unwrap();
+        let elements: Vec<_> = forwarded.iter().cloned().collect();
+        assert_eq!(elements, vec![first, second]);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_rfc_examples() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append(FORWARDED, r#"for="_gazonk""#);
+        headers.append(FORWARDED, r#"For="[2001:db8:cafe::17]:4711""#);
+        headers.append(
+            FORWARDED,
+            "for=192.0.2.60;proto=http;by=203.0.113.43, for=unknown;ext=1",
+        );
+
+        let forwarded = Forwarded::from_headers(headers)?.unwrap();
+        let elements: Vec<_> = forwarded.iter().collect();
+        assert_eq!(elements.len(), 4);
+        assert_eq!(
+            elements[0].forwarded_for().unwrap().name(),
+            &NodeName::Obfuscated("_gazonk".into())
+        );
+        assert_eq!(
+            elements[1].forwarded_for().unwrap().port(),
+            Some(&NodePort::Port(4711))
+        );
+        assert_eq!(elements[2].proto(), Some("http"));
+        assert_eq!(
+            elements[2].by().unwrap().ip(),
+            Some("203.0.113.43".parse()?)
+        );
+        assert_eq!(
+            elements[3].forwarded_for().unwrap().name(),
+            &NodeName::Unknown
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn x_forwarded_headers() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert("X-Forwarded-For", "192.0.2.60, 2001:db8::1");
+        headers.insert("X-Forwarded-Host", "example.com");
+        headers.insert("X-Forwarded-Proto", "https");
+
+        let forwarded = Forwarded::from_headers(headers)?.unwrap();
+        assert_eq!(
+            forwarded.value(),
+            r#"for=192.0.2.60, for="[2001:db8::1]";host=example.com;proto=https"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = [
+            "for=example.com",
+            "for=192.0.2.60;for=192.0.2.61",
+            "for=2001:db8::1",
+            r#"proto="a b""#,
+        ];
+        for value in &values {
+            let mut headers = Headers::new();
+            headers.insert(FORWARDED, *value);
+            let err = Forwarded::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+
+        for value in &["ht@tp", "https, x y"] {
+            let mut headers = Headers::new();
+            headers.insert("X-Forwarded-Proto", *value);
+            let err = Forwarded::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Headers set by proxies, and deciding which proxies to trust.
+//!
+//! Proxies report the client address, and the host and protocol of the
+//! original request, in the `Forwarded` header or the older
+//! `X-Forwarded-For`, `X-Forwarded-Host` and `X-Forwarded-Proto` headers.
+//! Clients can set these headers too, so `Request::remote` and
+//! `Request::host` only consult the values appended by trusted proxies once a
+//! `ProxyTrust` is set on the request.
+//!
+//! # Specifications
+//!
+//! - [RFC 7239: Forwarded HTTP Extension](https://tools.ietf.org/html/rfc7239)
+
+mod forwarded;
+mod node;
+mod proxy_trust;
+
+pub use forwarded::{Forwarded, ForwardedElement, Iter};
+pub use node::{Node, NodeName, NodePort};
+pub use proxy_trust::{Cidr, ProxyTrust};
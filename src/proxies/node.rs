@@ -0,0 +1,245 @@
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+
+/// A node identifier in the `for` and `by` parameters of the `Forwarded`
+/// header.
+///
+/// # Specifications
+///
+/// - [RFC 7239, section 6: Node Identifiers](https://tools.ietf.org/html/rfc7239#section-6)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::proxies::{Node, NodeName, NodePort};
+///
+/// let node: Node = "[2001:db8:cafe::17]:4711".parse()?;
+/// assert_eq!(node.ip(), Some("2001:db8:cafe::17".parse()?));
+/// assert_eq!(node.port(), Some(&NodePort::Port(4711)));
+///
+/// let node: Node = "_hidden:_port".parse()?;
+/// assert_eq!(node.name(), &NodeName::Obfuscated("_hidden".into()));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Node {
+    name: NodeName,
+    port: Option<NodePort>,
+}
+
+/// The name of a node.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum NodeName {
+    /// The IP address of the node.
+    Ip(IpAddr),
+    /// An obfuscated identifier, which starts with an underscore.
+    Obfuscated(String),
+    /// The node is unknown, but the proxy wants to indicate that the request
+    /// was forwarded.
+    Unknown,
+}
+
+/// The port of a node.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum NodePort {
+    /// A port number.
+    Port(u16),
+    /// An obfuscated port, which starts with an underscore.
+    Obfuscated(String),
+}
+
+impl Node {
+    /// Create a new instance of `Node`.
+    pub fn new(name: NodeName, port: Option<NodePort>) -> Self {
+        Self { name, port }
+    }
+
+    /// Parse a node from an `X-Forwarded-For` header, which may also contain
+    /// IPv6 addresses without brackets.
+    pub(crate) fn from_x_forwarded(s: &str) -> crate::Result<Self> {
+        match s.trim().parse::<IpAddr>() {
+            Ok(ip) => Ok(ip.into()),
+            Err(_) => s.parse(),
+        }
+    }
+
+    /// Get the name of the node.
+    pub fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    /// Get the port of the node.
+    pub fn port(&self) -> Option<&NodePort> {
+        self.port.as_ref()
+    }
+
+    /// Get the IP address of the node, if it's known and not obfuscated.
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self.name {
+            NodeName::Ip(ip) => Some(ip),
+            _ => None,
+        }
+    }
+}
+
+/// Returns `true` if the string is a valid obfuscated identifier.
+fn is_obfuscated(s: &str) -> bool {
+    s.len() > 1
+        && s.starts_with('_')
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"._-".contains(&b))
+}
+
+impl FromStr for NodeName {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("unknown") {
+            Ok(Self::Unknown)
+        } else if is_obfuscated(s) {
+            Ok(Self::Obfuscated(s.to_owned()))
+        } else if let Some(ip) = s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            match ip.parse::<Ipv6Addr>() {
+                Ok(ip) => Ok(Self::Ip(ip.into())),
+                Err(_) => Err(invalid_node(s)),
+            }
+        } else {
+            match s.parse::<Ipv4Addr>() {
+                Ok(ip) => Ok(Self::Ip(ip.into())),
+                Err(_) => Err(invalid_node(s)),
+            }
+        }
+    }
+}
+
+impl FromStr for NodePort {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if is_obfuscated(s) {
+            return Ok(Self::Obfuscated(s.to_owned()));
+        }
+        match s.bytes().all(|b| b.is_ascii_digit()) {
+            true => s.parse().map(Self::Port).map_err(|_| invalid_node(s)),
+            false => Err(invalid_node(s)),
+        }
+    }
+}
+
+impl FromStr for Node {
+    type Err = Error;
+
+    /// Parse a node identifier, such as `192.0.2.43:47011` or `"[2001:db8::17]"`.
+    /// Surrounding quotes are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(s) => s,
+            None => s,
+        };
+
+        // IPv6 addresses contain colons, so look for the port after them.
+        let start = match s.starts_with('[') {
+            true => s.find(']').ok_or_else(|| invalid_node(s))?,
+            false => 0,
+        };
+        let (name, port) = match s[start..].find(':') {
+            Some(index) => (&s[..start + index], Some(s[start + index + 1..].parse()?)),
+            None => (s, None),
+        };
+        Ok(Self {
+            name: name.parse()?,
+            port,
+        })
+    }
+}
+
+fn invalid_node(s: &str) -> Error {
+    let msg = format!("Invalid node identifier: {}", s);
+    Error::from_str(StatusCode::BadRequest, msg)
+}
+
+impl Display for NodeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ip(IpAddr::V4(ip)) => write!(f, "{}", ip),
+            Self::Ip(IpAddr::V6(ip)) => write!(f, "[{}]", ip),
+            Self::Obfuscated(name) => f.write_str(name),
+            Self::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+impl Display for NodePort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Port(port) => write!(f, "{}", port),
+            Self::Obfuscated(port) => f.write_str(port),
+        }
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.port {
+            Some(port) => write!(f, "{}:{}", self.name, port),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl From<IpAddr> for Node {
+    fn from(ip: IpAddr) -> Self {
+        Self::new(NodeName::Ip(ip), None)
+    }
+}
+
+impl From<SocketAddr> for Node {
+    fn from(addr: SocketAddr) -> Self {
+        Self::new(NodeName::Ip(addr.ip()), Some(NodePort::Port(addr.port())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let node: Node = "192.0.2.43:47011".parse()?;
+        assert_eq!(node, Node::from("192.0.2.43:47011".parse::<SocketAddr>()?));
+
+        let node: Node = r#""[2001:db8:cafe::17]""#.parse()?;
+        assert_eq!(node.ip(), Some("2001:db8:cafe::17".parse()?));
+        assert_eq!(node.port(), None);
+
+        let node: Node = "unknown:_abc".parse()?;
+        assert_eq!(node.name(), &NodeName::Unknown);
+        assert_eq!(node.port(), Some(&NodePort::Obfuscated("_abc".into())));
+
+        let node = Node::from_x_forwarded("2001:db8::1")?;
+        assert_eq!(node.to_string(), "[2001:db8::1]");
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = [
+            "example.com",
+            "2001:db8::1",
+            "[2001:db8::1",
+            "1.2.3.4:99999",
+            "_",
+        ];
+        for value in &values {
+            let err = value.parse::<Node>().unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
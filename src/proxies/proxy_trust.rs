@@ -0,0 +1,339 @@
+use crate::headers::{Headers, FORWARDED};
+use crate::proxies::forwarded::{split_elements, x_forwarded_hops};
+use crate::proxies::{Forwarded, Node};
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+
+/// Which proxies are trusted to report the client address and host of a
+/// request.
+///
+/// Clients can send forwarding headers themselves, so only the values
+/// appended by trusted proxies can be relied upon. The client is the last
+/// hop which was reported by a trusted proxy.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::{Method, Request};
+/// use http_types::proxies::ProxyTrust;
+///
+/// let mut req = Request::new(Method::Get, "http://example.com");
+/// req.set_peer_addr(Some("10.0.0.2:4000"));
+/// req.insert_header("X-Forwarded-For", "203.0.113.7, 192.0.2.60, 10.0.0.1");
+///
+/// req.set_proxy_trust(Some(ProxyTrust::cidrs(&["10.0.0.0/8"])?));
+/// assert_eq!(req.remote(), Some("192.0.2.60"));
+///
+/// req.set_proxy_trust(Some(ProxyTrust::Hops(1)));
+/// assert_eq!(req.remote(), Some("10.0.0.1"));
+/// #
+/// # Ok(()) }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ProxyTrust {
+    /// Trust proxies with an address in one of the ranges.
+    Cidrs(Vec<Cidr>),
+    /// Trust a fixed number of proxies in front of the server.
+    Hops(usize),
+}
+
+/// A hop resolved from the forwarding headers, borrowed from the headers.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Hop<'a> {
+    pub(crate) forwarded_for: Option<&'a str>,
+    pub(crate) host: Option<&'a str>,
+}
+
+impl ProxyTrust {
+    /// Trust proxies with an address in one of the ranges.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if any of the ranges is invalid.
+    pub fn cidrs(ranges: &[&str]) -> crate::Result<Self> {
+        let ranges = ranges
+            .iter()
+            .map(|range| range.parse())
+            .collect::<crate::Result<_>>()?;
+        Ok(Self::Cidrs(ranges))
+    }
+
+    /// Returns `true` if a proxy with the address is trusted.
+    ///
+    /// Proxies are never trusted by their address when trusting a number of
+    /// hops.
+    pub fn is_trusted(&self, addr: IpAddr) -> bool {
+        match self {
+            Self::Cidrs(ranges) => ranges.iter().any(|range| range.contains(addr)),
+            Self::Hops(_) => false,
+        }
+    }
+
+    /// Find the hop describing the client, given the peer address of the
+    /// transport.
+    ///
+    /// Returns `None` if the peer isn't a trusted proxy, or if the forwarding
+    /// headers are malformed.
+    pub(crate) fn resolve<'a>(
+        &self,
+        peer_addr: Option<&str>,
+        headers: &'a Headers,
+    ) -> Option<Hop<'a>> {
+        let hops = hops(headers)?;
+        match self {
+            Self::Hops(0) => None,
+            Self::Hops(count) => hops
+                .get(hops.len().saturating_sub(*count))
+                .map(|(hop, _)| *hop),
+            Self::Cidrs(_) => {
+                let peer = peer_addr.and_then(parse_addr)?;
+                if !self.is_trusted(peer) {
+                    return None;
+                }
+                let client = hops
+                    .iter()
+                    .rev()
+                    .find(|(_, ip)| !matches!(ip, Some(ip) if self.is_trusted(*ip)));
+                client.or_else(|| hops.first()).map(|(hop, _)| *hop)
+            }
+        }
+    }
+}
+
+/// Collect the hops from the forwarding headers, along with the IP address
+/// of each client.
+fn hops(headers: &Headers) -> Option<Vec<(Hop<'_>, Option<IpAddr>)>> {
+    let values = match headers.get(FORWARDED) {
+        Some(values) => values,
+        None => {
+            let hops = x_forwarded_hops(headers)
+                .into_iter()
+                .map(|(node, host, _)| {
+                    let ip = node.and_then(|node| Node::from_x_forwarded(node).ok()?.ip());
+                    let hop = Hop {
+                        forwarded_for: node,
+                        host,
+                    };
+                    (hop, ip)
+                });
+            return Some(hops.collect());
+        }
+    };
+
+    // Only trust the borrowed values if the header as a whole is valid.
+    Forwarded::from_headers(headers).ok()?;
+
+    let mut hops = vec![];
+    for value in values {
+        for pairs in split_elements(value.as_str()) {
+            let mut hop = Hop::default();
+            for (name, value) in pairs {
+                if name.eq_ignore_ascii_case("for") {
+                    hop.forwarded_for = Some(value);
+                } else if name.eq_ignore_ascii_case("host") {
+                    hop.host = Some(value);
+                }
+            }
+            let ip = hop
+                .forwarded_for
+                .and_then(|node| node.parse::<Node>().ok()?.ip());
+            hops.push((hop, ip));
+        }
+    }
+    Some(hops)
+}
+
+/// Parse a peer address, which may or may not include a port.
+fn parse_addr(addr: &str) -> Option<IpAddr> {
+    match addr.parse::<SocketAddr>() {
+        Ok(addr) => Some(addr.ip()),
+        Err(_) => addr.parse().ok(),
+    }
+}
+
+/// A range of IP addresses, in CIDR notation.
+///
+/// IPv4-mapped IPv6 addresses are matched against IPv4 ranges.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::proxies::Cidr;
+///
+/// let range: Cidr = "192.168.0.0/16".parse()?;
+/// assert!(range.contains("192.168.1.1".parse()?));
+/// assert!(range.contains("::ffff:192.168.1.1".parse()?));
+/// assert!(!range.contains("10.0.0.1".parse()?));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl Cidr {
+    /// Create a new range from an address and a prefix length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix length is larger than the address.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Self {
+        let max = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        assert!(
+            prefix_len <= max,
+            "The prefix length should fit the address"
+        );
+        Self { addr, prefix_len }
+    }
+
+    /// Get the address.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Get the prefix length.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns `true` if the address is in the range.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        let addr = match addr {
+            IpAddr::V6(v6) => match v6.octets() {
+                [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, a, b, c, d] => {
+                    IpAddr::V4(Ipv4Addr::new(a, b, c, d))
+                }
+                _ => addr,
+            },
+            addr => addr,
+        };
+        match (self.addr, addr) {
+            (IpAddr::V4(range), IpAddr::V4(addr)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u32::from(range) & mask == u32::from(addr) & mask
+            }
+            (IpAddr::V6(range), IpAddr::V6(addr)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u128::from(range) & mask == u128::from(addr) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = Error;
+
+    /// Parse a range such as `10.0.0.0/8`. A single address is parsed as a
+    /// range containing only that address.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            let msg = format!("Invalid CIDR range: {}", s);
+            Error::from_str(StatusCode::BadRequest, msg)
+        };
+        let (addr, prefix_len) = match s.trim().find('/') {
+            Some(index) => (&s.trim()[..index], Some(&s.trim()[index + 1..])),
+            None => (s.trim(), None),
+        };
+        let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+        let max = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len.parse().map_err(|_| invalid())?,
+            None => max,
+        };
+        if prefix_len > max {
+            return Err(invalid());
+        }
+        Ok(Self::new(addr, prefix_len))
+    }
+}
+
+impl Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cidr() -> crate::Result<()> {
+        let range: Cidr = "2001:db8::/32".parse()?;
+        assert!(range.contains("2001:db8:cafe::17".parse()?));
+        assert!(!range.contains("2001:db9::1".parse()?));
+        assert_eq!(range.to_string(), "2001:db8::/32");
+
+        let any: Cidr = "0.0.0.0/0".parse()?;
+        assert!(any.contains("203.0.113.7".parse()?));
+        assert!(!any.contains("2001:db8::1".parse()?));
+
+        let single: Cidr = "127.0.0.1".parse()?;
+        assert_eq!(single.prefix_len(), 32);
+
+        for value in &["10.0.0.0/33", "10.0.0/8", "::1/x"] {
+            let err = value.parse::<Cidr>().unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_forwarded() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.append(FORWARDED, "for=203.0.113.7;host=evil.com");
+        headers.append(
+            FORWARDED,
+            r#"for="[2001:db8::17]:4711";host=example.com, for=10.0.0.1"#,
+        );
+
+        let trust = ProxyTrust::cidrs(&["10.0.0.0/8"])?;
+        let hop = trust.resolve(Some("10.0.0.2:80"), &headers).unwrap();
+        assert_eq!(hop.forwarded_for, Some("[2001:db8::17]:4711"));
+        assert_eq!(hop.host, Some("example.com"));
+        assert!(trust.resolve(Some("192.0.2.1:80"), &headers).is_none());
+        assert!(trust.resolve(None, &headers).is_none());
+
+        let hop = ProxyTrust::Hops(3).resolve(None, &headers).unwrap();
+        assert_eq!(hop.forwarded_for, Some("203.0.113.7"));
+        let hop = ProxyTrust::Hops(5).resolve(None, &headers).unwrap();
+        assert_eq!(hop.forwarded_for, Some("203.0.113.7"));
+        assert!(ProxyTrust::Hops(0).resolve(None, &headers).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_obfuscated_and_malformed() -> crate::Result<()> {
+        let trust = ProxyTrust::cidrs(&["10.0.0.0/8"])?;
+
+        let mut headers = Headers::new();
+        headers.insert(FORWARDED, "for=_hidden, for=10.0.0.1");
+        let hop = trust.resolve(Some("10.0.0.2"), &headers).unwrap();
+        assert_eq!(hop.forwarded_for, Some("_hidden"));
+
+        headers.insert(FORWARDED, "for=10.0.0.3;for=10.0.0.4");
+        assert!(trust.resolve(Some("10.0.0.2"), &headers).is_none());
+        Ok(())
+    }
+}
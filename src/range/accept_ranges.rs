@@ -0,0 +1,90 @@
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, ACCEPT_RANGES};
+
+use std::option;
+
+/// Advertise whether the server supports range requests.
+///
+/// # Specifications
+///
+/// - [RFC 7233, section 2.3: Accept-Ranges](https://tools.ietf.org/html/rfc7233#section-2.3)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::range::AcceptRanges;
+/// use http_types::Response;
+///
+/// let mut res = Response::new(200);
+/// AcceptRanges::bytes().apply(&mut res);
+/// assert_eq!(res["Accept-Ranges"], "bytes");
+///
+/// let accept_ranges = AcceptRanges::from_headers(res)?.unwrap();
+/// assert!(accept_ranges.accepts_bytes());
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AcceptRanges {
+    bytes: bool,
+}
+
+impl AcceptRanges {
+    /// Byte range requests are supported.
+    pub fn bytes() -> Self {
+        Self { bytes: true }
+    }
+
+    /// Range requests are not supported.
+    pub fn none() -> Self {
+        Self { bytes: false }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// Range units other than `bytes` are ignored.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(ACCEPT_RANGES) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let bytes = headers
+            .iter()
+            .flat_map(|value| value.as_str().split(','))
+            .any(|unit| unit.trim().eq_ignore_ascii_case("bytes"));
+        Ok(Some(Self { bytes }))
+    }
+
+    /// Returns `true` if byte range requests are supported.
+    pub fn accepts_bytes(&self) -> bool {
+        self.bytes
+    }
+
+    /// Sets the `Accept-Ranges` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(ACCEPT_RANGES, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        ACCEPT_RANGES
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = if self.bytes { "bytes" } else { "none" };
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+}
+
+impl ToHeaderValues for AcceptRanges {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::str::FromStr;
+
+/// A single byte range, as requested in the `Range` header.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::range::ByteRange;
+///
+/// let range: ByteRange = "-500".parse()?;
+/// assert_eq!(range, ByteRange::Last(500));
+/// assert_eq!(range.resolve(10_000), Some(9500..10_000));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// The bytes from the first position up to and including the last
+    /// position, such as `0-499`.
+    FromTo(u64, u64),
+    /// All bytes starting at a position, such as `9500-`.
+    From(u64),
+    /// The last number of bytes, such as `-500`.
+    Last(u64),
+}
+
+impl ByteRange {
+    /// Resolve the range against a representation of `len` bytes.
+    ///
+    /// Returns `None` if the range is unsatisfiable. Ranges extending past
+    /// the end of the representation are truncated.
+    pub fn resolve(&self, len: u64) -> Option<Range<u64>> {
+        let range = match *self {
+            ByteRange::FromTo(first, last) => first..last.saturating_add(1).min(len),
+            ByteRange::From(first) => first..len,
+            ByteRange::Last(suffix) => len.saturating_sub(suffix)..len,
+        };
+        if range.start < range.end {
+            Some(range)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for ByteRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let mut parts = s.splitn(2, '-');
+        let first = parts.next().unwrap_or("").trim();
+        let last = match parts.next() {
+            Some(last) => last.trim(),
+            None => return Err(bad_request(s)),
+        };
+
+        let parse = |n: &str| n.parse::<u64>().map_err(|_| bad_request(s));
+        let range = match (first, last) {
+            ("", "") => return Err(bad_request(s)),
+            ("", last) => ByteRange::Last(parse(last)?),
+            (first, "") => ByteRange::From(parse(first)?),
+            (first, last) => {
+                let (first, last) = (parse(first)?, parse(last)?);
+                if last < first {
+                    return Err(bad_request(s));
+                }
+                ByteRange::FromTo(first, last)
+            }
+        };
+        Ok(range)
+    }
+}
+
+impl Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ByteRange::FromTo(first, last) => write!(f, "{}-{}", first, last),
+            ByteRange::From(first) => write!(f, "{}-", first),
+            ByteRange::Last(suffix) => write!(f, "-{}", suffix),
+        }
+    }
+}
+
+fn bad_request(range: &str) -> Error {
+    let msg = format!("Invalid byte range: {}", range);
+    Error::from_str(StatusCode::BadRequest, msg)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        assert_eq!("0-499".parse::<ByteRange>()?, ByteRange::FromTo(0, 499));
+        assert_eq!("9500-".parse::<ByteRange>()?, ByteRange::From(9500));
+        assert_eq!("-500".parse::<ByteRange>()?, ByteRange::Last(500));
+        for s in &["", "-", "500", "a-b", "5-1", "--5"] {
+            let err = s.parse::<ByteRange>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn resolve() {
+        assert_eq!(ByteRange::FromTo(0, 499).resolve(10_000), Some(0..500));
+        assert_eq!(
+            ByteRange::FromTo(500, 20_000).resolve(10_000),
+            Some(500..10_000)
+        );
+        assert_eq!(ByteRange::From(9500).resolve(10_000), Some(9500..10_000));
+        assert_eq!(ByteRange::Last(20_000).resolve(10_000), Some(0..10_000));
+        assert_eq!(ByteRange::FromTo(10_000, 10_001).resolve(10_000), None);
+        assert_eq!(ByteRange::From(10_000).resolve(10_000), None);
+        assert_eq!(ByteRange::Last(0).resolve(10_000), None);
+        assert_eq!(ByteRange::Last(10).resolve(0), None);
+    }
+}
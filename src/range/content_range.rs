@@ -0,0 +1,204 @@
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, CONTENT_RANGE};
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::ops::RangeInclusive;
+use std::option;
+use std::str::FromStr;
+
+/// Indicate where in a full representation a partial body belongs.
+///
+/// # Specifications
+///
+/// - [RFC 7233, section 4.2: Content-Range](https://tools.ietf.org/html/rfc7233#section-4.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::range::ContentRange;
+/// use http_types::Response;
+///
+/// let content_range = ContentRange::new(0..=499, Some(1234));
+///
+/// let mut res = Response::new(206);
+/// content_range.apply(&mut res);
+/// assert_eq!(res["Content-Range"], "bytes 0-499/1234");
+///
+/// let content_range = ContentRange::from_headers(res)?.unwrap();
+/// assert_eq!(content_range.range(), Some(0..=499));
+/// assert_eq!(content_range.complete_len(), Some(1234));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentRange {
+    range: Option<(u64, u64)>,
+    complete_len: Option<u64>,
+}
+
+impl ContentRange {
+    /// Create a new instance for a range of bytes, and the length of the
+    /// complete representation if known.
+    pub fn new(range: RangeInclusive<u64>, complete_len: Option<u64>) -> Self {
+        Self {
+            range: Some((*range.start(), *range.end())),
+            complete_len,
+        }
+    }
+
+    /// Create a new instance for a `416 Range Not Satisfiable` response.
+    pub fn unsatisfied(complete_len: u64) -> Self {
+        Self {
+            range: None,
+            complete_len: Some(complete_len),
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(CONTENT_RANGE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If we successfully parsed the header then there's always at least one
+        // entry. We want the last entry.
+        let value = headers.iter().last().unwrap().as_str();
+        Ok(Some(value.parse()?))
+    }
+
+    /// Sets the `Content-Range` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(CONTENT_RANGE, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CONTENT_RANGE
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Get the range of bytes enclosed, or `None` if the range was
+    /// unsatisfiable.
+    pub fn range(&self) -> Option<RangeInclusive<u64>> {
+        self.range.map(|(first, last)| first..=last)
+    }
+
+    /// Get the length of the complete representation, if known.
+    pub fn complete_len(&self) -> Option<u64> {
+        self.complete_len
+    }
+}
+
+impl FromStr for ContentRange {
+    type Err = Error;
+
+    /// Parse a value such as `bytes 0-499/1234`, `bytes 0-499/*` or
+    /// `bytes */1234`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad_request = || {
+            let msg = format!("Invalid Content-Range: {}", s);
+            Error::from_str(StatusCode::BadRequest, msg)
+        };
+        let parse = |n: &str| n.trim().parse::<u64>().map_err(|_| bad_request());
+
+        let s = s.trim();
+        let rest = match s.get(..6) {
+            Some(unit) if unit.eq_ignore_ascii_case("bytes ") => &s[6..],
+            _ => return Err(bad_request()),
+        };
+        let mut parts = rest.splitn(2, '/');
+        let range = parts.next().unwrap_or("").trim();
+        let complete_len = parts.next().ok_or_else(bad_request)?.trim();
+
+        let complete_len = match complete_len {
+            "*" => None,
+            len => Some(parse(len)?),
+        };
+        let range = match range {
+            "*" if complete_len.is_some() => None,
+            range => {
+                let mut bounds = range.splitn(2, '-');
+                let first = parse(bounds.next().unwrap_or(""))?;
+                let last = parse(bounds.next().ok_or_else(bad_request)?)?;
+                let valid = first <= last && !matches!(complete_len, Some(len) if last >= len);
+                if !valid {
+                    return Err(bad_request());
+                }
+                Some((first, last))
+            }
+        };
+        Ok(Self {
+            range,
+            complete_len,
+        })
+    }
+}
+
+impl Display for ContentRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.range {
+            Some((first, last)) => write!(f, "bytes {}-{}/", first, last)?,
+            None => write!(f, "bytes */")?,
+        }
+        match self.complete_len {
+            Some(len) => write!(f, "{}", len),
+            None => write!(f, "*"),
+        }
+    }
+}
+
+impl ToHeaderValues for ContentRange {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let range: ContentRange = "bytes 21010-47021/47022".parse()?;
+        assert_eq!(range, ContentRange::new(21010..=47021, Some(47022)));
+
+        let range: ContentRange = "bytes 0-499/*".parse()?;
+        assert_eq!(range, ContentRange::new(0..=499, None));
+
+        let range: ContentRange = "bytes */47022".parse()?;
+        assert_eq!(range, ContentRange::unsatisfied(47022));
+        assert_eq!(range.to_string(), "bytes */47022");
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for s in &[
+            "bytes */*",
+            "bytes 5-1/10",
+            "bytes 0-10/10",
+            "items 0-1/2",
+            "bytes 0-1",
+        ] {
+            let err = s.parse::<ContentRange>().unwrap_err();
+            assert_eq!(err.status(), 400, "{}", s);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::conditional::ETag;
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, IF_RANGE};
+use crate::HttpDate;
+
+use std::option;
+use std::time::SystemTime;
+
+/// Only honor the `Range` header if the representation is unchanged.
+///
+/// # Specifications
+///
+/// - [RFC 7233, section 3.2: If-Range](https://tools.ietf.org/html/rfc7233#section-3.2)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::conditional::ETag;
+/// use http_types::range::IfRange;
+/// use http_types::{Method, Request, Url};
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// IfRange::ETag(ETag::new("xyzzy".to_owned())).apply(&mut req);
+///
+/// let if_range = IfRange::from_headers(&req)?.unwrap();
+/// assert!(if_range.matches(Some(&ETag::new("xyzzy".to_owned())), None));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfRange {
+    /// Only honor the range if the representation has this ETag.
+    ETag(ETag),
+    /// Only honor the range if the representation was last modified at this
+    /// date.
+    Date(HttpDate),
+}
+
+impl IfRange {
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// value is neither an ETag nor an HTTP-date.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(IF_RANGE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If we successfully parsed the header then there's always at least one
+        // entry. We want the last entry.
+        let value = headers.iter().last().unwrap().as_str().trim();
+        let if_range = match value.starts_with('"') || value.starts_with("W/") {
+            true => IfRange::ETag(value.parse()?),
+            false => IfRange::Date(value.parse()?),
+        };
+        Ok(Some(if_range))
+    }
+
+    /// Returns `true` if the validator matches the current representation.
+    ///
+    /// ETags are compared with the strong comparison function, and dates must
+    /// be equal to the last modification date.
+    pub fn matches(&self, etag: Option<&ETag>, last_modified: Option<SystemTime>) -> bool {
+        match (self, etag, last_modified) {
+            (IfRange::ETag(expected), Some(etag), _) => expected.strong_eq(etag),
+            (IfRange::Date(date), _, Some(modified)) => *date == HttpDate::from(modified),
+            _ => false,
+        }
+    }
+
+    /// Sets the `If-Range` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(IF_RANGE, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        IF_RANGE
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        match self {
+            IfRange::ETag(etag) => etag.value(),
+            IfRange::Date(date) => (*date).into(),
+        }
+    }
+}
+
+impl ToHeaderValues for IfRange {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn date() -> crate::Result<()> {
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(784111777);
+        let mut headers = Headers::new();
+        headers.insert(IF_RANGE, "Sun, 06 Nov 1994 08:49:37 GMT");
+
+        let if_range = IfRange::from_headers(headers)?.unwrap();
+        assert!(if_range.matches(None, Some(modified)));
+        assert!(!if_range.matches(None, Some(modified + Duration::from_secs(1))));
+        assert!(!if_range.matches(None, None));
+        Ok(())
+    }
+
+    #[test]
+    fn weak_etags_never_match() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(IF_RANGE, r#"W/"xyzzy""#);
+
+        let if_range = IfRange::from_headers(headers)?.unwrap();
+        assert!(!if_range.matches(Some(&ETag::new_weak("xyzzy".into())), None));
+        Ok(())
+    }
+}
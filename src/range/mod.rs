@@ -0,0 +1,23 @@
+//! HTTP range requests.
+//!
+//! Range requests allow clients to request only part of a representation,
+//! which is useful for resuming interrupted downloads and for seeking in
+//! media.
+//!
+//! # Specifications
+//!
+//! - [RFC 7233: HTTP/1.1: Range Requests](https://tools.ietf.org/html/rfc7233)
+
+mod accept_ranges;
+mod byte_range;
+mod content_range;
+mod if_range;
+mod range_header;
+mod respond;
+
+pub use accept_ranges::AcceptRanges;
+pub use byte_range::ByteRange;
+pub use content_range::ContentRange;
+pub use if_range::IfRange;
+pub use range_header::Range;
+pub use respond::respond;
@@ -0,0 +1,174 @@
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, RANGE};
+use crate::range::ByteRange;
+use crate::{Error, StatusCode};
+
+use std::fmt::Write;
+use std::option;
+use std::slice;
+
+/// Request only part of a representation.
+///
+/// Only the `bytes` range unit is supported.
+///
+/// # Specifications
+///
+/// - [RFC 7233, section 3.1: Range](https://tools.ietf.org/html/rfc7233#section-3.1)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::range::{ByteRange, Range};
+/// use http_types::{Method, Request, Url};
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// req.insert_header("Range", "bytes=0-499, -500");
+///
+/// let range = Range::from_headers(&req)?.unwrap();
+/// let mut ranges = range.iter();
+/// assert_eq!(ranges.next(), Some(&ByteRange::FromTo(0, 499)));
+/// assert_eq!(ranges.next(), Some(&ByteRange::Last(500)));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Range {
+    ranges: Vec<ByteRange>,
+}
+
+impl Range {
+    /// Create a new instance of `Range`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed, or uses a range unit other than `bytes`.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(RANGE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If we successfully parsed the header then there's always at least one
+        // entry. We want the last entry.
+        let value = headers.iter().last().unwrap().as_str().trim();
+        let mut parts = value.splitn(2, '=');
+        let unit = parts.next().unwrap_or("").trim();
+        let specs = match parts.next() {
+            Some(specs) if unit.eq_ignore_ascii_case("bytes") => specs,
+            _ => {
+                let msg = format!("Unsupported range: {}", value);
+                return Err(Error::from_str(StatusCode::BadRequest, msg));
+            }
+        };
+
+        let mut ranges = vec![];
+        for spec in specs.split(',') {
+            if !spec.trim().is_empty() {
+                ranges.push(spec.parse()?);
+            }
+        }
+        if ranges.is_empty() {
+            let msg = format!("Range should contain at least one byte range: {}", value);
+            return Err(Error::from_str(StatusCode::BadRequest, msg));
+        }
+        Ok(Some(Self { ranges }))
+    }
+
+    /// Sets the `Range` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(RANGE, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        RANGE
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::from("bytes=");
+        for (n, range) in self.ranges.iter().enumerate() {
+            match n {
+                0 => write!(output, "{}", range).unwrap(),
+                _ => write!(output, ", {}", range).unwrap(),
+            };
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Push a byte range into the list of ranges.
+    pub fn push(&mut self, range: ByteRange) {
+        self.ranges.push(range);
+    }
+
+    /// An iterator visiting all byte ranges.
+    pub fn iter(&self) -> slice::Iter<'_, ByteRange> {
+        self.ranges.iter()
+    }
+}
+
+impl IntoIterator for Range {
+    type Item = ByteRange;
+    type IntoIter = std::vec::IntoIter<ByteRange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Range {
+    type Item = &'a ByteRange;
+    type IntoIter = slice::Iter<'a, ByteRange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl ToHeaderValues for Range {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut range = Range::new();
+        range.push(ByteRange::FromTo(0, 99));
+        range.push(ByteRange::From(500));
+        range.push(ByteRange::Last(10));
+
+        let mut headers = Headers::new();
+        range.apply(&mut headers);
+        assert_eq!(headers[RANGE], "bytes=0-99, 500-, -10");
+
+        let parsed = Range::from_headers(headers)?.unwrap();
+        assert_eq!(parsed, range);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        for value in &["items=0-1", "bytes=", "bytes=a-b", "0-1"] {
+            let mut headers = Headers::new();
+            headers.insert(RANGE, *value);
+            let err = Range::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400, "{}", value);
+        }
+    }
+}
@@ -0,0 +1,451 @@
+use async_std::io::{self, BufReader, Read, Seek, SeekFrom};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::io::Cursor;
+use std::ops;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::conditional::ETag;
+use crate::headers::{CONTENT_TYPE, LAST_MODIFIED};
+use crate::range::{AcceptRanges, ContentRange, IfRange, Range};
+use crate::{Body, HttpDate, Method, Mime, Request, Response, StatusCode};
+
+/// The most ranges a `Range` header may contain before it's ignored.
+const MAX_RANGES: usize = 100;
+
+/// Respond to a request for part of the response body, honoring the `Range`
+/// and `If-Range` headers.
+///
+/// The response should be a `200 OK` response with its body and any
+/// `Content-Type`, `ETag` and `Last-Modified` headers already set. Responses
+/// whose body has an unknown length are left untouched. Otherwise the
+/// response becomes one of:
+///
+/// - `200 OK` with the whole body, if no valid `Range` header was sent, the
+///   method isn't `GET`, or the `If-Range` validator doesn't match.
+/// - `206 Partial Content` with a `Content-Range` header, if a single range
+///   was requested.
+/// - `206 Partial Content` with a `multipart/byteranges` body, if multiple
+///   ranges were requested and the body is seekable. Multiple ranges of a
+///   body which isn't seekable are ignored.
+/// - `416 Range Not Satisfiable`, if none of the ranges overlap the body.
+///
+/// Overlapping and adjacent ranges are coalesced before responding, and a
+/// `Range` header with more than 100 ranges is ignored, so that a small body
+/// can't be turned into a much larger response.
+///
+/// The `Accept-Ranges: bytes` header is set whenever the length of the body
+/// is known. Bytes outside the requested ranges of a seekable body, such as
+/// one created with `Body::from_file`, are never read.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> { async_std::task::block_on(async {
+/// #
+/// use http_types::{range, Method, Request, Response, StatusCode, Url};
+///
+/// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+/// req.insert_header("Range", "bytes=6-");
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// res.set_body("hello world");
+/// range::respond(&req, &mut res);
+///
+/// assert_eq!(res.status(), StatusCode::PartialContent);
+/// assert_eq!(res["Content-Range"], "bytes 6-10/11");
+/// assert_eq!(res.body_string().await?, "world");
+/// #
+/// # Ok(()) }) }
+/// ```
+pub fn respond(req: &Request, res: &mut Response) {
+    let len = match res.len() {
+        Some(len) => len as u64,
+        None => return,
+    };
+    AcceptRanges::bytes().apply(&mut *res);
+
+    let ranges = match requested_ranges(req, res) {
+        Some(ranges) if res.status() == StatusCode::Ok => ranges,
+        _ => return,
+    };
+
+    if ranges.iter().count() > MAX_RANGES {
+        return;
+    }
+    let ranges = coalesce(ranges.iter().filter_map(|r| r.resolve(len)).collect());
+    match ranges.len() {
+        0 => {
+            res.set_status(StatusCode::RequestedRangeNotSatisfiable);
+            ContentRange::unsatisfied(len).apply(&mut *res);
+            res.set_body(Body::empty());
+        }
+        1 => {
+            let range = ranges[0].clone();
+            res.set_status(StatusCode::PartialContent);
+            ContentRange::new(range.start..=range.end - 1, Some(len)).apply(&mut *res);
+
+            let body = res.take_body();
+            res.set_body(body.slice(range));
+        }
+        _ => {
+            let body = res.take_body();
+            if !body.is_seekable() {
+                res.set_body(body);
+                return;
+            }
+            let source = body.into_seekable().unwrap();
+
+            let boundary: String = rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(32)
+                .collect();
+            let mime: Mime = format!("multipart/byteranges; boundary={}", boundary)
+                .parse()
+                .unwrap();
+
+            let mut segments = vec![];
+            for range in ranges {
+                let mut headers = format!("\r\n--{}\r\n", boundary);
+                if let Some(content_type) = res.content_type() {
+                    write!(headers, "Content-Type: {}\r\n", content_type).unwrap();
+                }
+                let content_range = ContentRange::new(range.start..=range.end - 1, Some(len));
+                write!(headers, "Content-Range: {}\r\n\r\n", content_range).unwrap();
+                segments.push(Segment::Bytes(Cursor::new(headers.into_bytes())));
+                segments.push(Segment::range(range));
+            }
+            let closing = format!("\r\n--{}--\r\n", boundary);
+            segments.push(Segment::Bytes(Cursor::new(closing.into_bytes())));
+
+            let body_len = segments.iter().map(Segment::len).sum::<u64>() as usize;
+            let mut body = Body::from_reader(
+                BufReader::new(RangesReader::new(source, segments)),
+                Some(body_len),
+            );
+            body.set_mime(mime.clone());
+
+            res.set_status(StatusCode::PartialContent);
+            res.insert_header(CONTENT_TYPE, mime);
+            res.set_body(body);
+        }
+    }
+}
+
+/// Merge overlapping and adjacent ranges, ordering them by their start.
+fn coalesce(mut ranges: Vec<ops::Range<u64>>) -> Vec<ops::Range<u64>> {
+    ranges.sort_by_key(|range| range.start);
+    let mut output: Vec<ops::Range<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match output.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => output.push(range),
+        }
+    }
+    output
+}
+
+/// Get the ranges the client requested, if they should be honored.
+fn requested_ranges(req: &Request, res: &Response) -> Option<Range> {
+    if req.method() != Method::Get {
+        return None;
+    }
+    let range = Range::from_headers(req).ok()??;
+
+    match IfRange::from_headers(req) {
+        Ok(None) => Some(range),
+        Ok(Some(if_range)) => {
+            let etag = ETag::from_headers(res).ok().flatten();
+            let last_modified = res
+                .header(LAST_MODIFIED)
+                .and_then(|values| values.last().as_str().parse::<HttpDate>().ok())
+                .map(Into::into);
+            match if_range.matches(etag.as_ref(), last_modified) {
+                true => Some(range),
+                false => None,
+            }
+        }
+        Err(_) => None,
+    }
+}
+
+/// A part of the response body.
+#[derive(Debug)]
+enum Segment {
+    /// Literal bytes, such as the headers of a part.
+    Bytes(Cursor<Vec<u8>>),
+    /// A range of the source.
+    Range {
+        start: u64,
+        remaining: u64,
+        seeked: bool,
+    },
+}
+
+impl Segment {
+    fn range(range: ops::Range<u64>) -> Self {
+        Segment::Range {
+            start: range.start,
+            remaining: range.end - range.start,
+            seeked: false,
+        }
+    }
+
+    fn len(&self) -> u64 {
+        match self {
+            Segment::Bytes(cursor) => cursor.get_ref().len() as u64,
+            Segment::Range { remaining, .. } => *remaining,
+        }
+    }
+}
+
+/// Read a sequence of literal bytes and ranges of a seekable source.
+#[derive(Debug)]
+struct RangesReader<R> {
+    source: R,
+    segments: VecDeque<Segment>,
+}
+
+impl<R> RangesReader<R> {
+    fn new(source: R, segments: Vec<Segment>) -> Self {
+        Self {
+            source,
+            segments: segments.into(),
+        }
+    }
+}
+
+impl<R: Read + Seek + Unpin> Read for RangesReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            let segment = match this.segments.front_mut() {
+                Some(segment) => segment,
+                None => return Poll::Ready(Ok(0)),
+            };
+
+            match segment {
+                Segment::Bytes(cursor) => {
+                    let n = std::io::Read::read(cursor, buf)?;
+                    if n == 0 && !buf.is_empty() {
+                        this.segments.pop_front();
+                        continue;
+                    }
+                    return Poll::Ready(Ok(n));
+                }
+                Segment::Range {
+                    start,
+                    remaining,
+                    seeked,
+                } => {
+                    if *remaining == 0 {
+                        this.segments.pop_front();
+                        continue;
+                    }
+                    if !*seeked {
+                        let pos = SeekFrom::Start(*start);
+                        match Pin::new(&mut this.source).poll_seek(cx, pos) {
+                            Poll::Ready(Ok(_)) => *seeked = true,
+                            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                            Poll::Pending => return Poll::Pending,
+                        }
+                    }
+
+                    let max = buf.len().min(*remaining as usize);
+                    let n = match Pin::new(&mut this.source).poll_read(cx, &mut buf[..max]) {
+                        Poll::Ready(Ok(n)) => n,
+                        Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                        Poll::Pending => return Poll::Pending,
+                    };
+                    if n == 0 && max > 0 {
+                        let err = io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "Source ended before the end of the range",
+                        );
+                        return Poll::Ready(Err(err));
+                    }
+                    *remaining -= n as u64;
+                    return Poll::Ready(Ok(n));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::range::ContentRange;
+    use crate::Url;
+
+    const SOURCE: &str = "0123456789abcdefghij";
+
+    fn request(headers: &[(&str, &str)]) -> Request {
+        let mut req = Request::new(Method::Get, Url::parse("https://example.com").unwrap());
+        for (name, value) in headers {
+            req.insert_header(*name, *value);
+        }
+        req
+    }
+
+    fn respond_to(req: &Request) -> Response {
+        let mut res = Response::new(StatusCode::Ok);
+        res.insert_header("ETag", r#""v1""#);
+        res.set_body(SOURCE);
+        respond(req, &mut res);
+        res
+    }
+
+    fn respond_with_stream(req: &Request, len: Option<usize>) -> Response {
+        let mut res = Response::new(StatusCode::Ok);
+        res.set_body(Body::from_reader(io::Cursor::new(SOURCE), len));
+        respond(req, &mut res);
+        res
+    }
+
+    #[async_std::test]
+    async fn no_range() -> crate::Result<()> {
+        let mut res = respond_to(&request(&[]));
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert_eq!(res["Accept-Ranges"], "bytes");
+        assert_eq!(res.len(), Some(20));
+        assert_eq!(res.body_string().await?, SOURCE);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn single_range() -> crate::Result<()> {
+        let mut res = respond_to(&request(&[("Range", "bytes=-5")]));
+        assert_eq!(res.status(), StatusCode::PartialContent);
+        assert_eq!(res["Content-Range"], "bytes 15-19/20");
+        assert_eq!(res.len(), Some(5));
+        assert_eq!(res.body_string().await?, "fghij");
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn multiple_ranges() -> crate::Result<()> {
+        let mut res = respond_to(&request(&[("Range", "bytes=0-1, 30-40, 18-")]));
+        assert_eq!(res.status(), StatusCode::PartialContent);
+        assert!(res.header("Content-Range").is_none());
+
+        let mime = res.content_type().unwrap();
+        assert_eq!(mime.essence(), "multipart/byteranges");
+        let boundary = mime.param("boundary").unwrap().as_str().to_owned();
+
+        let len = res.len();
+        let body = res.body_string().await?;
+        assert_eq!(len, Some(body.len()));
+
+        let expected = format!(
+            concat!(
+                "\r\n--{b}\r\n",
+                "Content-Type: text/plain;charset=utf-8\r\n",
+                "Content-Range: bytes 0-1/20\r\n",
+                "\r\n",
+                "01",
+                "\r\n--{b}\r\n",
+                "Content-Type: text/plain;charset=utf-8\r\n",
+                "Content-Range: bytes 18-19/20\r\n",
+                "\r\n",
+                "ij",
+                "\r\n--{b}--\r\n",
+            ),
+            b = boundary
+        );
+        assert_eq!(body, expected);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn coalesces_ranges() -> crate::Result<()> {
+        let mut res = respond_to(&request(&[("Range", "bytes=5-9, 0-4, 3-6, -0")]));
+        assert_eq!(res.status(), StatusCode::PartialContent);
+        assert_eq!(res["Content-Range"], "bytes 0-9/20");
+        assert_eq!(res.body_string().await?, "0123456789");
+
+        let mut res = respond_to(&request(&[("Range", "bytes=15-, 0-1, 1-2, 16-17")]));
+        assert_eq!(res.status(), StatusCode::PartialContent);
+        let body = res.body_string().await?;
+        assert!(body.contains("Content-Range: bytes 0-2/20\r\n\r\n012\r\n"));
+        assert!(body.contains("Content-Range: bytes 15-19/20\r\n\r\nfghij\r\n"));
+        assert_eq!(body.matches("Content-Range").count(), 2);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn ignores_too_many_ranges() -> crate::Result<()> {
+        let range = format!("bytes={}", vec!["0-"; MAX_RANGES + 1].join(", "));
+        let mut res = respond_to(&request(&[("Range", &range)]));
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert_eq!(res.len(), Some(20));
+        assert_eq!(res.body_string().await?, SOURCE);
+
+        let range = format!("bytes={}", vec!["0-"; MAX_RANGES].join(", "));
+        let res = respond_to(&request(&[("Range", &range)]));
+        assert_eq!(res.status(), StatusCode::PartialContent);
+        assert_eq!(res.len(), Some(20));
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn unsatisfiable() -> crate::Result<()> {
+        let res = respond_to(&request(&[("Range", "bytes=20-, -0")]));
+        assert_eq!(res.status(), StatusCode::RequestedRangeNotSatisfiable);
+        assert_eq!(
+            ContentRange::from_headers(&res)?,
+            Some(ContentRange::unsatisfied(20))
+        );
+        assert_eq!(res.len(), Some(0));
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn if_range() -> crate::Result<()> {
+        let res = respond_to(&request(&[("Range", "bytes=0-1"), ("If-Range", r#""v1""#)]));
+        assert_eq!(res.status(), StatusCode::PartialContent);
+
+        let res = respond_to(&request(&[("Range", "bytes=0-1"), ("If-Range", r#""v2""#)]));
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert_eq!(res.len(), Some(20));
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn ignores_invalid_ranges_and_other_methods() -> crate::Result<()> {
+        let res = respond_to(&request(&[("Range", "bytes=5-1")]));
+        assert_eq!(res.status(), StatusCode::Ok);
+
+        let mut req = request(&[("Range", "bytes=0-1")]);
+        req.set_method(Method::Post);
+        let res = respond_to(&req);
+        assert_eq!(res.status(), StatusCode::Ok);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn streaming_bodies() -> crate::Result<()> {
+        let mut res = respond_with_stream(&request(&[("Range", "bytes=2-4")]), Some(20));
+        assert_eq!(res.status(), StatusCode::PartialContent);
+        assert_eq!(res.len(), Some(3));
+        assert_eq!(res.body_string().await?, "234");
+
+        let res = respond_with_stream(&request(&[("Range", "bytes=0-1, 4-5")]), Some(20));
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert_eq!(res["Accept-Ranges"], "bytes");
+        assert_eq!(res.len(), Some(20));
+
+        let res = respond_with_stream(&request(&[("Range", "bytes=0-1")]), None);
+        assert_eq!(res.status(), StatusCode::Ok);
+        assert!(res.header("Accept-Ranges").is_none());
+        Ok(())
+    }
+}
@@ -8,14 +8,20 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use crate::convert::{DeserializeOwned, Serialize};
+use crate::cookies::{Cookie, CookieJar};
 use crate::headers::{
     self, HeaderName, HeaderValue, HeaderValues, Headers, Names, ToHeaderValues, Values,
-    CONTENT_TYPE,
+    CONTENT_TYPE, COOKIE,
 };
 use crate::mime::Mime;
+use crate::multipart::MultipartReader;
+use crate::proxies::ProxyTrust;
 use crate::trailers::{self, Trailers};
 use crate::{Body, Extensions, Method, StatusCode, Url, Version};
 
+#[cfg(feature = "cookie-secure")]
+use crate::cookies::Key;
+
 pin_project_lite::pin_project! {
     /// An HTTP request.
     ///
@@ -37,6 +43,7 @@ pin_project_lite::pin_project! {
         body: Body,
         local_addr: Option<String>,
         peer_addr: Option<String>,
+        proxy_trust: Option<ProxyTrust>,
         ext: Extensions,
         trailers_sender: Option<sync::Sender<Trailers>>,
         trailers_receiver: Option<sync::Receiver<Trailers>>,
@@ -62,6 +69,7 @@ impl Request {
             ext: Extensions::new(),
             peer_addr: None,
             local_addr: None,
+            proxy_trust: None,
             trailers_receiver: Some(trailers_receiver),
             trailers_sender: Some(trailers_sender),
             has_trailers: false,
@@ -94,14 +102,38 @@ impl Request {
         self.local_addr.as_deref()
     }
 
+    /// Sets which proxies are trusted to report the client address and
+    /// host of this request.
+    ///
+    /// Without a `ProxyTrust`, forwarding headers are trusted naively.
+    pub fn set_proxy_trust(&mut self, proxy_trust: Option<ProxyTrust>) {
+        self.proxy_trust = proxy_trust;
+    }
+
+    /// Get which proxies are trusted to report the client address and host
+    /// of this request.
+    pub fn proxy_trust(&self) -> Option<&ProxyTrust> {
+        self.proxy_trust.as_ref()
+    }
+
     /// Get the remote address for this request.
     ///
     /// This is determined in the following priority:
     /// 1. `Forwarded` header `for` key
     /// 2. The first `X-Forwarded-For` header
     /// 3. Peer address of the transport
+    ///
+    /// If a `ProxyTrust` is set, the forwarding headers are only used when
+    /// the peer is a trusted proxy, and the last address reported by a
+    /// trusted proxy is used instead of the first.
     pub fn remote(&self) -> Option<&str> {
-        self.forwarded_for().or_else(|| self.peer_addr())
+        match &self.proxy_trust {
+            Some(trust) => trust
+                .resolve(self.peer_addr(), &self.headers)
+                .and_then(|hop| hop.forwarded_for),
+            None => self.forwarded_for(),
+        }
+        .or_else(|| self.peer_addr())
     }
 
     /// Get the destination host for this request.
@@ -111,14 +143,22 @@ impl Request {
     /// 2. The first `X-Forwarded-Host` header
     /// 3. `Host` header
     /// 4. URL domain, if any
+    ///
+    /// If a `ProxyTrust` is set, the forwarding headers are only used when
+    /// the peer is a trusted proxy, and the host reported along with the
+    /// remote address is used.
     pub fn host(&self) -> Option<&str> {
-        self.forwarded_header_part("host")
-            .or_else(|| {
+        match &self.proxy_trust {
+            Some(trust) => trust
+                .resolve(self.peer_addr(), &self.headers)
+                .and_then(|hop| hop.host),
+            None => self.forwarded_header_part("host").or_else(|| {
                 self.header("X-Forwarded-Host")
                     .and_then(|h| h.as_str().split(',').next())
-            })
-            .or_else(|| self.header(&headers::HOST).map(|h| h.as_str()))
-            .or_else(|| self.url().host_str())
+            }),
+        }
+        .or_else(|| self.header(&headers::HOST).map(|h| h.as_str()))
+        .or_else(|| self.url().host_str())
     }
 
     fn forwarded_header_part(&self, part: &str) -> Option<&str> {
@@ -407,6 +447,40 @@ impl Request {
         body.into_form().await
     }
 
+    /// Read the body as `multipart/form-data`, streaming its parts.
+    ///
+    /// The boundary is read from the request's `Content-Type` header. See
+    /// [`Body::into_multipart`](struct.Body.html#method.into_multipart) for
+    /// more details.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// `Content-Type` is not `multipart/*`, or has no valid boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> { async_std::task::block_on(async {
+    /// use http_types::{Method, Request, Url};
+    ///
+    /// let mut req = Request::new(Method::Post, Url::parse("https://example.com")?);
+    /// req.insert_header("Content-Type", "multipart/form-data; boundary=boundary");
+    /// req.set_body("--boundary\r\nContent-Disposition: form-data; name=\"cat\"\r\n\r\nchashu\r\n--boundary--");
+    ///
+    /// let mut multipart = req.body_multipart()?;
+    /// let part = multipart.next_part().await?.unwrap();
+    /// assert_eq!(part.name(), Some("cat"));
+    /// # Ok(()) }) }
+    /// ```
+    pub fn body_multipart(&mut self) -> crate::Result<MultipartReader> {
+        let mut body = self.take_body();
+        if let Some(mime) = self.content_type() {
+            body.set_mime(mime);
+        }
+        body.into_multipart()
+    }
+
     /// Get an HTTP header.
     pub fn header(&self, name: impl Into<HeaderName>) -> Option<&HeaderValues> {
         self.headers.get(name)
@@ -466,6 +540,131 @@ impl Request {
         self.headers.append(name, values)
     }
 
+    /// Get a cookie sent with the request by name.
+    ///
+    /// If the same cookie name is sent more than once, the first occurrence is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> {
+    /// #
+    /// use http_types::{Method, Request, Url};
+    ///
+    /// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+    /// req.insert_header("Cookie", "name=chashu; color=orange");
+    ///
+    /// assert_eq!(req.cookie("color").unwrap().value(), "orange");
+    /// assert!(req.cookie("age").is_none());
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn cookie(&self, name: &str) -> Option<Cookie<'static>> {
+        self.cookies()
+            .into_iter()
+            .find(|cookie| cookie.name() == name)
+    }
+
+    /// Get all cookies sent with the request.
+    ///
+    /// Cookies from multiple `Cookie` headers are merged into a single list, in
+    /// the order they were sent. Malformed cookie pairs are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> {
+    /// #
+    /// use http_types::{Method, Request, Url};
+    ///
+    /// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+    /// req.append_header("Cookie", "name=chashu");
+    /// req.append_header("Cookie", "color=orange");
+    ///
+    /// let cookies = req.cookies();
+    /// assert_eq!(cookies.len(), 2);
+    /// assert_eq!(cookies[0].name_value(), ("name", "chashu"));
+    /// assert_eq!(cookies[1].name_value(), ("color", "orange"));
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn cookies(&self) -> Vec<Cookie<'static>> {
+        let mut cookies = vec![];
+        let headers = match self.header(COOKIE) {
+            Some(headers) => headers,
+            None => return cookies,
+        };
+
+        for header in headers {
+            for pair in header.as_str().split(';') {
+                let pair = pair.trim();
+                if pair.is_empty() {
+                    continue;
+                }
+                if let Ok(cookie) = Cookie::parse_encoded(pair.to_owned()) {
+                    cookies.push(cookie);
+                }
+            }
+        }
+        cookies
+    }
+
+    /// Get the cookie jar for this request.
+    ///
+    /// The jar is populated from the `Cookie` headers the first time it's
+    /// accessed, and stored in the request's extensions afterwards. Cookies
+    /// added to or removed from the jar are tracked as its delta, which can be
+    /// sent back to the client using
+    /// [`Response::set_cookie_jar`](struct.Response.html#method.set_cookie_jar).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> {
+    /// #
+    /// use http_types::{Cookie, Method, Request, Url};
+    ///
+    /// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+    /// req.insert_header("Cookie", "name=chashu");
+    ///
+    /// let jar = req.cookie_jar();
+    /// assert_eq!(jar.get("name").unwrap().value(), "chashu");
+    ///
+    /// jar.add(Cookie::new("color", "orange"));
+    /// assert_eq!(jar.delta().count(), 1);
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn cookie_jar(&mut self) -> &mut CookieJar {
+        if !self.ext.contains::<CookieJar>() {
+            let mut jar = CookieJar::new();
+            for cookie in self.cookies() {
+                jar.add_original(cookie);
+            }
+            self.ext.insert(jar);
+        }
+        self.ext.get_mut().unwrap()
+    }
+
+    /// Get a signed cookie sent with the request by name.
+    ///
+    /// Returns `None` if the cookie doesn't exist, or if its signature could
+    /// not be verified with `key`.
+    #[cfg(feature = "cookie-secure")]
+    pub fn signed_cookie(&mut self, key: &Key, name: &str) -> Option<Cookie<'static>> {
+        self.cookie_jar().signed(key).get(name)
+    }
+
+    /// Get a private (encrypted) cookie sent with the request by name.
+    ///
+    /// Returns `None` if the cookie doesn't exist, or if it could not be
+    /// decrypted and authenticated with `key`.
+    #[cfg(feature = "cookie-secure")]
+    pub fn private_cookie(&mut self, key: &Key, name: &str) -> Option<Cookie<'static>> {
+        self.cookie_jar().private(key).get(name)
+    }
+
     /// Set the response MIME.
     // TODO: return a parsed MIME
     pub fn set_content_type(&mut self, mime: Mime) -> Option<HeaderValues> {
@@ -881,6 +1080,7 @@ impl Clone for Request {
             ext: Extensions::new(),
             peer_addr: self.peer_addr.clone(),
             local_addr: self.local_addr.clone(),
+            proxy_trust: self.proxy_trust.clone(),
             has_trailers: false,
         }
     }
@@ -1166,6 +1366,98 @@ mod tests {
         }
     }
 
+    mod proxy_trust {
+        use super::*;
+
+        #[test]
+        fn ignores_headers_from_untrusted_peers() {
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("192.0.2.1:8000"));
+            set_forwarded(&mut request, "127.0.0.1:8001");
+            request.set_proxy_trust(Some(ProxyTrust::cidrs(&["10.0.0.0/8"]).unwrap()));
+
+            assert_eq!(request.remote(), Some("192.0.2.1:8000"));
+            assert_eq!(request.host(), Some("async.rs"));
+        }
+
+        #[test]
+        fn skips_trusted_proxies() {
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("10.0.0.2:8000"));
+            request.insert_header("Host", "internal");
+            request.insert_header(
+                "Forwarded",
+                r#"for=192.0.2.1;host=spoofed.com, for="[2001:db8::1]";host=example.com, for=10.0.0.1"#,
+            );
+            request.set_proxy_trust(Some(ProxyTrust::cidrs(&["10.0.0.0/8"]).unwrap()));
+
+            assert_eq!(request.remote(), Some("[2001:db8::1]"));
+            assert_eq!(request.host(), Some("example.com"));
+        }
+
+        #[test]
+        fn trusts_a_number_of_hops() {
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("10.0.0.2:8000"));
+            set_x_forwarded_for(&mut request, "client.com");
+            set_x_forwarded_host(&mut request, "client.com");
+
+            request.set_proxy_trust(Some(ProxyTrust::Hops(2)));
+            assert_eq!(request.remote(), Some("proxy.com"));
+            assert_eq!(request.host(), Some("proxy.com"));
+
+            request.set_proxy_trust(Some(ProxyTrust::Hops(0)));
+            assert_eq!(request.remote(), Some("10.0.0.2:8000"));
+        }
+
+        #[test]
+        fn ignores_malformed_headers() {
+            let mut request = build_test_request();
+            request.set_peer_addr(Some("10.0.0.2:8000"));
+            request.insert_header("Forwarded", "this is an improperly ;;; formatted header");
+            request.set_proxy_trust(Some(ProxyTrust::Hops(1)));
+
+            assert_eq!(request.remote(), Some("10.0.0.2:8000"));
+        }
+    }
+
+    mod cookies {
+        use super::*;
+
+        #[test]
+        fn merges_multiple_cookie_headers() {
+            let mut request = build_test_request();
+            request.append_header("cookie", "name=chashu; color=orange");
+            request.append_header("cookie", "age=5");
+
+            let cookies = request.cookies();
+            let names: Vec<_> = cookies.iter().map(|cookie| cookie.name()).collect();
+            assert_eq!(names, vec!["name", "color", "age"]);
+            assert_eq!(request.cookie("age").unwrap().value(), "5");
+        }
+
+        #[test]
+        fn decodes_percent_encoded_values() {
+            let mut request = build_test_request();
+            request.insert_header("cookie", "greeting=hello%20nori");
+            assert_eq!(request.cookie("greeting").unwrap().value(), "hello nori");
+        }
+
+        #[test]
+        fn skips_malformed_pairs() {
+            let mut request = build_test_request();
+            request.insert_header("cookie", "name=chashu; ; novalue; color=orange");
+            assert_eq!(request.cookies().len(), 2);
+        }
+
+        #[test]
+        fn when_no_cookie_header_is_set() {
+            let request = build_test_request();
+            assert!(request.cookies().is_empty());
+            assert!(request.cookie("name").is_none());
+        }
+    }
+
     fn build_test_request() -> Request {
         let url = Url::parse("http://async.rs/").unwrap();
         Request::new(Method::Get, url)
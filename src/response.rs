@@ -8,15 +8,25 @@ use std::ops::Index;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+use crate::content::{ContentDisposition, DispositionType};
 use crate::convert::DeserializeOwned;
+use crate::cookies::{Cookie, CookieJar};
 use crate::headers::{
     self, HeaderName, HeaderValue, HeaderValues, Headers, Names, ToHeaderValues, Values,
-    CONTENT_TYPE,
+    CONTENT_TYPE, SET_COOKIE,
 };
 use crate::mime::Mime;
+use crate::security::CspNonce;
 use crate::trailers::{self, Trailers};
 use crate::{Body, Extensions, StatusCode, Version};
 
+#[cfg(feature = "compression")]
+use crate::content::{ContentEncoding, Encoding};
+#[cfg(feature = "cookie-secure")]
+use crate::cookies::Key;
+#[cfg(feature = "compression")]
+use crate::headers::{CONTENT_ENCODING, CONTENT_LENGTH};
+
 cfg_unstable! {
     use crate::upgrade;
 }
@@ -207,6 +217,148 @@ impl Response {
         self.headers.append(name, values)
     }
 
+    /// Add a cookie to the response.
+    ///
+    /// Every cookie is sent as its own `Set-Cookie` header. The cookie's name
+    /// and value are percent-encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http_types::{Cookie, Response, StatusCode};
+    ///
+    /// let mut res = Response::new(StatusCode::Ok);
+    /// res.insert_cookie(Cookie::new("name", "chashu"));
+    /// res.insert_cookie(Cookie::new("color", "orange"));
+    ///
+    /// let cookies = res.header("Set-Cookie").unwrap();
+    /// assert_eq!(cookies.iter().count(), 2);
+    /// assert_eq!(cookies[0], "name=chashu");
+    /// ```
+    pub fn insert_cookie(&mut self, cookie: Cookie<'_>) {
+        self.append_header(SET_COOKIE, cookie.encoded().to_string());
+    }
+
+    /// Instruct the client to remove a cookie.
+    ///
+    /// This sends a `Set-Cookie` header with an empty value, a `Max-Age` of
+    /// zero, and an expiration date in the past. The `Path` and `Domain` of the
+    /// cookie should match the ones it was originally set with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http_types::{Cookie, Response, StatusCode};
+    ///
+    /// let mut res = Response::new(StatusCode::Ok);
+    /// res.remove_cookie(Cookie::named("name"));
+    ///
+    /// let cookie = res.header("Set-Cookie").unwrap().last().as_str();
+    /// assert!(cookie.starts_with("name=;"));
+    /// assert!(cookie.contains("Max-Age=0"));
+    /// ```
+    pub fn remove_cookie(&mut self, cookie: Cookie<'_>) {
+        // `CookieJar` knows how to build a removal cookie; let it do the work.
+        let cookie = cookie.into_owned();
+        let mut jar = CookieJar::new();
+        jar.add_original(cookie.clone());
+        jar.remove(cookie);
+        for cookie in jar.delta() {
+            self.insert_cookie(cookie.clone());
+        }
+    }
+
+    /// Get the cookie jar for this response.
+    ///
+    /// An empty jar is created and stored in the response's extensions if none
+    /// has been set yet. Cookies added to or removed from the jar are turned
+    /// into `Set-Cookie` headers by
+    /// [`finalize_cookies`](#method.finalize_cookies).
+    pub fn cookie_jar(&mut self) -> &mut CookieJar {
+        if !self.ext.contains::<CookieJar>() {
+            self.ext.insert(CookieJar::new());
+        }
+        self.ext.get_mut().unwrap()
+    }
+
+    /// Set the cookie jar for this response, returning the previous jar.
+    ///
+    /// This is useful to carry the jar of a `Request` over to its `Response`,
+    /// so that only the changes made while handling the request are sent back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), http_types::Error> {
+    /// #
+    /// use http_types::{Cookie, Method, Request, Response, StatusCode, Url};
+    ///
+    /// let mut req = Request::new(Method::Get, Url::parse("https://example.com")?);
+    /// req.insert_header("Cookie", "name=chashu; color=orange");
+    ///
+    /// let jar = req.cookie_jar();
+    /// jar.add(Cookie::new("name", "nori"));
+    /// let jar = jar.clone();
+    ///
+    /// let mut res = Response::new(StatusCode::Ok);
+    /// res.set_cookie_jar(jar);
+    /// res.finalize_cookies();
+    ///
+    /// assert_eq!(res["Set-Cookie"], "name=nori");
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn set_cookie_jar(&mut self, jar: CookieJar) -> Option<CookieJar> {
+        self.ext.insert(jar)
+    }
+
+    /// Add a signed cookie to the response's cookie jar.
+    ///
+    /// The cookie's value is signed with `key`, which lets the cookie be
+    /// verified with [`Request::signed_cookie`] once it's sent back.
+    ///
+    /// [`Request::signed_cookie`]: struct.Request.html#method.signed_cookie
+    #[cfg(feature = "cookie-secure")]
+    pub fn insert_signed_cookie(&mut self, key: &Key, cookie: Cookie<'static>) {
+        self.cookie_jar().signed(key).add(cookie);
+    }
+
+    /// Add a private (encrypted) cookie to the response's cookie jar.
+    ///
+    /// The cookie's value is encrypted and authenticated with `key`, which lets
+    /// the cookie be read with [`Request::private_cookie`] once it's sent back.
+    ///
+    /// [`Request::private_cookie`]: struct.Request.html#method.private_cookie
+    #[cfg(feature = "cookie-secure")]
+    pub fn insert_private_cookie(&mut self, key: &Key, cookie: Cookie<'static>) {
+        self.cookie_jar().private(key).add(cookie);
+    }
+
+    /// Turn the delta of the response's cookie jar into `Set-Cookie` headers.
+    ///
+    /// The jar is taken out of the response's extensions, so calling this
+    /// more than once won't send the same cookies twice. This should be
+    /// called once the response is done being handled, before it's encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http_types::{Cookie, Response, StatusCode};
+    ///
+    /// let mut res = Response::new(StatusCode::Ok);
+    /// res.cookie_jar().add(Cookie::new("name", "chashu"));
+    /// res.finalize_cookies();
+    ///
+    /// assert_eq!(res["Set-Cookie"], "name=chashu");
+    /// ```
+    pub fn finalize_cookies(&mut self) {
+        if let Some(jar) = self.ext.remove::<CookieJar>() {
+            for cookie in jar.delta() {
+                self.insert_cookie(cookie.clone());
+            }
+        }
+    }
+
     /// Set the body reader.
     ///
     /// # Examples
@@ -311,6 +463,41 @@ impl Response {
         self.replace_body(Body::empty())
     }
 
+    /// Compress the response body using the given encoding.
+    ///
+    /// This removes the `Content-Length` header, sets the `Content-Encoding`
+    /// header, and adds `Accept-Encoding` to the `Vary` header. Nothing
+    /// happens if the encoding is `Encoding::Identity`, or if the body has
+    /// already been encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http_types::content::Encoding;
+    /// use http_types::{Response, StatusCode};
+    ///
+    /// let mut res = Response::new(StatusCode::Ok);
+    /// res.set_body("Hello, Nori!");
+    /// res.compress_body(Encoding::Gzip);
+    ///
+    /// assert_eq!(res["Content-Encoding"], "gzip");
+    /// assert_eq!(res["Vary"], "Accept-Encoding");
+    /// assert_eq!(res.len(), None);
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn compress_body(&mut self, encoding: Encoding) {
+        if encoding == Encoding::Identity || self.header(CONTENT_ENCODING).is_some() {
+            return;
+        }
+
+        let body = self.take_body().compress(encoding);
+        self.set_body(body);
+        self.remove_header(CONTENT_LENGTH);
+        ContentEncoding::new(encoding).apply(&mut self.headers);
+
+        crate::utils::append_vary(&mut self.headers, "Accept-Encoding");
+    }
+
     /// Read the body as a string.
     ///
     /// This consumes the response. If you want to read the body without
@@ -453,6 +640,37 @@ impl Response {
         self.header(CONTENT_TYPE)?.last().as_str().parse().ok()
     }
 
+    /// Mark the body as a download, using the file name when saving it.
+    ///
+    /// If no content type has been set yet, it's inferred from the file
+    /// extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http_types::{mime, Response, StatusCode};
+    ///
+    /// let mut res = Response::new(StatusCode::Ok);
+    /// res.set_attachment("report.json");
+    /// res.set_body("{}");
+    ///
+    /// assert_eq!(res["Content-Disposition"], r#"attachment; filename="report.json""#);
+    /// assert_eq!(res.content_type(), Some(mime::JSON));
+    /// ```
+    pub fn set_attachment(&mut self, filename: impl Into<String>) {
+        let filename = filename.into();
+        if self.header(CONTENT_TYPE).is_none() {
+            let extension = filename.rfind('.').map(|index| &filename[index + 1..]);
+            if let Some(mime) = extension.and_then(|ext| Mime::from_extension(ext.to_lowercase())) {
+                self.set_content_type(mime);
+            }
+        }
+
+        let mut disposition = ContentDisposition::new(DispositionType::Attachment);
+        disposition.set_filename(Some(filename));
+        disposition.apply(self);
+    }
+
     /// Get the length of the body stream, if it has been set.
     ///
     /// This value is set when passing a fixed-size object into as the body.
@@ -638,6 +856,18 @@ impl Response {
     pub fn ext_mut(&mut self) -> &mut Extensions {
         &mut self.ext
     }
+
+    /// Get the `Content-Security-Policy` nonce for this response.
+    ///
+    /// A new nonce is generated and stored in the response's extensions if
+    /// none has been set yet, so every call for the same response returns the
+    /// same nonce.
+    pub fn csp_nonce(&mut self) -> &CspNonce {
+        if !self.ext.contains::<CspNonce>() {
+            self.ext.insert(CspNonce::new());
+        }
+        self.ext.get().unwrap()
+    }
 }
 
 impl Clone for Response {
@@ -785,6 +1015,7 @@ impl<'a> IntoIterator for &'a mut Response {
 #[cfg(test)]
 mod test {
     use super::Response;
+    use crate::Cookie;
 
     #[test]
     fn construct_shorthand_with_valid_status_code() {
@@ -796,4 +1027,107 @@ mod test {
     fn construct_shorthand_with_invalid_status_code() {
         let _res = Response::new(600);
     }
+
+    #[test]
+    fn insert_cookie_emits_one_header_per_cookie() {
+        let mut res = Response::new(200);
+        res.insert_cookie(Cookie::new("name", "chashu"));
+        res.insert_cookie(Cookie::build("color", "orange").path("/").finish());
+
+        let values: Vec<_> = res["set-cookie"].iter().map(|v| v.as_str()).collect();
+        assert_eq!(values, vec!["name=chashu", "color=orange; Path=/"]);
+    }
+
+    #[test]
+    fn insert_cookie_percent_encodes() {
+        let mut res = Response::new(200);
+        res.insert_cookie(Cookie::new("greeting", "hello nori"));
+        assert_eq!(res["set-cookie"], "greeting=hello%20nori");
+    }
+
+    #[test]
+    fn remove_cookie_expires_cookie() {
+        let mut res = Response::new(200);
+        res.remove_cookie(Cookie::build("name", "chashu").path("/").finish());
+
+        let cookie = Cookie::parse(res["set-cookie"].as_str().to_owned()).unwrap();
+        assert_eq!(cookie.name(), "name");
+        assert_eq!(cookie.value(), "");
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.max_age().map(|age| age.whole_seconds()), Some(0));
+        assert!(cookie.expires().is_some());
+    }
+
+    #[cfg(feature = "compression")]
+    #[async_std::test]
+    async fn compress_body() -> crate::Result<()> {
+        use crate::content::Encoding;
+
+        let mut res = Response::new(200);
+        res.set_body("Hello, Nori!");
+        res.insert_header("Content-Length", "12");
+        res.compress_body(Encoding::Brotli);
+
+        assert!(res.header("Content-Length").is_none());
+        assert_eq!(res["Content-Encoding"], "br");
+        assert_eq!(res["Vary"], "Accept-Encoding");
+
+        let body = res.take_body().decompress(Encoding::Brotli);
+        assert_eq!(body.into_string().await?, "Hello, Nori!");
+        Ok(())
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compress_body_keeps_existing_encoding_and_vary() {
+        use crate::content::Encoding;
+
+        let mut res = Response::new(200);
+        res.insert_header("Vary", "Origin, accept-encoding");
+        res.compress_body(Encoding::Gzip);
+        assert_eq!(res["Vary"], "Origin, accept-encoding");
+        assert_eq!(res["Content-Encoding"], "gzip");
+
+        res.compress_body(Encoding::Brotli);
+        assert_eq!(res["Content-Encoding"], "gzip");
+
+        let mut res = Response::new(200);
+        res.compress_body(Encoding::Identity);
+        assert!(res.header("Content-Encoding").is_none());
+        assert!(res.header("Vary").is_none());
+    }
+
+    #[test]
+    fn set_attachment() {
+        let mut res = Response::new(200);
+        res.set_attachment("Übersicht.CSS");
+        assert_eq!(
+            res["Content-Disposition"],
+            r#"attachment; filename="_bersicht.CSS"; filename*=UTF-8''%C3%9Cbersicht.CSS"#
+        );
+        assert_eq!(res.content_type(), Some(crate::mime::CSS));
+
+        let mut res = Response::new(200);
+        res.set_body("Hello, Nori!");
+        res.set_attachment("nori.html");
+        assert_eq!(res.content_type(), Some(crate::mime::PLAIN));
+
+        let mut res = Response::new(200);
+        res.set_attachment("json");
+        assert!(res.content_type().is_none());
+
+        let mut res = Response::new(200);
+        res.set_attachment("a.txt\r\nSet-Cookie: x=1");
+        assert!(!res["Content-Disposition"].as_str().contains('\n'));
+        assert!(res.header("Set-Cookie").is_none());
+    }
+
+    #[test]
+    fn csp_nonce() {
+        let mut res = Response::new(200);
+        let nonce = res.csp_nonce().clone();
+        assert_eq!(res.csp_nonce(), &nonce);
+        assert_eq!(res.ext().get::<crate::security::CspNonce>(), Some(&nonce));
+        assert_ne!(Response::new(200).csp_nonce(), &nonce);
+    }
 }
@@ -0,0 +1,275 @@
+use crate::headers::{
+    HeaderName, HeaderValue, Headers, ToHeaderValues, CROSS_ORIGIN_EMBEDDER_POLICY,
+    CROSS_ORIGIN_EMBEDDER_POLICY_REPORT_ONLY,
+};
+use crate::security::{format_policy_item, parse_policy_item};
+use crate::utils::is_printable;
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::option;
+use std::str::FromStr;
+
+/// Require cross-origin resources loaded by a document to explicitly allow
+/// being embedded.
+///
+/// Together with a `Cross-Origin-Opener-Policy` of `same-origin`, this makes
+/// a document cross-origin isolated.
+///
+/// # Specifications
+///
+/// - [HTML, section 7.1.4: Cross-origin embedder policies](https://html.spec.whatwg.org/multipage/browsers.html#coep)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::security::{CrossOriginEmbedderPolicy, EmbedderPolicy};
+///
+/// let mut coep = CrossOriginEmbedderPolicy::new(EmbedderPolicy::Credentialless);
+/// coep.set_report_to(Some("coep-endpoint".into()));
+///
+/// let mut res = Response::new(200);
+/// coep.apply(&mut res);
+/// assert_eq!(
+///     res["Cross-Origin-Embedder-Policy"],
+///     r#"credentialless; report-to="coep-endpoint""#
+/// );
+///
+/// let coep = CrossOriginEmbedderPolicy::from_headers(res)?.unwrap();
+/// assert_eq!(coep.policy(), EmbedderPolicy::Credentialless);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CrossOriginEmbedderPolicy {
+    policy: EmbedderPolicy,
+    report_to: Option<String>,
+    report_only: bool,
+}
+
+impl CrossOriginEmbedderPolicy {
+    /// Create a new instance of `CrossOriginEmbedderPolicy`.
+    pub fn new(policy: EmbedderPolicy) -> Self {
+        Self {
+            policy,
+            report_to: None,
+            report_only: false,
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// The `Cross-Origin-Embedder-Policy` header is used if it's present, and
+    /// the `Cross-Origin-Embedder-Policy-Report-Only` header otherwise.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed or names an unknown policy.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = headers.as_ref();
+        match Self::parse_header(headers, CROSS_ORIGIN_EMBEDDER_POLICY)? {
+            Some(policy) => Ok(Some(policy)),
+            None => Self::from_report_only_headers(headers),
+        }
+    }
+
+    /// Create a new instance from the `Cross-Origin-Embedder-Policy-Report-Only`
+    /// header, ignoring any `Cross-Origin-Embedder-Policy` header.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed or names an unknown policy.
+    pub fn from_report_only_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let policy =
+            Self::parse_header(headers.as_ref(), CROSS_ORIGIN_EMBEDDER_POLICY_REPORT_ONLY)?;
+        Ok(policy.map(|mut policy| {
+            policy.report_only = true;
+            policy
+        }))
+    }
+
+    fn parse_header(headers: &Headers, name: HeaderName) -> crate::Result<Option<Self>> {
+        let headers = match headers.get(name) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If we successfully parsed the header then there's always at least one
+        // entry. We want the last entry.
+        let value = headers.iter().last().unwrap();
+        let (policy, report_to) = match parse_policy_item(value.as_str()) {
+            Some(item) => item,
+            None => {
+                let msg = format!("Invalid Cross-Origin-Embedder-Policy header: {}", value);
+                return Err(Error::from_str(StatusCode::BadRequest, msg));
+            }
+        };
+        Ok(Some(Self {
+            policy: policy.parse()?,
+            report_to,
+            report_only: false,
+        }))
+    }
+
+    /// Sets the `Cross-Origin-Embedder-Policy` header, or the
+    /// `Cross-Origin-Embedder-Policy-Report-Only` header if the policy is only
+    /// reported.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        match self.report_only {
+            true => CROSS_ORIGIN_EMBEDDER_POLICY_REPORT_ONLY,
+            false => CROSS_ORIGIN_EMBEDDER_POLICY,
+        }
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = format_policy_item(self.policy.as_str(), self.report_to.as_deref());
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Get the policy.
+    pub fn policy(&self) -> EmbedderPolicy {
+        self.policy
+    }
+
+    /// Set the policy.
+    pub fn set_policy(&mut self, policy: EmbedderPolicy) {
+        self.policy = policy;
+    }
+
+    /// Get the name of the endpoint violations are reported to.
+    pub fn report_to(&self) -> Option<&str> {
+        self.report_to.as_deref()
+    }
+
+    /// Set the name of the endpoint violations are reported to.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the name contains characters other than
+    /// printable ASCII.
+    pub fn set_report_to(&mut self, endpoint: Option<String>) {
+        if let Some(endpoint) = &endpoint {
+            assert!(
+                is_printable(endpoint),
+                "Reporting endpoint names should be printable ASCII"
+            );
+        }
+        self.report_to = endpoint;
+    }
+
+    /// Returns `true` if the policy is only reported, and not enforced.
+    pub fn is_report_only(&self) -> bool {
+        self.report_only
+    }
+
+    /// Set whether the policy is only reported, and not enforced.
+    pub fn set_report_only(&mut self, report_only: bool) {
+        self.report_only = report_only;
+    }
+}
+
+impl ToHeaderValues for CrossOriginEmbedderPolicy {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+/// The value of the `Cross-Origin-Embedder-Policy` header.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum EmbedderPolicy {
+    /// Allow loading any cross-origin resource. This is the default.
+    UnsafeNone,
+    /// Only load cross-origin resources which allow it with a
+    /// `Cross-Origin-Resource-Policy` header, or with CORS.
+    RequireCorp,
+    /// Load cross-origin `no-cors` resources without credentials, such as
+    /// cookies.
+    Credentialless,
+}
+
+impl EmbedderPolicy {
+    /// Get the name of the policy as it's used in headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmbedderPolicy::UnsafeNone => "unsafe-none",
+            EmbedderPolicy::RequireCorp => "require-corp",
+            EmbedderPolicy::Credentialless => "credentialless",
+        }
+    }
+}
+
+impl FromStr for EmbedderPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "unsafe-none" => Ok(EmbedderPolicy::UnsafeNone),
+            "require-corp" => Ok(EmbedderPolicy::RequireCorp),
+            "credentialless" => Ok(EmbedderPolicy::Credentialless),
+            s => {
+                let msg = format!("Unknown Cross-Origin-Embedder-Policy: {}", s);
+                Err(Error::from_str(StatusCode::BadRequest, msg))
+            }
+        }
+    }
+}
+
+impl Display for EmbedderPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut coep = CrossOriginEmbedderPolicy::new(EmbedderPolicy::RequireCorp);
+        coep.set_report_only(true);
+
+        let mut headers = Headers::new();
+        coep.apply(&mut headers);
+        assert_eq!(
+            headers[CROSS_ORIGIN_EMBEDDER_POLICY_REPORT_ONLY],
+            "require-corp"
+        );
+
+        let parsed = CrossOriginEmbedderPolicy::from_headers(headers)?.unwrap();
+        assert_eq!(parsed, coep);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = [
+            "same-origin",
+            "require-corp; report-to=endpoint",
+            "require-corp;A=1",
+        ];
+        for value in &values {
+            let mut headers = Headers::new();
+            headers.insert(CROSS_ORIGIN_EMBEDDER_POLICY, *value);
+            let err = CrossOriginEmbedderPolicy::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
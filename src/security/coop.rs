@@ -0,0 +1,277 @@
+use crate::headers::{
+    HeaderName, HeaderValue, Headers, ToHeaderValues, CROSS_ORIGIN_OPENER_POLICY,
+    CROSS_ORIGIN_OPENER_POLICY_REPORT_ONLY,
+};
+use crate::security::{format_policy_item, parse_policy_item};
+use crate::utils::is_printable;
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::option;
+use std::str::FromStr;
+
+/// Isolate a document's browsing context group from cross-origin documents
+/// which open it, or which it opens.
+///
+/// # Specifications
+///
+/// - [HTML, section 7.1.3: Cross-origin opener policies](https://html.spec.whatwg.org/multipage/browsers.html#cross-origin-opener-policies)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::security::{CrossOriginOpenerPolicy, OpenerPolicy};
+///
+/// let mut coop = CrossOriginOpenerPolicy::new(OpenerPolicy::SameOrigin);
+/// coop.set_report_to(Some("coop-endpoint".into()));
+///
+/// let mut res = Response::new(200);
+/// coop.apply(&mut res);
+/// assert_eq!(
+///     res["Cross-Origin-Opener-Policy"],
+///     r#"same-origin; report-to="coop-endpoint""#
+/// );
+///
+/// let coop = CrossOriginOpenerPolicy::from_headers(res)?.unwrap();
+/// assert_eq!(coop.policy(), OpenerPolicy::SameOrigin);
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CrossOriginOpenerPolicy {
+    policy: OpenerPolicy,
+    report_to: Option<String>,
+    report_only: bool,
+}
+
+impl CrossOriginOpenerPolicy {
+    /// Create a new instance of `CrossOriginOpenerPolicy`.
+    pub fn new(policy: OpenerPolicy) -> Self {
+        Self {
+            policy,
+            report_to: None,
+            report_only: false,
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// The `Cross-Origin-Opener-Policy` header is used if it's present, and
+    /// the `Cross-Origin-Opener-Policy-Report-Only` header otherwise.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed or names an unknown policy.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = headers.as_ref();
+        match Self::parse_header(headers, CROSS_ORIGIN_OPENER_POLICY)? {
+            Some(policy) => Ok(Some(policy)),
+            None => Self::from_report_only_headers(headers),
+        }
+    }
+
+    /// Create a new instance from the `Cross-Origin-Opener-Policy-Report-Only`
+    /// header, ignoring any `Cross-Origin-Opener-Policy` header.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed or names an unknown policy.
+    pub fn from_report_only_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let policy = Self::parse_header(headers.as_ref(), CROSS_ORIGIN_OPENER_POLICY_REPORT_ONLY)?;
+        Ok(policy.map(|mut policy| {
+            policy.report_only = true;
+            policy
+        }))
+    }
+
+    fn parse_header(headers: &Headers, name: HeaderName) -> crate::Result<Option<Self>> {
+        let headers = match headers.get(name) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If we successfully parsed the header then there's always at least one
+        // entry. We want the last entry.
+        let value = headers.iter().last().unwrap();
+        let (policy, report_to) = match parse_policy_item(value.as_str()) {
+            Some(item) => item,
+            None => {
+                let msg = format!("Invalid Cross-Origin-Opener-Policy header: {}", value);
+                return Err(Error::from_str(StatusCode::BadRequest, msg));
+            }
+        };
+        Ok(Some(Self {
+            policy: policy.parse()?,
+            report_to,
+            report_only: false,
+        }))
+    }
+
+    /// Sets the `Cross-Origin-Opener-Policy` header, or the
+    /// `Cross-Origin-Opener-Policy-Report-Only` header if the policy is only
+    /// reported.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        match self.report_only {
+            true => CROSS_ORIGIN_OPENER_POLICY_REPORT_ONLY,
+            false => CROSS_ORIGIN_OPENER_POLICY,
+        }
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = format_policy_item(self.policy.as_str(), self.report_to.as_deref());
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Get the policy.
+    pub fn policy(&self) -> OpenerPolicy {
+        self.policy
+    }
+
+    /// Set the policy.
+    pub fn set_policy(&mut self, policy: OpenerPolicy) {
+        self.policy = policy;
+    }
+
+    /// Get the name of the endpoint violations are reported to.
+    pub fn report_to(&self) -> Option<&str> {
+        self.report_to.as_deref()
+    }
+
+    /// Set the name of the endpoint violations are reported to.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the name contains characters other than
+    /// printable ASCII.
+    pub fn set_report_to(&mut self, endpoint: Option<String>) {
+        if let Some(endpoint) = &endpoint {
+            assert!(
+                is_printable(endpoint),
+                "Reporting endpoint names should be printable ASCII"
+            );
+        }
+        self.report_to = endpoint;
+    }
+
+    /// Returns `true` if the policy is only reported, and not enforced.
+    pub fn is_report_only(&self) -> bool {
+        self.report_only
+    }
+
+    /// Set whether the policy is only reported, and not enforced.
+    pub fn set_report_only(&mut self, report_only: bool) {
+        self.report_only = report_only;
+    }
+}
+
+impl ToHeaderValues for CrossOriginOpenerPolicy {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+/// The value of the `Cross-Origin-Opener-Policy` header.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum OpenerPolicy {
+    /// Share the browsing context group with any document. This is the
+    /// default.
+    UnsafeNone,
+    /// Like `SameOrigin`, but keep references to popups which don't set a
+    /// policy.
+    SameOriginAllowPopups,
+    /// Only share the browsing context group with same-origin documents which
+    /// use the same policy.
+    SameOrigin,
+    /// Always open the document in a new browsing context group, but keep
+    /// references to popups it opens.
+    NoopenerAllowPopups,
+}
+
+impl OpenerPolicy {
+    /// Get the name of the policy as it's used in headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpenerPolicy::UnsafeNone => "unsafe-none",
+            OpenerPolicy::SameOriginAllowPopups => "same-origin-allow-popups",
+            OpenerPolicy::SameOrigin => "same-origin",
+            OpenerPolicy::NoopenerAllowPopups => "noopener-allow-popups",
+        }
+    }
+}
+
+impl FromStr for OpenerPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "unsafe-none" => Ok(OpenerPolicy::UnsafeNone),
+            "same-origin-allow-popups" => Ok(OpenerPolicy::SameOriginAllowPopups),
+            "same-origin" => Ok(OpenerPolicy::SameOrigin),
+            "noopener-allow-popups" => Ok(OpenerPolicy::NoopenerAllowPopups),
+            s => {
+                let msg = format!("Unknown Cross-Origin-Opener-Policy: {}", s);
+                Err(Error::from_str(StatusCode::BadRequest, msg))
+            }
+        }
+    }
+}
+
+impl Display for OpenerPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut coop = CrossOriginOpenerPolicy::new(OpenerPolicy::SameOriginAllowPopups);
+        coop.set_report_only(true);
+
+        let mut headers = Headers::new();
+        coop.apply(&mut headers);
+        assert_eq!(
+            headers[CROSS_ORIGIN_OPENER_POLICY_REPORT_ONLY],
+            "same-origin-allow-popups"
+        );
+
+        let parsed = CrossOriginOpenerPolicy::from_headers(headers)?.unwrap();
+        assert_eq!(parsed, coop);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = [
+            "same-site",
+            "same-origin; report-to=endpoint",
+            "same-origin;A=1",
+        ];
+        for value in &values {
+            let mut headers = Headers::new();
+            headers.insert(CROSS_ORIGIN_OPENER_POLICY, *value);
+            let err = CrossOriginOpenerPolicy::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::headers::{
+    HeaderName, HeaderValue, Headers, ToHeaderValues, CROSS_ORIGIN_RESOURCE_POLICY,
+};
+use crate::{Error, StatusCode};
+
+use std::fmt::{self, Display};
+use std::option;
+use std::str::FromStr;
+
+/// Restrict which sites can load a resource in `no-cors` mode.
+///
+/// # Specifications
+///
+/// - [Fetch, section 3.7: Cross-Origin-Resource-Policy header](https://fetch.spec.whatwg.org/#cross-origin-resource-policy-header)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::security::CrossOriginResourcePolicy;
+///
+/// let mut res = Response::new(200);
+/// CrossOriginResourcePolicy::SameSite.apply(&mut res);
+/// assert_eq!(res["Cross-Origin-Resource-Policy"], "same-site");
+///
+/// let corp = CrossOriginResourcePolicy::from_headers(res)?.unwrap();
+/// assert_eq!(corp, CrossOriginResourcePolicy::SameSite);
+/// #
+/// # Ok(()) }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CrossOriginResourcePolicy {
+    /// Only allow requests from the same site.
+    SameSite,
+    /// Only allow requests from the same origin.
+    SameOrigin,
+    /// Allow requests from any origin.
+    CrossOrigin,
+}
+
+impl CrossOriginResourcePolicy {
+    /// Create a new instance from headers.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header names an unknown policy.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(CROSS_ORIGIN_RESOURCE_POLICY) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If we successfully parsed the header then there's always at least one
+        // entry. We want the last entry.
+        let value = headers.iter().last().unwrap();
+        value.as_str().parse().map(Some)
+    }
+
+    /// Sets the `Cross-Origin-Resource-Policy` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers
+            .as_mut()
+            .insert(CROSS_ORIGIN_RESOURCE_POLICY, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        CROSS_ORIGIN_RESOURCE_POLICY
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(self.as_str().into()) }
+    }
+
+    /// Get the name of the policy as it's used in headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrossOriginResourcePolicy::SameSite => "same-site",
+            CrossOriginResourcePolicy::SameOrigin => "same-origin",
+            CrossOriginResourcePolicy::CrossOrigin => "cross-origin",
+        }
+    }
+}
+
+impl FromStr for CrossOriginResourcePolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "same-site" => Ok(CrossOriginResourcePolicy::SameSite),
+            "same-origin" => Ok(CrossOriginResourcePolicy::SameOrigin),
+            "cross-origin" => Ok(CrossOriginResourcePolicy::CrossOrigin),
+            s => {
+                let msg = format!("Unknown Cross-Origin-Resource-Policy: {}", s);
+                Err(Error::from_str(StatusCode::BadRequest, msg))
+            }
+        }
+    }
+}
+
+impl Display for CrossOriginResourcePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ToHeaderValues for CrossOriginResourcePolicy {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
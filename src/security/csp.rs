@@ -1,12 +1,20 @@
-use crate::Headers;
+use crate::headers::{
+    HeaderName, HeaderValue, Headers, ToHeaderValues, CONTENT_SECURITY_POLICY,
+    CONTENT_SECURITY_POLICY_REPORT_ONLY,
+};
+use crate::{Error, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::collections::BTreeMap;
 use std::fmt;
+use std::option;
+use std::str::FromStr;
 
 /// Define source value
 ///
 /// [read more](https://content-security-policy.com)
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Source {
     /// Set source `'self'`
     SameOrigin,
@@ -30,32 +38,78 @@ pub enum Source {
     StrictDynamic,
     /// Set source `'unsafe-eval'`
     UnsafeEval,
+    /// Set source `'unsafe-hashes'`
+    UnsafeHashes,
+    /// Set source `'report-sample'`
+    ReportSample,
     /// Set source `*`
     Wildcard,
+    /// Set a scheme source, such as `wss:`
+    Scheme(String),
+    /// Set a host source, such as `https://*.example.com:443/path`
+    Host(String),
+    /// Set a nonce source, such as `'nonce-2726c7f26c'`
+    Nonce(String),
+    /// Set a SHA-256 hash source, such as `'sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU='`
+    Sha256(String),
+    /// Set a SHA-384 hash source, such as `'sha384-OLBgp1GsljhM2TJ+sbHjaiH9txEUvgdDTAzHv2P24donTt6/529l+9Ua0vFImLlb'`
+    Sha384(String),
+    /// Set a SHA-512 hash source, such as `'sha512-z4PhNX7vuL3xVChQ1m2AB9Yg5AULVxXcg/SpIdNs6c5H0NE8XYXysP+DGNKHfuwvY7kxvUdBeoGlODJ6+SfaPg=='`
+    Sha512(String),
+    /// A quoted source expression which isn't otherwise recognized
+    Other(String),
+}
+
+impl Source {
+    /// Create a nonce source from a base64 encoded nonce.
+    ///
+    /// See [`CspNonce`](struct.CspNonce.html) to generate a nonce for each
+    /// response.
+    pub fn nonce(nonce: &str) -> Self {
+        assert!(is_base64(nonce), "CSP nonces should be base64 encoded");
+        Source::Nonce(format!("'nonce-{}'", nonce))
+    }
+
+    /// Create a SHA-256 hash source which allows an inline script or style
+    /// with the given contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http_types::security::Source;
+    ///
+    /// let source = Source::sha256("alert('Hello, Chashu!');");
+    /// assert_eq!(source.to_string(), "'sha256-Me+E+HZtTRZMVa4tXOgk1xRyGzo/NSkyLCrtg58PEik='");
+    /// ```
+    pub fn sha256(contents: impl AsRef<[u8]>) -> Self {
+        let hash = base64::encode(Sha256::digest(contents.as_ref()));
+        Source::Sha256(format!("'sha256-{}'", hash))
+    }
+
+    /// Create a SHA-384 hash source which allows an inline script or style
+    /// with the given contents.
+    pub fn sha384(contents: impl AsRef<[u8]>) -> Self {
+        let hash = base64::encode(Sha384::digest(contents.as_ref()));
+        Source::Sha384(format!("'sha384-{}'", hash))
+    }
+
+    /// Create a SHA-512 hash source which allows an inline script or style
+    /// with the given contents.
+    pub fn sha512(contents: impl AsRef<[u8]>) -> Self {
+        let hash = base64::encode(Sha512::digest(contents.as_ref()));
+        Source::Sha512(format!("'sha512-{}'", hash))
+    }
 }
 
 impl fmt::Display for Source {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
-            Source::SameOrigin => write!(f, "'self'"),
-            Source::Src => write!(f, "'src'"),
-            Source::None => write!(f, "'none'"),
-            Source::UnsafeInline => write!(f, "'unsafe-inline'"),
-            Source::Data => write!(f, "data:"),
-            Source::Mediastream => write!(f, "mediastream:"),
-            Source::Https => write!(f, "https:"),
-            Source::Blob => write!(f, "blob:"),
-            Source::Filesystem => write!(f, "filesystem:"),
-            Source::StrictDynamic => write!(f, "'strict-dynamic'"),
-            Source::UnsafeEval => write!(f, "'unsafe-eval'"),
-            Source::Wildcard => write!(f, "*"),
-        }
+        f.write_str(self.as_ref())
     }
 }
 
 impl AsRef<str> for Source {
     fn as_ref(&self) -> &str {
-        match *self {
+        match self {
             Source::SameOrigin => "'self'",
             Source::Src => "'src'",
             Source::None => "'none'",
@@ -67,11 +121,97 @@ impl AsRef<str> for Source {
             Source::Filesystem => "filesystem:",
             Source::StrictDynamic => "'strict-dynamic'",
             Source::UnsafeEval => "'unsafe-eval'",
+            Source::UnsafeHashes => "'unsafe-hashes'",
+            Source::ReportSample => "'report-sample'",
             Source::Wildcard => "*",
+            Source::Scheme(source)
+            | Source::Host(source)
+            | Source::Nonce(source)
+            | Source::Sha256(source)
+            | Source::Sha384(source)
+            | Source::Sha512(source)
+            | Source::Other(source) => source,
         }
     }
 }
 
+impl FromStr for Source {
+    type Err = Error;
+
+    /// Parse a source expression. Keywords and schemes are matched
+    /// case-insensitively.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty()
+            || !s.is_ascii()
+            || s.contains(|c: char| c.is_ascii_whitespace() || c == ';' || c == ',')
+        {
+            let msg = format!("Invalid CSP source: {}", s);
+            return Err(Error::from_str(StatusCode::BadRequest, msg));
+        }
+
+        let keywords = [
+            Source::SameOrigin,
+            Source::Src,
+            Source::None,
+            Source::UnsafeInline,
+            Source::Data,
+            Source::Mediastream,
+            Source::Https,
+            Source::Blob,
+            Source::Filesystem,
+            Source::StrictDynamic,
+            Source::UnsafeEval,
+            Source::UnsafeHashes,
+            Source::ReportSample,
+            Source::Wildcard,
+        ];
+        if let Some(source) = keywords.iter().find(|k| k.as_ref().eq_ignore_ascii_case(s)) {
+            return Ok(source.clone());
+        }
+
+        if let Some(expression) = s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+            let (prefix, value) = match expression.find('-') {
+                Some(index) => (&expression[..index], &expression[index + 1..]),
+                None => (expression, ""),
+            };
+            let source = s.to_owned();
+            let source = match prefix.to_ascii_lowercase().as_str() {
+                _ if !is_base64(value) => Source::Other(source),
+                "nonce" => Source::Nonce(source),
+                "sha256" => Source::Sha256(source),
+                "sha384" => Source::Sha384(source),
+                "sha512" => Source::Sha512(source),
+                _ => Source::Other(source),
+            };
+            return Ok(source);
+        } else if s.starts_with('\'') {
+            return Ok(Source::Other(s.to_owned()));
+        }
+        match s.strip_suffix(':') {
+            Some(scheme) if is_scheme(scheme) => Ok(Source::Scheme(s.to_ascii_lowercase())),
+            _ => Ok(Source::Host(s.to_owned())),
+        }
+    }
+}
+
+/// Returns `true` if the string is a valid URL scheme.
+fn is_scheme(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    matches!(bytes.next(), Some(b) if b.is_ascii_alphabetic())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b"+-.".contains(&b))
+}
+
+/// Returns `true` if the string is a valid base64 or base64url value.
+fn is_base64(s: &str) -> bool {
+    let value = s.trim_end_matches('=');
+    !value.is_empty()
+        && s.len() - value.len() <= 2
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"+/-_".contains(&b))
+}
+
 /// Define `report-to` directive value
 ///
 /// [MDN | report-to](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-to)
@@ -125,20 +265,18 @@ pub struct ReportToEndpoint {
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContentSecurityPolicy {
-    policy: Vec<String>,
     report_only_flag: bool,
-    directives: HashMap<String, Vec<String>>,
+    directives: BTreeMap<String, Vec<String>>,
 }
 
 impl Default for ContentSecurityPolicy {
     /// Sets the Content-Security-Policy default to "script-src 'self'; object-src 'self'"
     fn default() -> Self {
-        let policy = String::from("script-src 'self'; object-src 'self'");
-        ContentSecurityPolicy {
-            policy: vec![policy],
-            report_only_flag: false,
-            directives: HashMap::new(),
-        }
+        let mut policy = Self::new();
+        policy
+            .script_src(Source::SameOrigin)
+            .object_src(Source::SameOrigin);
+        policy
     }
 }
 
@@ -146,17 +284,221 @@ impl ContentSecurityPolicy {
     /// Create a new instance.
     pub fn new() -> Self {
         Self {
-            policy: Vec::new(),
             report_only_flag: false,
-            directives: HashMap::new(),
+            directives: BTreeMap::new(),
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// The `Content-Security-Policy` header is used if it's present, and the
+    /// `Content-Security-Policy-Report-Only` header otherwise. If the header
+    /// contains several policies, they're combined with
+    /// [`intersect`](#method.intersect).
+    ///
+    /// Parsing follows the CSP specification: directives with invalid names
+    /// and repeated directives are skipped, and unknown directives are kept.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = headers.as_ref();
+        match Self::parse_header(headers, CONTENT_SECURITY_POLICY) {
+            Some(policy) => Ok(Some(policy)),
+            None => Self::from_report_only_headers(headers),
+        }
+    }
+
+    /// Create a new instance from the `Content-Security-Policy-Report-Only`
+    /// header, ignoring any `Content-Security-Policy` header.
+    pub fn from_report_only_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let policy = Self::parse_header(headers.as_ref(), CONTENT_SECURITY_POLICY_REPORT_ONLY);
+        Ok(policy.map(|mut policy| {
+            policy.report_only_flag = true;
+            policy
+        }))
+    }
+
+    fn parse_header(headers: &Headers, name: HeaderName) -> Option<Self> {
+        let values = headers.get(name)?;
+        let mut output: Option<Self> = None;
+        for policy in values.iter().flat_map(|value| value.as_str().split(',')) {
+            let policy = Self::parse(policy);
+            match output.as_mut() {
+                Some(output) => {
+                    output.intersect(&policy);
+                }
+                None => output = Some(policy),
+            }
+        }
+        output
+    }
+
+    /// Parse a single serialized policy.
+    fn parse(s: &str) -> Self {
+        let mut policy = Self::new();
+        for directive in s.split(';') {
+            let mut tokens = directive.split_ascii_whitespace();
+            let name = match tokens.next() {
+                Some(name) => name.to_ascii_lowercase(),
+                None => continue,
+            };
+            let valid = name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-');
+            if !valid || policy.directives.contains_key(&name) {
+                continue;
+            }
+            let values = tokens.map(ToOwned::to_owned).collect();
+            policy.directives.insert(name, values);
         }
+        policy
     }
 
     fn insert_directive<T: AsRef<str>>(&mut self, directive: &str, source: T) {
-        let directive = String::from(directive);
-        let directives = self.directives.entry(directive).or_insert_with(Vec::new);
-        let source: String = source.as_ref().to_string();
-        directives.push(source);
+        let source = source.as_ref();
+        assert!(source.is_ascii(), "CSP sources should be ASCII");
+        let directives = self.directives.entry(directive.to_owned()).or_default();
+        if !directives.iter().any(|s| s == source) {
+            directives.push(source.to_owned());
+        }
+    }
+
+    /// Get the values of a directive, if it's set.
+    pub fn directive(&self, name: &str) -> Option<&[String]> {
+        self.directives
+            .get(&name.to_ascii_lowercase())
+            .map(|values| values.as_slice())
+    }
+
+    /// Get the sources of a directive, if it's set.
+    ///
+    /// This is meant for directives which take a source list, such as
+    /// `script-src`.
+    pub fn sources(&self, name: &str) -> Option<Vec<Source>> {
+        let values = self.directive(name)?;
+        Some(
+            values
+                .iter()
+                .filter_map(|value| value.parse().ok())
+                .collect(),
+        )
+    }
+
+    /// An iterator visiting all directives and their values, ordered by name.
+    pub fn directives(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.directives
+            .iter()
+            .map(|(name, values)| (name.as_str(), values.as_slice()))
+    }
+
+    /// Remove a directive, returning its values if it was set.
+    pub fn remove_directive(&mut self, name: &str) -> Option<Vec<String>> {
+        self.directives.remove(&name.to_ascii_lowercase())
+    }
+
+    /// Returns `true` if the policy is only reported, and not enforced.
+    pub fn is_report_only(&self) -> bool {
+        self.report_only_flag
+    }
+
+    /// Allow what's allowed by either this or another policy.
+    ///
+    /// Source lists allow the sources of either policy, taking the
+    /// `default-src` fallback of fetch directives into account, so merging
+    /// `script-src` into a policy with only `default-src` keeps the sources of
+    /// `default-src` for scripts. Other directives which are set in both
+    /// policies keep the values of either. Directives which don't apply in
+    /// one of the policies are copied from the other.
+    pub fn merge(&mut self, other: &Self) -> &mut Self {
+        let names: Vec<String> = self
+            .directives
+            .keys()
+            .chain(other.directives.keys())
+            .cloned()
+            .collect();
+
+        let mut directives = BTreeMap::new();
+        for name in names {
+            if directives.contains_key(&name) {
+                continue;
+            }
+            let (a, b) = match is_source_list(&name) {
+                true => (self.effective(&name), other.effective(&name)),
+                false => (
+                    self.directives.get(&name).map(|values| values.as_slice()),
+                    other.directives.get(&name).map(|values| values.as_slice()),
+                ),
+            };
+            let mut values: Vec<String> = vec![];
+            for value in a.into_iter().chain(b).flatten() {
+                if !values.contains(value) {
+                    values.push(value.clone());
+                }
+            }
+            // A source list which allows something can't also allow nothing.
+            if is_source_list(&name) && values.len() > 1 {
+                values.retain(|value| !value.eq_ignore_ascii_case("'none'"));
+            }
+            directives.insert(name, values);
+        }
+        self.directives = directives;
+        self
+    }
+
+    /// Restrict this policy to what's allowed by both this and another
+    /// policy.
+    ///
+    /// Source lists only keep the sources which both policies allow, taking
+    /// the `default-src` fallback of fetch directives into account. Sources are
+    /// compared exactly, except that `*` allows any host source, `'self'`, and
+    /// the network schemes `http:`, `https:`, `ws:` and `wss:`. Other
+    /// directives are kept from this policy if they're set in both.
+    pub fn intersect(&mut self, other: &Self) -> &mut Self {
+        let names: Vec<String> = self
+            .directives
+            .keys()
+            .chain(other.directives.keys())
+            .cloned()
+            .collect();
+
+        let mut directives = BTreeMap::new();
+        for name in names {
+            if directives.contains_key(&name) {
+                continue;
+            }
+            let values = match (self.effective(&name), other.effective(&name)) {
+                (Some(a), Some(b)) if is_source_list(&name) => intersect_sources(a, b),
+                (Some(a), Some(b)) if name == "sandbox" => a
+                    .iter()
+                    .filter(|value| b.contains(value))
+                    .cloned()
+                    .collect(),
+                (Some(a), _) if self.directives.contains_key(&name) => a.to_vec(),
+                (_, Some(b)) => b.to_vec(),
+                (Some(a), None) => a.to_vec(),
+                (None, None) => continue,
+            };
+            directives.insert(name, values);
+        }
+        self.directives = directives;
+        self
+    }
+
+    /// Get the values which apply to a directive, following the fallback
+    /// chain of fetch directives up to `default-src`.
+    fn effective(&self, name: &str) -> Option<&[String]> {
+        if let Some(values) = self.directives.get(name) {
+            return Some(values);
+        }
+        let fallbacks: &[&str] = match name {
+            "script-src-elem" | "script-src-attr" => &["script-src", "default-src"],
+            "style-src-elem" | "style-src-attr" => &["style-src", "default-src"],
+            "worker-src" => &["child-src", "script-src", "default-src"],
+            "frame-src" => &["child-src", "default-src"],
+            "default-src" => &[],
+            name if name.ends_with("-src") => &["default-src"],
+            _ => &[],
+        };
+        fallbacks
+            .iter()
+            .find_map(|name| self.directives.get(*name))
+            .map(|values| values.as_slice())
     }
 
     /// Defines the Content-Security-Policy `base-uri` directive
@@ -171,8 +513,9 @@ impl ContentSecurityPolicy {
     ///
     /// [MDN | block-all-mixed-content](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/block-all-mixed-content)
     pub fn block_all_mixed_content(&mut self) -> &mut Self {
-        let policy = String::from("block-all-mixed-content");
-        self.policy.push(policy);
+        self.directives
+            .entry(String::from("block-all-mixed-content"))
+            .or_default();
         self
     }
 
@@ -232,6 +575,14 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `manifest-src` directive
+    ///
+    /// [MDN | manifest-src](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/manifest-src)
+    pub fn manifest_src<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("manifest-src", source);
+        self
+    }
+
     /// Defines the Content-Security-Policy `media-src` directive
     ///
     /// [MDN | media-src](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/media-src)
@@ -240,6 +591,14 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `navigate-to` directive
+    ///
+    /// [MDN | navigate-to](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/navigate-to)
+    pub fn navigate_to<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("navigate-to", source);
+        self
+    }
+
     /// Defines the Content-Security-Policy `object-src` directive
     ///
     /// [MDN | object-src](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/object-src)
@@ -256,6 +615,14 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `prefetch-src` directive
+    ///
+    /// [MDN | prefetch-src](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/prefetch-src)
+    pub fn prefetch_src<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("prefetch-src", source);
+        self
+    }
+
     /// Defines the Content-Security-Policy `require-sri-for` directive
     ///
     /// [MDN | require-sri-for](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/require-sri-for)
@@ -274,14 +641,15 @@ impl ContentSecurityPolicy {
 
     /// Defines the Content-Security-Policy `report-to` directive
     ///
+    /// This serializes the deprecated `Report-To` groups into the policy. Use
+    /// [`report_to_endpoint`](#method.report_to_endpoint) with a
+    /// [`ReportingEndpoints`](struct.ReportingEndpoints.html) header instead.
+    ///
     /// [MDN | report-to](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-to)
     pub fn report_to(&mut self, endpoints: Vec<ReportTo>) -> &mut Self {
         for endpoint in endpoints.iter() {
             match serde_json::to_string(&endpoint) {
-                Ok(json) => {
-                    let policy = format!("report-to {}", json);
-                    self.policy.push(policy);
-                }
+                Ok(json) => self.insert_directive("report-to", json),
                 Err(error) => {
                     println!("{:?}", error);
                 }
@@ -290,6 +658,30 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `report-to` directive, naming an
+    /// endpoint of the `Reporting-Endpoints` header
+    ///
+    /// A policy can only report to a single endpoint, so this replaces any
+    /// endpoint set before.
+    ///
+    /// [MDN | report-to](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-to)
+    pub fn report_to_endpoint<T: AsRef<str>>(&mut self, name: T) -> &mut Self {
+        self.directives.remove("report-to");
+        self.insert_directive("report-to", name);
+        self
+    }
+
+    /// Defines the Content-Security-Policy `require-trusted-types-for` directive
+    ///
+    /// The only sink group is `'script'`, which requires Trusted Types for
+    /// DOM XSS injection sinks.
+    ///
+    /// [MDN | require-trusted-types-for](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/require-trusted-types-for)
+    pub fn require_trusted_types_for<T: AsRef<str>>(&mut self, sink: T) -> &mut Self {
+        self.insert_directive("require-trusted-types-for", sink);
+        self
+    }
+
     /// Defines the Content-Security-Policy `sandbox` directive
     ///
     /// [MDN | sandbox](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/sandbox)
@@ -306,6 +698,22 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `script-src-attr` directive
+    ///
+    /// [MDN | script-src-attr](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/script-src-attr)
+    pub fn script_src_attr<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("script-src-attr", source);
+        self
+    }
+
+    /// Defines the Content-Security-Policy `script-src-elem` directive
+    ///
+    /// [MDN | script-src-elem](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/script-src-elem)
+    pub fn script_src_elem<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("script-src-elem", source);
+        self
+    }
+
     /// Defines the Content-Security-Policy `style-src` directive
     ///
     /// [MDN | style-src](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/style-src)
@@ -314,12 +722,40 @@ impl ContentSecurityPolicy {
         self
     }
 
+    /// Defines the Content-Security-Policy `style-src-attr` directive
+    ///
+    /// [MDN | style-src-attr](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/style-src-attr)
+    pub fn style_src_attr<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("style-src-attr", source);
+        self
+    }
+
+    /// Defines the Content-Security-Policy `style-src-elem` directive
+    ///
+    /// [MDN | style-src-elem](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/style-src-elem)
+    pub fn style_src_elem<T: AsRef<str>>(&mut self, source: T) -> &mut Self {
+        self.insert_directive("style-src-elem", source);
+        self
+    }
+
+    /// Defines the Content-Security-Policy `trusted-types` directive
+    ///
+    /// Each call allows a Trusted Types policy name, or a keyword such as
+    /// `'allow-duplicates'`.
+    ///
+    /// [MDN | trusted-types](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/trusted-types)
+    pub fn trusted_types<T: AsRef<str>>(&mut self, policy: T) -> &mut Self {
+        self.insert_directive("trusted-types", policy);
+        self
+    }
+
     /// Defines the Content-Security-Policy `upgrade-insecure-requests` directive
     ///
     /// [MDN | upgrade-insecure-requests](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/upgrade-insecure-requests)
     pub fn upgrade_insecure_requests(&mut self) -> &mut Self {
-        let policy = String::from("upgrade-insecure-requests");
-        self.policy.push(policy);
+        self.directives
+            .entry(String::from("upgrade-insecure-requests"))
+            .or_default();
         self
     }
 
@@ -337,23 +773,281 @@ impl ContentSecurityPolicy {
         self
     }
 
-    /// Create and retrieve the policy value
-    fn value(&mut self) -> String {
-        for (directive, sources) in &self.directives {
-            let policy = format!("{} {}", directive, sources.join(" "));
-            self.policy.push(policy);
-            self.policy.sort();
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        if self.report_only_flag {
+            CONTENT_SECURITY_POLICY_REPORT_ONLY
+        } else {
+            CONTENT_SECURITY_POLICY
         }
-        self.policy.join("; ")
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let policy: Vec<String> = self
+            .directives
+            .iter()
+            .map(|(directive, sources)| match sources.is_empty() {
+                true => directive.clone(),
+                false => format!("{} {}", directive, sources.join(" ")),
+            })
+            .collect();
+        let output = policy.join("; ");
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
     }
 
     /// Sets the `Content-Security-Policy` (CSP) HTTP header to prevent cross-site injections
-    pub fn apply(&mut self, mut headers: impl AsMut<Headers>) {
-        let name = if self.report_only_flag {
-            "Content-Security-Policy-Report-Only"
-        } else {
-            "Content-Security-Policy"
-        };
-        headers.as_mut().insert(name, self.value());
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+}
+
+impl ToHeaderValues for ContentSecurityPolicy {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+/// Returns `true` if the directive takes a source list.
+fn is_source_list(name: &str) -> bool {
+    ["-src", "-src-elem", "-src-attr"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+        || ["base-uri", "form-action", "frame-ancestors", "navigate-to"].contains(&name)
+}
+
+/// Keep the sources which are allowed by both source lists.
+fn intersect_sources(a: &[String], b: &[String]) -> Vec<String> {
+    let parse = |values: &[String]| -> Vec<Source> {
+        values
+            .iter()
+            .filter_map(|value| value.parse().ok())
+            .collect()
+    };
+    let (a, b) = (parse(a), parse(b));
+
+    // `*` matches any URL with a network scheme, which includes the origin of
+    // the document itself.
+    let wildcard_allows = |source: &Source| match source {
+        Source::Host(_) | Source::SameOrigin | Source::Https => true,
+        Source::Scheme(scheme) => ["http:", "https:", "ws:", "wss:"].contains(&scheme.as_str()),
+        _ => false,
+    };
+    let allows = |list: &[Source], source: &Source| {
+        list.contains(source) || (wildcard_allows(source) && list.contains(&Source::Wildcard))
+    };
+    let mut output: Vec<String> = vec![];
+    for source in a
+        .iter()
+        .filter(|s| allows(&b, s))
+        .chain(b.iter().filter(|s| allows(&a, s)))
+    {
+        if *source != Source::None && !output.iter().any(|s| s == source.as_ref()) {
+            output.push(source.to_string());
+        }
+    }
+    if output.is_empty() {
+        output.push(Source::None.to_string());
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(
+            CONTENT_SECURITY_POLICY,
+            concat!(
+                "Script-Src 'self' https://cdn.example.com wss: 'wasm-unsafe-eval'; ",
+                "script-src *; upgrade-insecure-requests;; fenced-frame-src 'none'; b@d x"
+            ),
+        );
+
+        let policy = ContentSecurityPolicy::from_headers(headers)?.unwrap();
+        assert!(!policy.is_report_only());
+        assert_eq!(
+            policy.sources("script-src"),
+            Some(vec![
+                Source::SameOrigin,
+                Source::Host("https://cdn.example.com".into()),
+                Source::Scheme("wss:".into()),
+                Source::Other("'wasm-unsafe-eval'".into()),
+            ])
+        );
+        assert_eq!(policy.directive("upgrade-insecure-requests"), Some(&[][..]));
+        assert_eq!(
+            policy.directive("fenced-frame-src"),
+            Some(&["'none'".to_owned()][..])
+        );
+        assert_eq!(policy.directives().count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn nonce_and_hash_sources() -> crate::Result<()> {
+        assert_eq!(
+            Source::nonce("2726c7f26c").to_string(),
+            "'nonce-2726c7f26c'"
+        );
+        let source: Source = "'NONCE-2726c7f26c'".parse()?;
+        assert_eq!(source, Source::Nonce("'NONCE-2726c7f26c'".into()));
+
+        let source = Source::sha384("");
+        assert_eq!(source, source.to_string().parse()?);
+        assert!(matches!(source, Source::Sha384(_)));
+        let source: Source = Source::sha512("").to_string().parse()?;
+        assert!(matches!(source, Source::Sha512(_)));
+
+        let source: Source = "'sha256-%%%'".parse()?;
+        assert_eq!(source, Source::Other("'sha256-%%%'".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn intersect_follows_fallback_chain() {
+        let mut upstream = ContentSecurityPolicy::new();
+        upstream
+            .script_src_elem(Source::SameOrigin)
+            .script_src_elem("cdn.example.com")
+            .worker_src(Source::Blob)
+            .require_trusted_types_for("'script'");
+        let baseline = ContentSecurityPolicy::parse(
+            "default-src 'none'; script-src cdn.example.com; child-src blob:; trusted-types default",
+        );
+
+        upstream.intersect(&baseline);
+        assert_eq!(
+            upstream.directive("script-src-elem"),
+            Some(&["cdn.example.com".to_owned()][..])
+        );
+        assert_eq!(
+            upstream.directive("worker-src"),
+            Some(&["blob:".to_owned()][..])
+        );
+        assert_eq!(
+            upstream.directive("trusted-types"),
+            Some(&["default".to_owned()][..])
+        );
+        assert_eq!(
+            upstream.directive("require-trusted-types-for"),
+            Some(&["'script'".to_owned()][..])
+        );
+    }
+
+    #[test]
+    fn report_only() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(CONTENT_SECURITY_POLICY_REPORT_ONLY, "default-src 'self'");
+
+        let policy = ContentSecurityPolicy::from_headers(&headers)?.unwrap();
+        assert!(policy.is_report_only());
+
+        policy.apply(&mut headers);
+        assert_eq!(
+            headers[CONTENT_SECURITY_POLICY_REPORT_ONLY],
+            "default-src 'self'"
+        );
+        assert!(ContentSecurityPolicy::from_report_only_headers(Headers::new())?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn multiple_policies_are_intersected() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(
+            CONTENT_SECURITY_POLICY,
+            "default-src 'self' a.com, script-src a.com b.com",
+        );
+        headers.append(CONTENT_SECURITY_POLICY, "img-src *");
+
+        let policy = ContentSecurityPolicy::from_headers(headers)?.unwrap();
+        assert_eq!(
+            policy.value(),
+            "default-src 'self' a.com; img-src 'self' a.com; script-src a.com"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn merge() {
+        let mut upstream = ContentSecurityPolicy::new();
+        upstream
+            .script_src(Source::None)
+            .img_src(Source::Data)
+            .sandbox("allow-scripts");
+        let mut baseline = ContentSecurityPolicy::new();
+        baseline
+            .script_src(Source::SameOrigin)
+            .object_src(Source::None)
+            .upgrade_insecure_requests();
+
+        upstream.merge(&baseline);
+        assert_eq!(
+            upstream.value(),
+            "img-src data:; object-src 'none'; sandbox allow-scripts; script-src 'self'; upgrade-insecure-requests"
+        );
+    }
+
+    #[test]
+    fn merge_follows_fallback_chain() {
+        let mut upstream = ContentSecurityPolicy::new();
+        upstream.default_src(Source::SameOrigin);
+        let mut baseline = ContentSecurityPolicy::new();
+        baseline
+            .script_src("cdn.example.com")
+            .style_src(Source::None);
+
+        upstream.merge(&baseline);
+        assert_eq!(
+            upstream.value(),
+            "default-src 'self'; script-src 'self' cdn.example.com; style-src 'self'"
+        );
+
+        let mut upstream = ContentSecurityPolicy::new();
+        upstream.script_src("cdn.example.com");
+        upstream.merge(&ContentSecurityPolicy::parse("default-src 'self'"));
+        assert_eq!(
+            upstream.value(),
+            "default-src 'self'; script-src cdn.example.com 'self'"
+        );
+    }
+
+    #[test]
+    fn intersect() {
+        let mut upstream = ContentSecurityPolicy::new();
+        upstream
+            .default_src(Source::SameOrigin)
+            .default_src("wss:")
+            .default_src(Source::Data)
+            .script_src(Source::SameOrigin)
+            .script_src(Source::UnsafeInline)
+            .script_src("cdn.example.com")
+            .sandbox("allow-scripts")
+            .sandbox("allow-forms");
+        let mut baseline = ContentSecurityPolicy::new();
+        baseline
+            .default_src(Source::Wildcard)
+            .script_src(Source::SameOrigin)
+            .script_src(Source::Wildcard)
+            .frame_ancestors(Source::None)
+            .sandbox("allow-forms");
+
+        upstream.intersect(&baseline);
+        assert_eq!(
+            upstream.value(),
+            concat!(
+                "default-src 'self' wss:; frame-ancestors 'none'; sandbox allow-forms; ",
+                "script-src 'self' cdn.example.com"
+            )
+        );
     }
 }
@@ -0,0 +1,96 @@
+use crate::security::Source;
+
+use rand::Rng;
+use std::fmt::{self, Display};
+
+/// A random nonce to allow inline scripts and styles in a
+/// `Content-Security-Policy`.
+///
+/// A new nonce should be generated for every response, so that attackers
+/// can't guess it. [`Response::csp_nonce`] stores the nonce in the response's
+/// extensions, so templates can read it while rendering the body.
+///
+/// [`Response::csp_nonce`]: ../struct.Response.html#method.csp_nonce
+///
+/// # Specifications
+///
+/// - [Content Security Policy Level 3, section 8.2: Nonce usage](https://w3c.github.io/webappsec-csp/#security-nonces)
+///
+/// # Examples
+///
+/// ```
+/// use http_types::security::{ContentSecurityPolicy, CspNonce};
+/// use http_types::{Response, StatusCode};
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// let nonce = res.csp_nonce().clone();
+/// res.set_body(format!("<script nonce=\"{}\">run()</script>", nonce));
+///
+/// let mut policy = ContentSecurityPolicy::new();
+/// policy.script_src(nonce.source());
+/// policy.apply(&mut res);
+///
+/// assert_eq!(res.ext().get::<CspNonce>(), Some(&nonce));
+/// assert_eq!(
+///     res["Content-Security-Policy"],
+///     format!("script-src 'nonce-{}'", nonce).as_str()
+/// );
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CspNonce {
+    value: String,
+}
+
+impl CspNonce {
+    /// Generate a new nonce from 128 random bits.
+    pub fn new() -> Self {
+        let bytes: [u8; 16] = rand::thread_rng().gen();
+        Self {
+            value: base64::encode(bytes),
+        }
+    }
+
+    /// Get the base64 encoded nonce, as used in `nonce` attributes.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Get the source which allows elements with this nonce.
+    pub fn source(&self) -> Source {
+        Source::nonce(&self.value)
+    }
+}
+
+impl Default for CspNonce {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for CspNonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl AsRef<str> for CspNonce {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smoke() {
+        let nonce = CspNonce::new();
+        assert_eq!(nonce.as_str().len(), 24);
+        assert_ne!(nonce, CspNonce::new());
+        assert_eq!(
+            nonce.source(),
+            format!("'nonce-{}'", nonce).parse().unwrap()
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use crate::mime::Mime;
+use crate::{Error, Request, Status, StatusCode};
+
+use serde::{Deserialize, Serialize};
+
+/// A report browsers send when a `Content-Security-Policy` is violated.
+///
+/// Browsers POST reports to the `report-uri` of a policy in the
+/// `application/csp-report` format, and to the endpoints named by `report-to`
+/// in the `application/reports+json` format. Both formats are parsed into the
+/// same type, which serializes to the body of the latter format.
+///
+/// # Specifications
+///
+/// - [Content Security Policy Level 3, section 5: Reporting](https://w3c.github.io/webappsec-csp/#reporting)
+/// - [Reporting API, section 2.3: Reports](https://w3c.github.io/reporting/#concept-reports)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::security::CspViolationReport;
+///
+/// let body = r#"{
+///     "csp-report": {
+///         "document-uri": "https://example.com/",
+///         "blocked-uri": "inline",
+///         "violated-directive": "script-src-elem",
+///         "original-policy": "script-src 'self'; report-uri /csp-reports",
+///         "disposition": "enforce"
+///     }
+/// }"#;
+/// let content_type = "application/csp-report".parse()?;
+/// let reports = CspViolationReport::from_slice(&content_type, body.as_bytes())?;
+///
+/// assert_eq!(reports[0].document_url(), "https://example.com/");
+/// assert_eq!(reports[0].effective_directive(), Some("script-src-elem"));
+/// assert!(!reports[0].is_report_only());
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CspViolationReport {
+    #[serde(rename = "documentURL", alias = "document-uri")]
+    document_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    referrer: Option<String>,
+    #[serde(
+        rename = "blockedURL",
+        alias = "blocked-uri",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    blocked_url: Option<String>,
+    #[serde(
+        alias = "effective-directive",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    effective_directive: Option<String>,
+    #[serde(rename = "violated-directive", default, skip_serializing)]
+    violated_directive: Option<String>,
+    #[serde(alias = "original-policy", default)]
+    original_policy: String,
+    #[serde(
+        alias = "source-file",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    source_file: Option<String>,
+    #[serde(
+        alias = "script-sample",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    sample: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    disposition: Option<String>,
+    #[serde(
+        alias = "status-code",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    status_code: Option<u16>,
+    #[serde(
+        alias = "line-number",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    line_number: Option<u32>,
+    #[serde(
+        alias = "column-number",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    column_number: Option<u32>,
+}
+
+/// The body of an `application/csp-report` request.
+#[derive(Deserialize)]
+struct LegacyReport {
+    #[serde(rename = "csp-report")]
+    report: CspViolationReport,
+}
+
+/// A report in the body of an `application/reports+json` request.
+#[derive(Deserialize)]
+struct Report {
+    #[serde(rename = "type")]
+    kind: String,
+    body: serde_json::Value,
+}
+
+impl CspViolationReport {
+    /// Parse the violation reports in a request body.
+    ///
+    /// Reports of other types in an `application/reports+json` body are
+    /// skipped. An `application/json` body is treated as an
+    /// `application/csp-report` body, as some browsers send that.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `415: Unsupported Media Type` is
+    /// returned if the content type isn't a report format, and one with a
+    /// status code of `422: Unprocessable Entity` if the body is malformed.
+    pub fn from_slice(content_type: &Mime, body: &[u8]) -> crate::Result<Vec<Self>> {
+        match content_type.essence() {
+            "application/csp-report" | "application/json" => {
+                let report: LegacyReport =
+                    serde_json::from_slice(body).status(StatusCode::UnprocessableEntity)?;
+                Ok(vec![report.report])
+            }
+            "application/reports+json" => {
+                let reports: Vec<Report> =
+                    serde_json::from_slice(body).status(StatusCode::UnprocessableEntity)?;
+                reports
+                    .into_iter()
+                    .filter(|report| report.kind == "csp-violation")
+                    .map(|report| {
+                        serde_json::from_value(report.body).status(StatusCode::UnprocessableEntity)
+                    })
+                    .collect()
+            }
+            essence => {
+                let msg = format!("Unsupported CSP report content type: {}", essence);
+                Err(Error::from_str(StatusCode::UnsupportedMediaType, msg))
+            }
+        }
+    }
+
+    /// Read the violation reports in the body of a request.
+    ///
+    /// This consumes the request body. See
+    /// [`from_slice`](#method.from_slice) for the errors that are returned.
+    pub async fn from_request(req: &mut Request) -> crate::Result<Vec<Self>> {
+        let content_type = match req.content_type() {
+            Some(content_type) => content_type,
+            None => {
+                let msg = "CSP reports require a Content-Type";
+                return Err(Error::from_str(StatusCode::UnsupportedMediaType, msg));
+            }
+        };
+        let body = req.body_bytes().await?;
+        Self::from_slice(&content_type, &body)
+    }
+
+    /// Get the URL of the document in which the violation occurred.
+    pub fn document_url(&self) -> &str {
+        &self.document_url
+    }
+
+    /// Get the referrer of the document in which the violation occurred.
+    pub fn referrer(&self) -> Option<&str> {
+        self.referrer.as_deref()
+    }
+
+    /// Get the URL of the resource which was blocked, or a keyword such as
+    /// `inline` or `eval`.
+    pub fn blocked_url(&self) -> Option<&str> {
+        self.blocked_url.as_deref()
+    }
+
+    /// Get the name of the directive which was violated, such as
+    /// `script-src-elem`.
+    ///
+    /// Older browsers only send the `violated-directive` field, which is used
+    /// as a fallback.
+    pub fn effective_directive(&self) -> Option<&str> {
+        match &self.effective_directive {
+            Some(directive) => Some(directive),
+            None => self
+                .violated_directive
+                .as_deref()
+                .and_then(|directive| directive.split_ascii_whitespace().next()),
+        }
+    }
+
+    /// Get the policy which was violated.
+    pub fn original_policy(&self) -> &str {
+        &self.original_policy
+    }
+
+    /// Get the URL of the script in which the violation occurred.
+    pub fn source_file(&self) -> Option<&str> {
+        self.source_file.as_deref()
+    }
+
+    /// Get the first characters of the inline script, style or event handler
+    /// which was blocked.
+    ///
+    /// This is only sent for source lists which contain `'report-sample'`.
+    pub fn sample(&self) -> Option<&str> {
+        self.sample.as_deref()
+    }
+
+    /// Returns `true` if the violated policy was only reported, and not
+    /// enforced.
+    pub fn is_report_only(&self) -> bool {
+        self.disposition.as_deref() == Some("report")
+    }
+
+    /// Get the status code of the response for the document.
+    pub fn status_code(&self) -> Option<u16> {
+        self.status_code
+    }
+
+    /// Get the line number in `source_file` at which the violation occurred.
+    pub fn line_number(&self) -> Option<u32> {
+        self.line_number
+    }
+
+    /// Get the column number in `source_file` at which the violation
+    /// occurred.
+    pub fn column_number(&self) -> Option<u32> {
+        self.column_number
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Method, Url};
+
+    #[test]
+    fn reports_json() -> crate::Result<()> {
+        let body = r#"[
+            {
+                "type": "csp-violation",
+                "age": 10,
+                "url": "https://example.com/",
+                "user_agent": "Mozilla/5.0",
+                "body": {
+                    "documentURL": "https://example.com/",
+                    "blockedURL": "https://evil.example/x.js",
+                    "effectiveDirective": "script-src-elem",
+                    "originalPolicy": "script-src 'self'; report-to csp",
+                    "sourceFile": "https://example.com/app.js",
+                    "disposition": "report",
+                    "statusCode": 200,
+                    "lineNumber": 12,
+                    "columnNumber": 4
+                }
+            },
+            { "type": "deprecation", "age": 0, "url": "https://example.com/", "body": {} }
+        ]"#;
+        let content_type = "application/reports+json".parse()?;
+        let reports = CspViolationReport::from_slice(&content_type, body.as_bytes())?;
+        assert_eq!(reports.len(), 1);
+
+        let report = &reports[0];
+        assert_eq!(report.blocked_url(), Some("https://evil.example/x.js"));
+        assert_eq!(report.effective_directive(), Some("script-src-elem"));
+        assert_eq!(report.source_file(), Some("https://example.com/app.js"));
+        assert_eq!(report.line_number(), Some(12));
+        assert!(report.is_report_only());
+
+        let json = serde_json::to_value(report)?;
+        assert_eq!(json["documentURL"], "https://example.com/");
+        assert_eq!(json["statusCode"], 200);
+        Ok(())
+    }
+
+    #[test]
+    fn csp_report() -> crate::Result<()> {
+        let body = r#"{
+            "csp-report": {
+                "document-uri": "https://example.com/",
+                "referrer": "",
+                "violated-directive": "style-src 'self'",
+                "original-policy": "style-src 'self'; report-uri /csp",
+                "script-sample": "color: red",
+                "line-number": 3
+            }
+        }"#;
+        let content_type = crate::mime::JSON;
+        let reports = CspViolationReport::from_slice(&content_type, body.as_bytes())?;
+        assert_eq!(reports[0].effective_directive(), Some("style-src"));
+        assert_eq!(reports[0].sample(), Some("color: red"));
+        assert_eq!(reports[0].line_number(), Some(3));
+        assert_eq!(reports[0].status_code(), None);
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn from_request() -> crate::Result<()> {
+        let mut req = Request::new(Method::Post, Url::parse("https://example.com/csp")?);
+        req.set_body(r#"{"csp-report": {"original-policy": "default-src 'none'"}}"#);
+        req.set_content_type("application/csp-report".parse()?);
+        let err = CspViolationReport::from_request(&mut req)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), 422);
+
+        let mut req = Request::new(Method::Post, Url::parse("https://example.com/csp")?);
+        req.set_body("{}");
+        req.set_content_type("text/plain".parse()?);
+        let err = CspViolationReport::from_request(&mut req)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), 415);
+        Ok(())
+    }
+}
@@ -0,0 +1,307 @@
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, STRICT_TRANSPORT_SECURITY};
+use crate::utils::{split_quoted, unquote};
+use crate::{Error, StatusCode};
+
+use std::fmt::Write;
+use std::option;
+use std::time::Duration;
+
+/// The minimum `max-age` accepted for the HSTS preload list, which is one year.
+const PRELOAD_MIN_MAX_AGE: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Tell browsers to only access the site over HTTPS.
+///
+/// Note that the header won’t tell users on HTTP to switch to HTTPS, it will
+/// tell HTTPS users to stick around. Defaults to a `max-age` of 60 days.
+///
+/// # Specifications
+///
+/// - [RFC 6797: HTTP Strict Transport Security](https://tools.ietf.org/html/rfc6797)
+/// - [HSTS Preload List Submission](https://hstspreload.org/#submission-requirements)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::security::Hsts;
+/// use std::time::Duration;
+///
+/// let mut hsts = Hsts::new();
+/// hsts.set_max_age(Duration::from_secs(63072000))
+///     .set_include_subdomains(true)
+///     .set_preload(true);
+/// hsts.validate()?;
+///
+/// let mut res = Response::new(200);
+/// hsts.apply(&mut res);
+/// assert_eq!(
+///     res["Strict-Transport-Security"],
+///     "max-age=63072000; includeSubDomains; preload"
+/// );
+///
+/// let hsts = Hsts::from_headers(res)?.unwrap();
+/// assert!(hsts.include_subdomains());
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Hsts {
+    max_age: Duration,
+    include_subdomains: bool,
+    preload: bool,
+}
+
+impl Hsts {
+    /// Create a new instance of `Hsts`.
+    pub fn new() -> Self {
+        Self {
+            max_age: Duration::from_secs(60 * 24 * 60 * 60),
+            include_subdomains: false,
+            preload: false,
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// Unknown directives are skipped. Only the first header is used if there
+    /// are several, as browsers do.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed, has no `max-age` directive, or has duplicate
+    /// directives.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(STRICT_TRANSPORT_SECURITY) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        // If we successfully parsed the header then there's always at least one
+        // entry. We want the first entry.
+        let value = headers.iter().next().unwrap();
+        let invalid = || {
+            let msg = format!("Invalid Strict-Transport-Security header: {}", value);
+            Error::from_str(StatusCode::BadRequest, msg)
+        };
+
+        let mut max_age = None;
+        let mut include_subdomains = false;
+        let mut preload = false;
+        let mut seen = vec![];
+        for directive in split_quoted(value.as_str(), ';') {
+            let (name, value) = match directive.find('=') {
+                Some(index) => (
+                    directive[..index].trim(),
+                    Some(directive[index + 1..].trim()),
+                ),
+                None => (directive, None),
+            };
+            let name = name.to_ascii_lowercase();
+            if seen.contains(&name) {
+                return Err(invalid());
+            }
+
+            match (name.as_str(), value) {
+                ("max-age", Some(value)) => {
+                    let value = match value.starts_with('"') {
+                        true => unquote(value).ok_or_else(invalid)?,
+                        false => value.to_owned(),
+                    };
+                    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+                        return Err(invalid());
+                    }
+                    let secs = value.parse().unwrap_or(u64::MAX);
+                    max_age = Some(Duration::from_secs(secs));
+                }
+                ("max-age", None) => return Err(invalid()),
+                ("includesubdomains", _) => include_subdomains = true,
+                ("preload", _) => preload = true,
+                _ => {}
+            }
+            seen.push(name);
+        }
+
+        let max_age = max_age.ok_or_else(invalid)?;
+        Ok(Some(Self {
+            max_age,
+            include_subdomains,
+            preload,
+        }))
+    }
+
+    /// Sets the `Strict-Transport-Security` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers
+            .as_mut()
+            .insert(STRICT_TRANSPORT_SECURITY, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        STRICT_TRANSPORT_SECURITY
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let mut output = format!("max-age={}", self.max_age.as_secs());
+        if self.include_subdomains {
+            write!(output, "; includeSubDomains").unwrap();
+        }
+        if self.preload {
+            write!(output, "; preload").unwrap();
+        }
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Check that the policy meets the requirements of the HSTS preload list.
+    ///
+    /// Policies which don't ask to be preloaded are always valid.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `500: Internal Server Error` is returned
+    /// if `preload` is set, but `max-age` is shorter than a year or
+    /// `includeSubDomains` isn't set.
+    pub fn validate(&self) -> crate::Result<()> {
+        if !self.preload {
+            return Ok(());
+        }
+        if self.max_age < PRELOAD_MIN_MAX_AGE {
+            let msg = "HSTS preload requires a max-age of at least one year";
+            return Err(Error::from_str(StatusCode::InternalServerError, msg));
+        }
+        if !self.include_subdomains {
+            let msg = "HSTS preload requires includeSubDomains";
+            return Err(Error::from_str(StatusCode::InternalServerError, msg));
+        }
+        Ok(())
+    }
+
+    /// Get how long browsers should only access the site over HTTPS.
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    /// Set how long browsers should only access the site over HTTPS.
+    ///
+    /// A `max-age` of zero tells browsers to forget the policy. Durations are
+    /// truncated to whole seconds.
+    pub fn set_max_age(&mut self, max_age: Duration) -> &mut Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Returns `true` if the policy also applies to subdomains.
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    /// Set whether the policy also applies to subdomains.
+    pub fn set_include_subdomains(&mut self, include_subdomains: bool) -> &mut Self {
+        self.include_subdomains = include_subdomains;
+        self
+    }
+
+    /// Returns `true` if the site asks to be included in the HSTS preload list.
+    pub fn preload(&self) -> bool {
+        self.preload
+    }
+
+    /// Set whether the site asks to be included in the HSTS preload list.
+    ///
+    /// Use [`validate`](#method.validate) to check whether the policy meets
+    /// the requirements for preloading.
+    pub fn set_preload(&mut self, preload: bool) -> &mut Self {
+        self.preload = preload;
+        self
+    }
+}
+
+impl Default for Hsts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToHeaderValues for Hsts {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn smoke() -> crate::Result<()> {
+        let mut hsts = Hsts::new();
+        let mut headers = Headers::new();
+        hsts.apply(&mut headers);
+        assert_eq!(headers[STRICT_TRANSPORT_SECURITY], "max-age=5184000");
+
+        hsts.set_include_subdomains(true);
+        hsts.apply(&mut headers);
+        let parsed = Hsts::from_headers(headers)?.unwrap();
+        assert_eq!(parsed, hsts);
+        Ok(())
+    }
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(
+            STRICT_TRANSPORT_SECURITY,
+            r#"max-age="31536000" ; INCLUDESUBDOMAINS; preload; ext=1"#,
+        );
+        headers.append(STRICT_TRANSPORT_SECURITY, "max-age=0");
+
+        let hsts = Hsts::from_headers(headers)?.unwrap();
+        assert_eq!(hsts.max_age(), Duration::from_secs(31536000));
+        assert!(hsts.include_subdomains());
+        assert!(hsts.preload());
+        assert!(hsts.validate().is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn validate_preload() {
+        let mut hsts = Hsts::new();
+        assert!(hsts.validate().is_ok());
+
+        hsts.set_preload(true).set_include_subdomains(true);
+        assert_eq!(hsts.validate().unwrap_err().status(), 500);
+
+        hsts.set_max_age(Duration::from_secs(31536000))
+            .set_include_subdomains(false);
+        assert_eq!(hsts.validate().unwrap_err().status(), 500);
+
+        hsts.set_include_subdomains(true);
+        assert!(hsts.validate().is_ok());
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = [
+            "includeSubDomains",
+            "max-age",
+            "max-age=-1",
+            "max-age=1; max-age=2",
+            r#"max-age="1"#,
+        ];
+        for value in &values {
+            let mut headers = Headers::new();
+            headers.insert(STRICT_TRANSPORT_SECURITY, *value);
+            let err = Hsts::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
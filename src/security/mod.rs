@@ -12,12 +12,34 @@
 //! ```
 
 use crate::headers::{HeaderName, HeaderValue, Headers};
+use crate::utils::{is_key, quote, split_quoted, unquote};
+pub use coep::{CrossOriginEmbedderPolicy, EmbedderPolicy};
+pub use coop::{CrossOriginOpenerPolicy, OpenerPolicy};
+pub use corp::CrossOriginResourcePolicy;
 pub use csp::{ContentSecurityPolicy, ReportTo, ReportToEndpoint, Source};
+pub use csp_nonce::CspNonce;
+pub use csp_report::CspViolationReport;
+pub use hsts::Hsts;
+pub use permissions_policy::{Allowlist, PermissionsPolicy};
+pub use reporting_endpoints::ReportingEndpoints;
+pub use security_headers::{AuditFinding, SecurityHeaders};
 
+mod coep;
+mod coop;
+mod corp;
 mod csp;
+mod csp_nonce;
+mod csp_report;
+mod hsts;
+mod permissions_policy;
+mod reporting_endpoints;
+mod security_headers;
 
 /// Apply a set of default protections.
 ///
+/// Use [`SecurityHeaders`](struct.SecurityHeaders.html) to configure which
+/// headers are set, and to keep headers which are already set.
+///
 // /// ## Examples
 // /// ```
 // /// use http_types::Response;
@@ -36,6 +58,32 @@ pub fn default(mut headers: impl AsMut<Headers>) {
     xss_filter(&mut headers);
 }
 
+/// Make documents cross-origin isolated, which browsers require for features
+/// such as `SharedArrayBuffer`.
+///
+/// This sets `Cross-Origin-Opener-Policy: same-origin`,
+/// `Cross-Origin-Embedder-Policy: require-corp` and
+/// `Cross-Origin-Resource-Policy: same-origin`. Cross-origin resources then
+/// need to allow being embedded, with CORS or a `Cross-Origin-Resource-Policy`
+/// header.
+///
+/// [read more](https://web.dev/coop-coep/)
+///
+/// ## Examples
+/// ```
+/// use http_types::{Response, StatusCode};
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// http_types::security::cross_origin_isolated(&mut res);
+/// assert_eq!(res["Cross-Origin-Opener-Policy"], "same-origin");
+/// assert_eq!(res["Cross-Origin-Embedder-Policy"], "require-corp");
+/// ```
+pub fn cross_origin_isolated(mut headers: impl AsMut<Headers>) {
+    CrossOriginOpenerPolicy::new(OpenerPolicy::SameOrigin).apply(&mut headers);
+    CrossOriginEmbedderPolicy::new(EmbedderPolicy::RequireCorp).apply(&mut headers);
+    CrossOriginResourcePolicy::SameOrigin.apply(&mut headers);
+}
+
 /// Disable browsers’ DNS prefetching by setting the `X-DNS-Prefetch-Control` header.
 ///
 /// [read more](https://helmetjs.github.io/docs/dns-prefetch-control/)
@@ -99,7 +147,7 @@ pub fn frameguard(mut headers: impl AsMut<Headers>, guard: Option<FrameOptions>)
 // /// ```
 #[inline]
 pub fn powered_by(mut headers: impl AsMut<Headers>, value: Option<HeaderValue>) {
-    let name = HeaderName::from_lowercase_str("X-Powered-By");
+    let name = HeaderName::from_lowercase_str("x-powered-by");
     match value {
         Some(value) => {
             headers.as_mut().insert(name, value);
@@ -113,7 +161,7 @@ pub fn powered_by(mut headers: impl AsMut<Headers>, value: Option<HeaderValue>)
 /// Sets the `Strict-Transport-Security` header to keep your users on `HTTPS`.
 ///
 /// Note that the header won’t tell users on HTTP to switch to HTTPS, it will tell HTTPS users to
-/// stick around. Defaults to 60 days.
+/// stick around. Defaults to 60 days. Use [`Hsts`](struct.Hsts.html) to configure the policy.
 ///
 /// [read more](https://helmetjs.github.io/docs/hsts/)
 ///
@@ -126,10 +174,8 @@ pub fn powered_by(mut headers: impl AsMut<Headers>, value: Option<HeaderValue>)
 // /// assert_eq!(headers["Strict-Transport-Security"], "max-age=5184000");
 // /// ```
 #[inline]
-pub fn hsts(mut headers: impl AsMut<Headers>) {
-    headers
-        .as_mut()
-        .insert("Strict-Transport-Security", "max-age=5184000");
+pub fn hsts(headers: impl AsMut<Headers>) {
+    Hsts::new().apply(headers);
 }
 
 /// Prevent browsers from trying to guess (“sniff”) the MIME type, which can have security
@@ -222,3 +268,34 @@ pub fn referrer_policy(mut headers: impl AsMut<Headers>, referrer: Option<Referr
     // See: https://w3c.github.io/webappsec-referrer-policy/#unknown-policy-values example #13
     headers.as_mut().append("Referrer-Policy", policy);
 }
+
+/// Parse a structured field token with an optional `report-to` parameter, as
+/// used by the `Cross-Origin-Opener-Policy` and `Cross-Origin-Embedder-Policy`
+/// headers. Other parameters are skipped.
+fn parse_policy_item(s: &str) -> Option<(&str, Option<String>)> {
+    let mut items = split_quoted(s, ';').into_iter();
+    let token = items.next()?;
+    let mut report_to = None;
+    for param in items {
+        let (key, value) = match param.find('=') {
+            Some(index) => (param[..index].trim(), Some(param[index + 1..].trim())),
+            None => (param, None),
+        };
+        if !is_key(key) {
+            return None;
+        }
+        if key == "report-to" {
+            report_to = Some(unquote(value?)?);
+        }
+    }
+    Some((token, report_to))
+}
+
+/// Serialize a structured field token with an optional `report-to`
+/// parameter.
+fn format_policy_item(token: &str, report_to: Option<&str>) -> String {
+    match report_to {
+        Some(endpoint) => format!("{}; report-to={}", token, quote(endpoint)),
+        None => token.to_owned(),
+    }
+}
@@ -0,0 +1,273 @@
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, PERMISSIONS_POLICY};
+use crate::utils::{is_key, is_printable, quote, split_quoted, unquote};
+use crate::{Error, StatusCode};
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::option;
+
+/// Control which browser features a document and its frames may use.
+///
+/// Every feature maps to an allowlist of origins. An empty allowlist disables
+/// the feature entirely.
+///
+/// # Specifications
+///
+/// - [Permissions Policy, section 5.2: The Permissions-Policy HTTP Header Field](https://w3c.github.io/webappsec-permissions-policy/#permissions-policy-http-header-field)
+/// - [RFC 8941: Structured Field Values for HTTP](https://tools.ietf.org/html/rfc8941)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::security::{Allowlist, PermissionsPolicy};
+///
+/// let mut policy = PermissionsPolicy::new();
+/// policy
+///     .deny("geolocation")
+///     .allow("camera", Allowlist::SameOrigin)
+///     .allow("camera", Allowlist::Origin("https://meet.example".into()))
+///     .allow("fullscreen", Allowlist::All);
+///
+/// let mut res = Response::new(200);
+/// policy.apply(&mut res);
+/// assert_eq!(
+///     res["Permissions-Policy"],
+///     r#"camera=(self "https://meet.example"), fullscreen=*, geolocation=()"#
+/// );
+///
+/// let policy = PermissionsPolicy::from_headers(res)?.unwrap();
+/// assert_eq!(policy.allowlist("geolocation"), Some(&[][..]));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct PermissionsPolicy {
+    features: BTreeMap<String, Vec<Allowlist>>,
+}
+
+/// An entry in the allowlist of a feature.
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Allowlist {
+    /// Allow all origins, `*`.
+    All,
+    /// Allow the origin of the document, `self`.
+    SameOrigin,
+    /// Allow the origin of a frame's `src` attribute, `src`.
+    Src,
+    /// Allow an origin, such as `https://example.com`.
+    Origin(String),
+}
+
+impl PermissionsPolicy {
+    /// Create a new instance of `PermissionsPolicy`.
+    pub fn new() -> Self {
+        Self {
+            features: BTreeMap::new(),
+        }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// If a feature is listed more than once, the last allowlist is used.
+    /// Unknown allowlist entries and parameters are skipped.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header isn't a valid structured field dictionary.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(PERMISSIONS_POLICY) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut output = Self::new();
+        for value in headers {
+            for member in split_quoted(value.as_str(), ',') {
+                let invalid = || {
+                    let msg = format!("Invalid Permissions-Policy member: {}", member);
+                    Error::from_str(StatusCode::BadRequest, msg)
+                };
+                let index = member.find('=').ok_or_else(invalid)?;
+                let (feature, value) = (&member[..index], &member[index + 1..]);
+                if !is_key(feature) {
+                    return Err(invalid());
+                }
+
+                // Parameters follow the value, separated by semicolons.
+                let value = split_quoted(value, ';').into_iter().next();
+                let items = match value {
+                    Some(value) if value.starts_with('(') => match value.strip_suffix(')') {
+                        Some(list) => split_quoted(&list[1..], ' '),
+                        None => return Err(invalid()),
+                    },
+                    Some(value) => vec![value],
+                    None => return Err(invalid()),
+                };
+
+                let mut allowlist = vec![];
+                for item in items {
+                    let entry = match item {
+                        "*" => Allowlist::All,
+                        "self" => Allowlist::SameOrigin,
+                        "src" => Allowlist::Src,
+                        item if item.starts_with('"') => {
+                            Allowlist::Origin(unquote(item).ok_or_else(invalid)?)
+                        }
+                        _ => continue,
+                    };
+                    if !allowlist.contains(&entry) {
+                        allowlist.push(entry);
+                    }
+                }
+                output.features.insert(feature.to_owned(), allowlist);
+            }
+        }
+        Ok(Some(output))
+    }
+
+    /// Sets the `Permissions-Policy` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(PERMISSIONS_POLICY, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        PERMISSIONS_POLICY
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self
+            .features
+            .iter()
+            .map(|(feature, allowlist)| match allowlist.as_slice() {
+                [Allowlist::All] => format!("{}=*", feature),
+                allowlist => {
+                    let entries: Vec<String> = allowlist.iter().map(|e| e.to_string()).collect();
+                    format!("{}=({})", feature, entries.join(" "))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Add an entry to the allowlist of a feature.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the feature name isn't a lowercase structured
+    /// field key, or if an origin contains characters other than printable
+    /// ASCII.
+    pub fn allow(&mut self, feature: &str, entry: Allowlist) -> &mut Self {
+        assert!(is_key(feature), "Invalid feature name: {}", feature);
+        if let Allowlist::Origin(origin) = &entry {
+            assert!(is_printable(origin), "Origins should be printable ASCII");
+        }
+        let allowlist = self.features.entry(feature.to_owned()).or_default();
+        if !allowlist.contains(&entry) {
+            allowlist.push(entry);
+        }
+        self
+    }
+
+    /// Disable a feature, clearing its allowlist.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the feature name isn't a lowercase structured
+    /// field key.
+    pub fn deny(&mut self, feature: &str) -> &mut Self {
+        assert!(is_key(feature), "Invalid feature name: {}", feature);
+        self.features.insert(feature.to_owned(), vec![]);
+        self
+    }
+
+    /// Get the allowlist of a feature, if it's set.
+    pub fn allowlist(&self, feature: &str) -> Option<&[Allowlist]> {
+        self.features
+            .get(feature)
+            .map(|allowlist| allowlist.as_slice())
+    }
+
+    /// Remove a feature, returning its allowlist if it was set.
+    pub fn remove(&mut self, feature: &str) -> Option<Vec<Allowlist>> {
+        self.features.remove(feature)
+    }
+
+    /// An iterator visiting all features and their allowlists, ordered by
+    /// name.
+    pub fn features(&self) -> impl Iterator<Item = (&str, &[Allowlist])> {
+        self.features
+            .iter()
+            .map(|(feature, allowlist)| (feature.as_str(), allowlist.as_slice()))
+    }
+}
+
+impl Display for Allowlist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Allowlist::All => f.write_str("*"),
+            Allowlist::SameOrigin => f.write_str("self"),
+            Allowlist::Src => f.write_str("src"),
+            Allowlist::Origin(origin) => f.write_str(&quote(origin)),
+        }
+    }
+}
+
+impl ToHeaderValues for PermissionsPolicy {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(
+            PERMISSIONS_POLICY,
+            r#"camera=(self  "https://a.example" unknown);report-to=x, usb=self, usb=()"#,
+        );
+        headers.append(PERMISSIONS_POLICY, "fullscreen=*");
+
+        let policy = PermissionsPolicy::from_headers(headers)?.unwrap();
+        assert_eq!(
+            policy.allowlist("camera"),
+            Some(
+                &[
+                    Allowlist::SameOrigin,
+                    Allowlist::Origin("https://a.example".into())
+                ][..]
+            )
+        );
+        assert_eq!(policy.allowlist("usb"), Some(&[][..]));
+        assert_eq!(policy.allowlist("fullscreen"), Some(&[Allowlist::All][..]));
+        assert_eq!(policy.features().count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = ["camera", "Camera=()", "camera=(self", r#"camera=("a)"#];
+        for value in &values {
+            let mut headers = Headers::new();
+            headers.insert(PERMISSIONS_POLICY, *value);
+            let err = PermissionsPolicy::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use crate::headers::{HeaderName, HeaderValue, Headers, ToHeaderValues, REPORTING_ENDPOINTS};
+use crate::utils::{is_key, is_printable, quote, split_quoted, unquote};
+use crate::{Error, StatusCode};
+
+use std::option;
+
+/// Name the endpoints browsers send reports to, such as CSP violation
+/// reports.
+///
+/// Endpoints are referred to by name from other headers, for example with
+/// [`ContentSecurityPolicy::report_to_endpoint`]. This header replaces the
+/// deprecated `Report-To` header.
+///
+/// [`ContentSecurityPolicy::report_to_endpoint`]: struct.ContentSecurityPolicy.html#method.report_to_endpoint
+///
+/// # Specifications
+///
+/// - [Reporting API, section 3.1: The Reporting-Endpoints HTTP Response Header Field](https://w3c.github.io/reporting/#header)
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> http_types::Result<()> {
+/// #
+/// use http_types::Response;
+/// use http_types::security::{ContentSecurityPolicy, ReportingEndpoints};
+///
+/// let mut endpoints = ReportingEndpoints::new();
+/// endpoints.insert("csp-endpoint", "https://example.com/csp-reports");
+///
+/// let mut policy = ContentSecurityPolicy::new();
+/// policy.default_src("'self'").report_to_endpoint("csp-endpoint");
+///
+/// let mut res = Response::new(200);
+/// endpoints.apply(&mut res);
+/// policy.apply(&mut res);
+/// assert_eq!(
+///     res["Reporting-Endpoints"],
+///     r#"csp-endpoint="https://example.com/csp-reports""#
+/// );
+/// assert_eq!(
+///     res["Content-Security-Policy"],
+///     "default-src 'self'; report-to csp-endpoint"
+/// );
+///
+/// let endpoints = ReportingEndpoints::from_headers(res)?.unwrap();
+/// assert_eq!(endpoints.get("csp-endpoint"), Some("https://example.com/csp-reports"));
+/// #
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct ReportingEndpoints {
+    endpoints: Vec<(String, String)>,
+}
+
+impl ReportingEndpoints {
+    /// Create a new instance of `ReportingEndpoints`.
+    pub fn new() -> Self {
+        Self { endpoints: vec![] }
+    }
+
+    /// Create a new instance from headers.
+    ///
+    /// If an endpoint is named more than once, the last URL is used.
+    /// Parameters are skipped.
+    ///
+    /// # Errors
+    ///
+    /// An error with a status code of `400: Bad Request` is returned if the
+    /// header is malformed.
+    pub fn from_headers(headers: impl AsRef<Headers>) -> crate::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(REPORTING_ENDPOINTS) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut output = Self::new();
+        for value in headers {
+            for member in split_quoted(value.as_str(), ',') {
+                let invalid = || {
+                    let msg = format!("Invalid Reporting-Endpoints member: {}", member);
+                    Error::from_str(StatusCode::BadRequest, msg)
+                };
+                let index = member.find('=').ok_or_else(invalid)?;
+                let (name, url) = (&member[..index], &member[index + 1..]);
+                // Parameters follow the URL, separated by semicolons.
+                let url = split_quoted(url, ';').into_iter().next();
+                let url = match url {
+                    Some(url) if is_key(name) && url.starts_with('"') => {
+                        unquote(url).ok_or_else(invalid)?
+                    }
+                    _ => return Err(invalid()),
+                };
+                if !is_printable(&url) {
+                    return Err(invalid());
+                }
+                output.insert(name, url);
+            }
+        }
+        Ok(Some(output))
+    }
+
+    /// Sets the `Reporting-Endpoints` header.
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(REPORTING_ENDPOINTS, self.value());
+    }
+
+    /// Get the `HeaderName`.
+    pub fn name(&self) -> HeaderName {
+        REPORTING_ENDPOINTS
+    }
+
+    /// Get the `HeaderValue`.
+    pub fn value(&self) -> HeaderValue {
+        let output = self
+            .endpoints
+            .iter()
+            .map(|(name, url)| format!("{}={}", name, quote(url)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // SAFETY: the internal string is validated to be ASCII.
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    /// Add an endpoint, replacing the URL of an endpoint with the same name.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the name isn't a lowercase structured field key,
+    /// or if the URL contains characters other than printable ASCII.
+    pub fn insert(&mut self, name: impl Into<String>, url: impl Into<String>) {
+        let (name, url) = (name.into(), url.into());
+        assert!(is_key(&name), "Invalid reporting endpoint name: {}", name);
+        assert!(
+            is_printable(&url),
+            "Reporting endpoint URLs should be printable ASCII"
+        );
+        match self.endpoints.iter_mut().find(|(n, _)| *n == name) {
+            Some(endpoint) => endpoint.1 = url,
+            None => self.endpoints.push((name, url)),
+        }
+    }
+
+    /// Get the URL of an endpoint.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.endpoints
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, url)| url.as_str())
+    }
+
+    /// Remove an endpoint, returning its URL if it was set.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let index = self.endpoints.iter().position(|(n, _)| n == name)?;
+        Some(self.endpoints.remove(index).1)
+    }
+
+    /// An iterator visiting all endpoint names and URLs, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.endpoints
+            .iter()
+            .map(|(name, url)| (name.as_str(), url.as_str()))
+    }
+
+    /// Returns `true` if no endpoints have been set.
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+}
+
+impl ToHeaderValues for ReportingEndpoints {
+    type Iter = option::IntoIter<HeaderValue>;
+    fn to_header_values(&self) -> crate::Result<Self::Iter> {
+        // A HeaderValue will always convert into itself.
+        Ok(self.value().to_header_values().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headers::Headers;
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let mut headers = Headers::new();
+        headers.insert(
+            REPORTING_ENDPOINTS,
+            r#"default="/reports";priority=1, csp="https://a.example/csp""#,
+        );
+        headers.append(REPORTING_ENDPOINTS, r#"default="https://b.example/""#);
+
+        let endpoints = ReportingEndpoints::from_headers(headers)?.unwrap();
+        let entries: Vec<_> = endpoints.iter().collect();
+        assert_eq!(
+            entries,
+            [
+                ("default", "https://b.example/"),
+                ("csp", "https://a.example/csp")
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_request_on_parse_error() {
+        let values = [
+            "default",
+            "Default=\"/reports\"",
+            "default=/reports",
+            "default=\"/reports",
+        ];
+        for value in &values {
+            let mut headers = Headers::new();
+            headers.insert(REPORTING_ENDPOINTS, *value);
+            let err = ReportingEndpoints::from_headers(headers).unwrap_err();
+            assert_eq!(err.status(), 400);
+        }
+    }
+}
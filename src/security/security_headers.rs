@@ -0,0 +1,380 @@
+use crate::headers::{
+    CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY_REPORT_ONLY, STRICT_TRANSPORT_SECURITY,
+};
+use crate::security::{
+    frameguard, nosniff, powered_by, referrer_policy, ContentSecurityPolicy, FrameOptions, Hsts,
+    ReferrerOptions, Source,
+};
+use crate::Response;
+
+use std::fmt::{self, Display};
+
+/// A configurable set of security headers.
+///
+/// By default this sets `X-Frame-Options: sameorigin`,
+/// `Referrer-Policy: no-referrer`, a 60 day `Strict-Transport-Security` policy
+/// and `X-Content-Type-Options: nosniff`, and removes the `X-Powered-By`
+/// header. No `Content-Security-Policy` is set unless one is configured, as
+/// policies depend on the site.
+///
+/// # Examples
+///
+/// ```
+/// use http_types::security::{ContentSecurityPolicy, ReferrerOptions, SecurityHeaders, Source};
+/// use http_types::{Response, StatusCode};
+///
+/// let mut policy = ContentSecurityPolicy::new();
+/// policy.default_src(Source::SameOrigin);
+///
+/// let mut headers = SecurityHeaders::new();
+/// headers
+///     .set_referrer_policy(Some(ReferrerOptions::StrictCrossOrigin))
+///     .set_content_security_policy(Some(policy));
+///
+/// let mut res = Response::new(StatusCode::Ok);
+/// res.insert_header("X-Frame-Options", "deny");
+/// headers.apply_to(&mut res);
+///
+/// assert_eq!(res["X-Frame-Options"], "deny");
+/// assert_eq!(res["Referrer-Policy"], "strict-origin-when-cross-origin");
+/// assert_eq!(res["Content-Security-Policy"], "default-src 'self'");
+/// assert!(SecurityHeaders::audit(&res).is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SecurityHeaders {
+    frameguard: Option<FrameOptions>,
+    referrer_policy: Option<ReferrerOptions>,
+    hsts: Option<Hsts>,
+    content_security_policy: Option<ContentSecurityPolicy>,
+    nosniff: bool,
+    hide_powered_by: bool,
+}
+
+impl SecurityHeaders {
+    /// Create a new instance with the default set of headers.
+    pub fn new() -> Self {
+        Self {
+            frameguard: Some(FrameOptions::SameOrigin),
+            referrer_policy: Some(ReferrerOptions::NoReferrer),
+            hsts: Some(Hsts::new()),
+            content_security_policy: None,
+            nosniff: true,
+            hide_powered_by: true,
+        }
+    }
+
+    /// Set the headers on a response.
+    ///
+    /// Headers which the response already has are left as they are, so
+    /// handlers can override the defaults for individual responses. The
+    /// `X-Powered-By` header is always removed if that's configured.
+    pub fn apply_to(&self, res: &mut Response) {
+        if let Some(guard) = self.frameguard {
+            if res.header("X-Frame-Options").is_none() {
+                frameguard(&mut *res, Some(guard));
+            }
+        }
+        if let Some(referrer) = self.referrer_policy {
+            if res.header("Referrer-Policy").is_none() {
+                referrer_policy(&mut *res, Some(referrer));
+            }
+        }
+        if let Some(hsts) = &self.hsts {
+            if res.header(STRICT_TRANSPORT_SECURITY).is_none() {
+                hsts.apply(&mut *res);
+            }
+        }
+        if let Some(policy) = &self.content_security_policy {
+            if res.header(policy.name()).is_none() {
+                policy.apply(&mut *res);
+            }
+        }
+        if self.nosniff && res.header("X-Content-Type-Options").is_none() {
+            nosniff(&mut *res);
+        }
+        if self.hide_powered_by {
+            powered_by(&mut *res, None);
+        }
+    }
+
+    /// Check a response for missing or weak security headers.
+    ///
+    /// This doesn't depend on the configured headers, so it can be used to
+    /// check responses after [`apply_to`](#method.apply_to) has run.
+    pub fn audit(res: &Response) -> Vec<AuditFinding> {
+        let mut findings = vec![];
+
+        let policy = ContentSecurityPolicy::from_headers(res).ok().flatten();
+        match &policy {
+            None => findings.push(AuditFinding::MissingContentSecurityPolicy),
+            Some(policy) => {
+                if policy.is_report_only() {
+                    findings.push(AuditFinding::ContentSecurityPolicyReportOnly);
+                }
+                for name in &["script-src", "style-src"] {
+                    let sources = match policy.sources(name) {
+                        Some(sources) => sources,
+                        None => match policy.sources("default-src") {
+                            Some(sources) => sources,
+                            None => continue,
+                        },
+                    };
+                    // Browsers ignore 'unsafe-inline' if a nonce or hash is allowed.
+                    let has_nonce_or_hash = sources.iter().any(|source| {
+                        matches!(
+                            source,
+                            Source::Nonce(_)
+                                | Source::Sha256(_)
+                                | Source::Sha384(_)
+                                | Source::Sha512(_)
+                        )
+                    });
+                    if sources.contains(&Source::UnsafeInline) && !has_nonce_or_hash {
+                        findings.push(AuditFinding::UnsafeInline(name.to_string()));
+                    }
+                    if sources.contains(&Source::UnsafeEval) {
+                        findings.push(AuditFinding::UnsafeEval(name.to_string()));
+                    }
+                }
+            }
+        }
+
+        match Hsts::from_headers(res) {
+            Ok(Some(_)) => {}
+            Ok(None) => findings.push(AuditFinding::MissingStrictTransportSecurity),
+            Err(_) => findings.push(AuditFinding::InvalidStrictTransportSecurity),
+        }
+
+        // `frame-ancestors` supersedes `X-Frame-Options` in browsers which
+        // support it.
+        let frame_ancestors = policy
+            .as_ref()
+            .filter(|policy| !policy.is_report_only())
+            .and_then(|policy| policy.directive("frame-ancestors"))
+            .is_some();
+        if res.header("X-Frame-Options").is_none() && !frame_ancestors {
+            findings.push(AuditFinding::MissingFrameOptions);
+        }
+
+        let nosniff = res
+            .header("X-Content-Type-Options")
+            .map(|values| {
+                values
+                    .last()
+                    .as_str()
+                    .trim()
+                    .eq_ignore_ascii_case("nosniff")
+            })
+            .unwrap_or(false);
+        if !nosniff {
+            findings.push(AuditFinding::MissingContentTypeOptions);
+        }
+
+        if res.header("Referrer-Policy").is_none() {
+            findings.push(AuditFinding::MissingReferrerPolicy);
+        }
+        if res.header("X-Powered-By").is_some() {
+            findings.push(AuditFinding::PoweredByExposed);
+        }
+        findings
+    }
+
+    /// Get the `X-Frame-Options` level, if it's set.
+    pub fn frameguard(&self) -> Option<FrameOptions> {
+        self.frameguard
+    }
+
+    /// Set the `X-Frame-Options` level, or `None` to not set the header.
+    pub fn set_frameguard(&mut self, guard: Option<FrameOptions>) -> &mut Self {
+        self.frameguard = guard;
+        self
+    }
+
+    /// Get the `Referrer-Policy`, if it's set.
+    pub fn referrer_policy(&self) -> Option<ReferrerOptions> {
+        self.referrer_policy
+    }
+
+    /// Set the `Referrer-Policy`, or `None` to not set the header.
+    pub fn set_referrer_policy(&mut self, referrer: Option<ReferrerOptions>) -> &mut Self {
+        self.referrer_policy = referrer;
+        self
+    }
+
+    /// Get the `Strict-Transport-Security` policy, if it's set.
+    pub fn hsts(&self) -> Option<&Hsts> {
+        self.hsts.as_ref()
+    }
+
+    /// Set the `Strict-Transport-Security` policy, or `None` to not set the
+    /// header.
+    pub fn set_hsts(&mut self, hsts: Option<Hsts>) -> &mut Self {
+        self.hsts = hsts;
+        self
+    }
+
+    /// Get the `Content-Security-Policy`, if it's set.
+    pub fn content_security_policy(&self) -> Option<&ContentSecurityPolicy> {
+        self.content_security_policy.as_ref()
+    }
+
+    /// Set the `Content-Security-Policy`, or `None` to not set the header.
+    ///
+    /// Report-only policies are set in the
+    /// `Content-Security-Policy-Report-Only` header.
+    pub fn set_content_security_policy(
+        &mut self,
+        policy: Option<ContentSecurityPolicy>,
+    ) -> &mut Self {
+        self.content_security_policy = policy;
+        self
+    }
+
+    /// Returns `true` if `X-Content-Type-Options: nosniff` is set.
+    pub fn nosniff(&self) -> bool {
+        self.nosniff
+    }
+
+    /// Set whether `X-Content-Type-Options: nosniff` is set.
+    pub fn set_nosniff(&mut self, nosniff: bool) -> &mut Self {
+        self.nosniff = nosniff;
+        self
+    }
+
+    /// Returns `true` if the `X-Powered-By` header is removed.
+    pub fn hide_powered_by(&self) -> bool {
+        self.hide_powered_by
+    }
+
+    /// Set whether the `X-Powered-By` header is removed.
+    pub fn set_hide_powered_by(&mut self, hide_powered_by: bool) -> &mut Self {
+        self.hide_powered_by = hide_powered_by;
+        self
+    }
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A missing or weak security header found by
+/// [`SecurityHeaders::audit`](struct.SecurityHeaders.html#method.audit).
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum AuditFinding {
+    /// No `Content-Security-Policy` header is set.
+    MissingContentSecurityPolicy,
+    /// The `Content-Security-Policy` is only reported, and not enforced.
+    ContentSecurityPolicyReportOnly,
+    /// The directive allows inline scripts or styles with `'unsafe-inline'`.
+    UnsafeInline(String),
+    /// The directive allows `eval()` and similar with `'unsafe-eval'`.
+    UnsafeEval(String),
+    /// No `Strict-Transport-Security` header is set.
+    MissingStrictTransportSecurity,
+    /// The `Strict-Transport-Security` header is malformed, so browsers ignore
+    /// it.
+    InvalidStrictTransportSecurity,
+    /// Neither `X-Frame-Options` nor the `frame-ancestors` directive is set,
+    /// so the page can be framed by any site.
+    MissingFrameOptions,
+    /// `X-Content-Type-Options: nosniff` is not set.
+    MissingContentTypeOptions,
+    /// No `Referrer-Policy` header is set.
+    MissingReferrerPolicy,
+    /// The `X-Powered-By` header reveals the server's technology.
+    PoweredByExposed,
+}
+
+impl Display for AuditFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingContentSecurityPolicy => {
+                write!(f, "missing {} header", CONTENT_SECURITY_POLICY)
+            }
+            Self::ContentSecurityPolicyReportOnly => write!(
+                f,
+                "{} is set, but the policy isn't enforced",
+                CONTENT_SECURITY_POLICY_REPORT_ONLY
+            ),
+            Self::UnsafeInline(directive) => write!(f, "{} allows 'unsafe-inline'", directive),
+            Self::UnsafeEval(directive) => write!(f, "{} allows 'unsafe-eval'", directive),
+            Self::MissingStrictTransportSecurity => {
+                write!(f, "missing {} header", STRICT_TRANSPORT_SECURITY)
+            }
+            Self::InvalidStrictTransportSecurity => {
+                write!(f, "invalid {} header", STRICT_TRANSPORT_SECURITY)
+            }
+            Self::MissingFrameOptions => {
+                f.write_str("missing x-frame-options header or frame-ancestors directive")
+            }
+            Self::MissingContentTypeOptions => {
+                f.write_str("missing x-content-type-options: nosniff")
+            }
+            Self::MissingReferrerPolicy => f.write_str("missing referrer-policy header"),
+            Self::PoweredByExposed => f.write_str("x-powered-by header is exposed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::StatusCode;
+
+    #[test]
+    fn apply_to() {
+        let mut res = Response::new(StatusCode::Ok);
+        res.insert_header("Strict-Transport-Security", "max-age=31536000");
+        res.insert_header("X-Powered-By", "Tide");
+        SecurityHeaders::new().apply_to(&mut res);
+
+        assert_eq!(res["X-Frame-Options"], "sameorigin");
+        assert_eq!(res["Referrer-Policy"], "no-referrer");
+        assert_eq!(res["Strict-Transport-Security"], "max-age=31536000");
+        assert_eq!(res["X-Content-Type-Options"], "nosniff");
+        assert!(res.header("X-Powered-By").is_none());
+        assert!(res.header("Content-Security-Policy").is_none());
+
+        let mut res = Response::new(StatusCode::Ok);
+        let mut headers = SecurityHeaders::new();
+        headers
+            .set_frameguard(None)
+            .set_hsts(None)
+            .set_nosniff(false);
+        headers.apply_to(&mut res);
+        assert_eq!(res.iter().count(), 1);
+    }
+
+    #[test]
+    fn audit() {
+        let mut res = Response::new(StatusCode::Ok);
+        res.insert_header("X-Powered-By", "Tide");
+        res.insert_header("Strict-Transport-Security", "includeSubDomains");
+        res.insert_header(
+            "Content-Security-Policy-Report-Only",
+            "default-src 'self' 'unsafe-inline'; style-src 'unsafe-inline' 'nonce-abc'; frame-ancestors 'none'",
+        );
+        assert_eq!(
+            SecurityHeaders::audit(&res),
+            [
+                AuditFinding::ContentSecurityPolicyReportOnly,
+                AuditFinding::UnsafeInline("script-src".into()),
+                AuditFinding::InvalidStrictTransportSecurity,
+                AuditFinding::MissingFrameOptions,
+                AuditFinding::MissingContentTypeOptions,
+                AuditFinding::MissingReferrerPolicy,
+                AuditFinding::PoweredByExposed,
+            ]
+        );
+
+        let res = Response::new(StatusCode::Ok);
+        assert_eq!(SecurityHeaders::audit(&res).len(), 5);
+        assert_eq!(
+            AuditFinding::UnsafeEval("script-src".into()).to_string(),
+            "script-src allows 'unsafe-eval'"
+        );
+    }
+}
@@ -9,3 +9,221 @@ macro_rules! cfg_unstable {
         )*
     }
 }
+
+use crate::headers::{Headers, VARY};
+use crate::{Error, StatusCode};
+
+/// Split a header value on `separator`, ignoring separators within quoted
+/// strings. Empty items are skipped and every item is trimmed.
+pub(crate) fn split_quoted(s: &str, separator: char) -> Vec<&str> {
+    let mut items = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                items.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    items.push(&s[start..]);
+    items
+        .into_iter()
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Parse the value of a `q` parameter into a weight between 0 and 1.
+pub(crate) fn parse_weight(s: &str) -> crate::Result<f32> {
+    match s.trim().parse::<f32>() {
+        Ok(weight) if (0.0..=1.0).contains(&weight) => Ok(weight),
+        _ => {
+            let msg = format!("{} is not a valid weight", s);
+            Err(Error::from_str(StatusCode::BadRequest, msg))
+        }
+    }
+}
+
+/// Returns `true` if the string is a non-empty HTTP token.
+pub(crate) fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+/// Returns `true` if the string only contains printable ASCII characters.
+pub(crate) fn is_printable(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_graphic() || b == b' ')
+}
+
+/// Returns `true` if the string is a valid structured field key, as used in
+/// dictionaries and parameters.
+pub(crate) fn is_key(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    matches!(bytes.next(), Some(b) if b.is_ascii_lowercase() || b == b'*')
+        && bytes.all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"_-.*".contains(&b))
+}
+
+/// Unescape a quoted string, returning `None` if it isn't quoted.
+pub(crate) fn unquote(s: &str) -> Option<String> {
+    let s = s.trim();
+    if s.len() < 2 || !s.starts_with('"') || !s.ends_with('"') {
+        return None;
+    }
+
+    let mut output = String::with_capacity(s.len() - 2);
+    let mut chars = s[1..s.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => output.push(chars.next()?),
+            '"' => return None,
+            c => output.push(c),
+        }
+    }
+    Some(output)
+}
+
+/// Encode a string as a quoted string.
+pub(crate) fn quote(s: &str) -> String {
+    let mut output = String::with_capacity(s.len() + 2);
+    output.push('"');
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+    output.push('"');
+    output
+}
+
+/// Returns `true` if the byte may appear unencoded in an RFC 8187 `ext-value`.
+fn is_attr_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b)
+}
+
+/// Decode an RFC 8187 `ext-value`, such as `UTF-8'en'%e2%82%ac%20rates`.
+///
+/// The language tag is ignored. Returns `None` if the value is malformed or
+/// uses a charset other than UTF-8 or ISO-8859-1.
+pub(crate) fn decode_ext_value(s: &str) -> Option<String> {
+    let mut parts = s.trim().splitn(3, '\'');
+    let (charset, _language, value) = (parts.next()?, parts.next()?, parts.next()?);
+
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+    while let Some(b) = iter.next() {
+        match b {
+            b'%' => {
+                let hex = [iter.next()?, iter.next()?];
+                let hex = std::str::from_utf8(&hex).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            }
+            b if is_attr_char(b) => bytes.push(b),
+            _ => return None,
+        }
+    }
+
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes).ok()
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        Some(bytes.into_iter().map(char::from).collect())
+    } else {
+        None
+    }
+}
+
+/// Encode a string as an RFC 8187 `ext-value` using the UTF-8 charset.
+pub(crate) fn encode_ext_value(s: &str) -> String {
+    let mut output = String::from("UTF-8''");
+    for b in s.bytes() {
+        if is_attr_char(b) {
+            output.push(b as char);
+        } else {
+            output.push_str(&format!("%{:02X}", b));
+        }
+    }
+    output
+}
+
+/// Add a header name to the `Vary` header, unless it's already listed or the
+/// response varies on `*`.
+pub(crate) fn append_vary(headers: &mut Headers, name: &str) {
+    let varies = headers
+        .get(VARY)
+        .into_iter()
+        .flat_map(|values| values.iter())
+        .flat_map(|value| value.as_str().split(','))
+        .map(|item| item.trim())
+        .any(|item| item == "*" || item.eq_ignore_ascii_case(name));
+    if !varies {
+        headers.append(VARY, name);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split_quoted_ignores_quoted_separators() {
+        let items = split_quoted(r#"a, b="c, \"d\"", , e"#, ',');
+        assert_eq!(items, vec!["a", r#"b="c, \"d\"""#, "e"]);
+    }
+
+    #[test]
+    fn parse_weight_bounds() {
+        assert_eq!(parse_weight("0.5").unwrap(), 0.5);
+        assert_eq!(parse_weight("1").unwrap(), 1.0);
+        assert_eq!(parse_weight("1.5").unwrap_err().status(), 400);
+        assert_eq!(parse_weight("nori").unwrap_err().status(), 400);
+    }
+
+    #[test]
+    fn quoted_strings() {
+        assert_eq!(unquote(r#""a \"b\" c""#).unwrap(), r#"a "b" c"#);
+        assert_eq!(unquote("abc"), None);
+        assert_eq!(unquote(r#""a"b""#), None);
+        assert_eq!(quote(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert!(is_token("no-cache"));
+        assert!(!is_token("a b"));
+    }
+
+    #[test]
+    fn append_vary_skips_listed_names() {
+        let mut headers = Headers::new();
+        append_vary(&mut headers, "Origin");
+        append_vary(&mut headers, "origin");
+        append_vary(&mut headers, "Accept-Encoding");
+        assert_eq!(headers[VARY], ["Origin", "Accept-Encoding"][..]);
+
+        let mut headers = Headers::new();
+        headers.insert(VARY, "*");
+        append_vary(&mut headers, "Origin");
+        assert_eq!(headers[VARY], "*");
+    }
+
+    #[test]
+    fn ext_values() {
+        let value = decode_ext_value("UTF-8'en'%e2%82%ac%20rates").unwrap();
+        assert_eq!(value, "€ rates");
+        assert_eq!(
+            decode_ext_value("iso-8859-1''%A3%20rates").unwrap(),
+            "£ rates"
+        );
+        assert_eq!(decode_ext_value("UTF-8''a b"), None);
+        assert_eq!(decode_ext_value("KOI8-R''abc"), None);
+        assert_eq!(decode_ext_value("abc"), None);
+
+        assert_eq!(encode_ext_value("€ rates"), "UTF-8''%E2%82%AC%20rates");
+        let roundtrip = decode_ext_value(&encode_ext_value("naïve \"file\".txt"));
+        assert_eq!(roundtrip.unwrap(), "naïve \"file\".txt");
+    }
+}
@@ -0,0 +1,82 @@
+use http_types::{Cookie, Method, Request, Response, StatusCode, Url};
+
+#[test]
+fn jar_delta_becomes_set_cookie_headers() {
+    let mut req = Request::new(Method::Get, Url::parse("https://example.com").unwrap());
+    req.insert_header("Cookie", "name=chashu; color=orange");
+
+    let jar = req.cookie_jar();
+    jar.add(Cookie::new("age", "5"));
+    jar.remove(Cookie::named("color"));
+    let jar = jar.clone();
+
+    let mut res = Response::new(StatusCode::Ok);
+    res.set_cookie_jar(jar);
+    res.finalize_cookies();
+
+    let mut cookies: Vec<_> = res["Set-Cookie"]
+        .iter()
+        .map(|value| Cookie::parse(value.as_str().to_owned()).unwrap())
+        .collect();
+    cookies.sort_by(|a, b| a.name().cmp(b.name()));
+
+    assert_eq!(cookies.len(), 2);
+    assert_eq!(cookies[0].name_value(), ("age", "5"));
+    assert_eq!(cookies[1].name_value(), ("color", ""));
+    assert!(cookies[1].expires().is_some());
+}
+
+#[test]
+fn finalize_cookies_is_idempotent() {
+    let mut res = Response::new(StatusCode::Ok);
+    res.cookie_jar().add(Cookie::new("name", "chashu"));
+    res.finalize_cookies();
+    res.finalize_cookies();
+    assert_eq!(res["Set-Cookie"].iter().count(), 1);
+}
+
+#[cfg(feature = "cookie-secure")]
+mod secure {
+    use super::*;
+    use http_types::cookies::Key;
+
+    fn request_with_cookies(res: &Response) -> Request {
+        let mut req = Request::new(Method::Get, Url::parse("https://example.com").unwrap());
+        for value in res["Set-Cookie"].iter() {
+            let cookie = Cookie::parse(value.as_str().to_owned()).unwrap();
+            let (name, value) = cookie.name_value();
+            req.append_header("Cookie", format!("{}={}", name, value));
+        }
+        req
+    }
+
+    #[test]
+    fn signed_cookie_roundtrip() {
+        let key = Key::generate();
+        let mut res = Response::new(StatusCode::Ok);
+        res.insert_signed_cookie(&key, Cookie::new("name", "chashu"));
+        res.finalize_cookies();
+        assert_ne!(res["Set-Cookie"], "name=chashu");
+
+        let mut req = request_with_cookies(&res);
+        let cookie = req.signed_cookie(&key, "name").unwrap();
+        assert_eq!(cookie.value(), "chashu");
+
+        let other_key = Key::generate();
+        let mut req = request_with_cookies(&res);
+        assert!(req.signed_cookie(&other_key, "name").is_none());
+    }
+
+    #[test]
+    fn private_cookie_roundtrip() {
+        let key = Key::generate();
+        let mut res = Response::new(StatusCode::Ok);
+        res.insert_private_cookie(&key, Cookie::new("name", "chashu"));
+        res.finalize_cookies();
+        assert!(!res["Set-Cookie"].as_str().contains("chashu"));
+
+        let mut req = request_with_cookies(&res);
+        let cookie = req.private_cookie(&key, "name").unwrap();
+        assert_eq!(cookie.value(), "chashu");
+    }
+}